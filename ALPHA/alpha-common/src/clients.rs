@@ -0,0 +1,81 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Per-service client constructors.
+
+#[allow(unused_imports)]
+use crate::Shared;
+
+/// Generates, for each service, a method returning a client and a method
+/// returning a config builder that callers can customize further.
+/// Both apply the endpoint override, if one was given, and the client
+/// sends its requests through the recording or replaying connector, if any.
+macro_rules! service_clients {
+    ($($feature:literal => $krate:ident: $client:ident, $builder:ident;)*) => {
+        impl Shared {
+            $(
+                #[cfg(feature = $feature)]
+                #[doc = concat!("Returns a `", stringify!($krate), "` config builder prepopulated from the shared configuration.")]
+                pub fn $builder(&self) -> $krate::config::Builder {
+                    let builder = $krate::config::Builder::from(&self.config);
+                    match &self.endpoint_url {
+                        Some(uri) => builder.endpoint_resolver($krate::Endpoint::immutable(uri.clone())),
+                        None => builder,
+                    }
+                }
+
+                #[cfg(feature = $feature)]
+                #[doc = concat!("Creates a `", stringify!($krate), "` client from the shared configuration.")]
+                pub fn $client(&self) -> $krate::Client {
+                    let conf = self.$builder().build();
+                    match self.connector_override() {
+                        Some(connector) => $krate::Client::from_conf_conn(conf, connector),
+                        None => $krate::Client::from_conf(conf),
+                    }
+                }
+            )*
+        }
+    };
+}
+
+service_clients! {
+    "apigateway" => aws_sdk_apigateway: apigateway, apigateway_config;
+    "applicationautoscaling" => aws_sdk_applicationautoscaling: applicationautoscaling, applicationautoscaling_config;
+    "autoscaling" => aws_sdk_autoscaling: autoscaling, autoscaling_config;
+    "batch" => aws_sdk_batch: batch, batch_config;
+    "cloudformation" => aws_sdk_cloudformation: cloudformation, cloudformation_config;
+    "cognitoidentity" => aws_sdk_cognitoidentity: cognitoidentity, cognitoidentity_config;
+    "cognitoidentityprovider" => aws_sdk_cognitoidentityprovider: cognitoidentityprovider, cognitoidentityprovider_config;
+    "cognitosync" => aws_sdk_cognitosync: cognitosync, cognitosync_config;
+    "config" => aws_sdk_config: config, config_config;
+    "dynamodb" => aws_sdk_dynamodb: dynamodb, dynamodb_config;
+    "dynamodbstreams" => aws_sdk_dynamodbstreams: dynamodbstreams, dynamodbstreams_config;
+    "ebs" => aws_sdk_ebs: ebs, ebs_config;
+    "ec2" => aws_sdk_ec2: ec2, ec2_config;
+    "iam" => aws_sdk_iam: iam, iam_config;
+    "iot" => aws_sdk_iot: iot, iot_config;
+    "kinesis" => aws_sdk_kinesis: kinesis, kinesis_config;
+    "kms" => aws_sdk_kms: kms, kms_config;
+    "lambda" => aws_sdk_lambda: lambda, lambda_config;
+    "medialive" => aws_sdk_medialive: medialive, medialive_config;
+    "mediapackage" => aws_sdk_mediapackage: mediapackage, mediapackage_config;
+    "polly" => aws_sdk_polly: polly, polly_config;
+    "qldb" => aws_sdk_qldb: qldb, qldb_config;
+    "qldbsession" => aws_sdk_qldbsession: qldbsession, qldbsession_config;
+    "rds" => aws_sdk_rds: rds, rds_config;
+    "rdsdata" => aws_sdk_rdsdata: rdsdata, rdsdata_config;
+    "rekognition" => aws_sdk_rekognition: rekognition, rekognition_config;
+    "route53" => aws_sdk_route53: route53, route53_config;
+    "s3" => aws_sdk_s3: s3, s3_config;
+    "sagemaker" => aws_sdk_sagemaker: sagemaker, sagemaker_config;
+    "secretsmanager" => aws_sdk_secretsmanager: secretsmanager, secretsmanager_config;
+    "ses" => aws_sdk_ses: ses, ses_config;
+    "snowball" => aws_sdk_snowball: snowball, snowball_config;
+    "sns" => aws_sdk_sns: sns, sns_config;
+    "sqs" => aws_sdk_sqs: sqs, sqs_config;
+    "ssm" => aws_sdk_ssm: ssm, ssm_config;
+    "sts" => aws_sdk_sts: sts, sts_config;
+    "transcribestreaming" => aws_sdk_transcribestreaming: transcribestreaming, transcribestreaming_config;
+}
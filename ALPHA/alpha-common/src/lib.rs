@@ -0,0 +1,52 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Command-line options and client bootstrap shared by the ALPHA code examples.
+//!
+//! Every example flattens [`CommonOpt`] into its own `Opt` struct, calls
+//! [`CommonOpt::load`] once, and then asks the resulting [`Shared`] for the
+//! service client it needs:
+//!
+//! ```no_run
+//! use alpha_common::CommonOpt;
+//! use structopt::StructOpt;
+//!
+//! #[derive(Debug, StructOpt)]
+//! struct Opt {
+//!     /// The name of the table.
+//!     #[structopt(short, long)]
+//!     table: String,
+//!
+//!     #[structopt(flatten)]
+//!     common: CommonOpt,
+//! }
+//!
+//! # async fn run() {
+//! let Opt { table, common } = Opt::from_args();
+//! let shared = common.load().await;
+//! shared.banner("DynamoDB", "0.0.0", &[("Table", &table)]);
+//! # }
+//! ```
+//!
+//! To run an example against a local stand-in for a service, such as
+//! DynamoDB Local, MinIO, or LocalStack, pass `--endpoint-url http://localhost:PORT`
+//! or set the **AWS_ENDPOINT_URL** environment variable.
+//! Every client created by [`Shared`] sends its requests there.
+//! The emulator still expects signed requests, so some credentials,
+//! even dummy ones, must be available.
+
+mod clients;
+mod opt;
+pub mod output;
+pub mod pagination;
+mod shared;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "testing")]
+pub mod traffic;
+pub mod waiter;
+
+pub use opt::{CommonOpt, OutputFormat, ENDPOINT_URL_ENV};
+pub use shared::Shared;
@@ -0,0 +1,221 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use std::env;
+use std::ffi::OsString;
+use std::fmt;
+use std::str::FromStr;
+#[cfg(feature = "testing")]
+use std::sync::Mutex;
+
+use aws_config::default_provider::{credentials, region};
+use aws_config::meta::region::RegionProviderChain;
+use aws_types::region::Region;
+use http::Uri;
+use structopt::StructOpt;
+
+#[cfg(feature = "testing")]
+use crate::traffic::Traffic;
+use crate::Shared;
+
+/// The Region used when none is given on the command line, in the environment, or in a profile.
+const DEFAULT_REGION: &str = "us-west-2";
+
+/// The environment variable that sets `--endpoint-url` when the option is not given.
+pub const ENDPOINT_URL_ENV: &str = "AWS_ENDPOINT_URL";
+
+/// Options accepted by every code example.
+///
+/// Only `--region` and `--verbose` have short forms, because the examples
+/// already use most other letters for their own options.
+#[derive(Debug, StructOpt)]
+pub struct CommonOpt {
+    /// The AWS Region.
+    #[structopt(short, long)]
+    pub region: Option<String>,
+
+    /// The named profile to load credentials and Region from.
+    #[structopt(long)]
+    pub profile: Option<String>,
+
+    /// The endpoint to send requests to instead of the Region's default endpoint,
+    /// such as http://localhost:8000 for DynamoDB Local.
+    /// Defaults to the value of the AWS_ENDPOINT_URL environment variable.
+    #[structopt(long, parse(try_from_str = parse_endpoint_url))]
+    pub endpoint_url: Option<Uri>,
+
+    /// The output format: text, json, table, csv, or ndjson.
+    #[structopt(long, default_value = "text")]
+    pub output: OutputFormat,
+
+    /// Whether to display additional information.
+    #[structopt(short, long)]
+    pub verbose: bool,
+}
+
+impl CommonOpt {
+    /// Initializes tracing and loads the shared AWS configuration.
+    ///
+    /// The Region is taken from `--region`, then the default provider chain
+    /// (or the named profile, if `--profile` is set), and finally falls back to **us-west-2**.
+    pub async fn load(self) -> Shared {
+        tracing_subscriber::fmt::init();
+
+        let endpoint_url = endpoint_url_or_env(self.endpoint_url, env::var_os(ENDPOINT_URL_ENV))
+            .unwrap_or_else(|e| {
+                structopt::clap::Error::with_description(
+                    &e,
+                    structopt::clap::ErrorKind::InvalidValue,
+                )
+                .exit()
+            });
+
+        let chain = RegionProviderChain::first_try(self.region.map(Region::new));
+        let chain = match &self.profile {
+            Some(profile) => {
+                chain.or_else(region::Builder::default().profile_name(profile).build())
+            }
+            None => chain.or_default_provider(),
+        };
+        let region = chain
+            .or_else(Region::new(DEFAULT_REGION))
+            .region()
+            .await
+            .expect("the fallback Region is always set");
+
+        let mut loader = aws_config::from_env().region(region.clone());
+        if let Some(profile) = &self.profile {
+            loader = loader.credentials_provider(
+                credentials::DefaultCredentialsChain::builder()
+                    .profile_name(profile)
+                    .region(region.clone())
+                    .build()
+                    .await,
+            );
+        }
+
+        Shared {
+            config: loader.load().await,
+            region,
+            endpoint_url,
+            output: self.output,
+            verbose: self.verbose,
+            raw_stdout: false,
+            #[cfg(feature = "testing")]
+            traffic: Mutex::new(Traffic::from_env()),
+        }
+    }
+}
+
+/// Parses an endpoint URL, which must be absolute so that requests know
+/// both the scheme and the host to connect to.
+fn parse_endpoint_url(s: &str) -> Result<Uri, String> {
+    let uri: Uri = s
+        .parse()
+        .map_err(|e| format!("invalid endpoint URL '{}': {}", s, e))?;
+    match (uri.scheme_str(), uri.host()) {
+        (Some("http"), Some(_)) | (Some("https"), Some(_)) => Ok(uri),
+        _ => Err(format!(
+            "invalid endpoint URL '{}': expected http://HOST[:PORT] or https://HOST[:PORT]",
+            s
+        )),
+    }
+}
+
+/// The endpoint URL from `--endpoint-url`, or else from `env`, the value of
+/// the **AWS_ENDPOINT_URL** environment variable. An empty value counts as unset.
+fn endpoint_url_or_env(flag: Option<Uri>, env: Option<OsString>) -> Result<Option<Uri>, String> {
+    match (flag, env) {
+        (Some(uri), _) => Ok(Some(uri)),
+        (None, Some(value)) if !value.is_empty() => parse_endpoint_url(&value.to_string_lossy())
+            .map(Some)
+            .map_err(|e| format!("{} in {}", e, ENDPOINT_URL_ENV)),
+        (None, _) => Ok(None),
+    }
+}
+
+/// How an example prints its results.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable lines, as the examples have always printed.
+    Text,
+    /// One JSON document on stdout.
+    Json,
+    /// Aligned columns.
+    Table,
+    /// Comma-separated values under a header line.
+    Csv,
+    /// One JSON object per line.
+    Ndjson,
+}
+
+impl OutputFormat {
+    /// Whether stdout is meant for another program, so messages go to stderr.
+    pub fn is_machine_readable(self) -> bool {
+        matches!(
+            self,
+            OutputFormat::Json | OutputFormat::Csv | OutputFormat::Ndjson
+        )
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "table" => Ok(OutputFormat::Table),
+            "csv" => Ok(OutputFormat::Csv),
+            "ndjson" => Ok(OutputFormat::Ndjson),
+            other => Err(format!(
+                "unknown output format '{}' (expected text, json, table, csv, or ndjson)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OutputFormat::Text => "text",
+            OutputFormat::Json => "json",
+            OutputFormat::Table => "table",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Ndjson => "ndjson",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endpoint_url_must_be_absolute() {
+        let uri = parse_endpoint_url("http://localhost:8000").unwrap();
+        assert_eq!(uri.host(), Some("localhost"));
+        assert_eq!(uri.port_u16(), Some(8000));
+
+        assert!(parse_endpoint_url("https://s3.example.com").is_ok());
+        assert!(parse_endpoint_url("localhost:8000").is_err());
+        assert!(parse_endpoint_url("ftp://localhost").is_err());
+    }
+
+    #[test]
+    fn endpoint_url_falls_back_to_environment() {
+        let env = Some(OsString::from("http://localhost:4566"));
+        let from_env = endpoint_url_or_env(None, env.clone()).unwrap();
+        let flag = parse_endpoint_url("http://localhost:9000").unwrap();
+        let from_flag = endpoint_url_or_env(Some(flag), env).unwrap();
+
+        assert_eq!(from_env.unwrap().port_u16(), Some(4566));
+        assert_eq!(from_flag.unwrap().port_u16(), Some(9000));
+        assert_eq!(endpoint_url_or_env(None, Some(OsString::new())), Ok(None));
+        assert!(endpoint_url_or_env(None, Some(OsString::from("localhost:4566"))).is_err());
+    }
+}
@@ -0,0 +1,234 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Rendering of list and describe results for `--output json`, `table`, `csv`, and `ndjson`.
+//!
+//! An example collects its results into a [`Listing`], one row per resource,
+//! and hands it to [`Shared::print`](crate::Shared::print) together with a
+//! closure that prints the example's usual text.
+
+use aws_smithy_types::date_time::Format;
+use aws_smithy_types::DateTime;
+use serde_json::{Map, Value};
+
+/// Rows of results with named columns.
+///
+/// Cells are JSON values, so numbers and booleans keep their types in JSON
+/// output; a missing value is `Value::Null`. `Option`s convert directly:
+///
+/// ```
+/// use alpha_common::output::Listing;
+///
+/// let mut listing = Listing::new(&["Name", "Size"]);
+/// listing.push(vec![Some("notes.txt").into(), 42.into()]);
+/// listing.push(vec![None::<&str>.into(), 0.into()]);
+///
+/// assert_eq!(listing.to_json()[0]["Name"], "notes.txt");
+/// assert!(listing.to_json()[1]["Name"].is_null());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Listing {
+    columns: Vec<String>,
+    rows: Vec<Vec<Value>>,
+}
+
+impl Listing {
+    /// Creates an empty listing with the given column names.
+    pub fn new(columns: &[&str]) -> Self {
+        Listing {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Appends a row, which must have one cell per column.
+    pub fn push(&mut self, row: Vec<Value>) {
+        assert_eq!(
+            row.len(),
+            self.columns.len(),
+            "row has {} cells but the listing has columns {:?}",
+            row.len(),
+            self.columns
+        );
+        self.rows.push(row);
+    }
+
+    /// The number of rows.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Whether there are no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Returns the rows as a JSON array of objects, with keys in column order.
+    pub fn to_json(&self) -> Value {
+        Value::Array(
+            self.rows
+                .iter()
+                .map(|row| {
+                    let object: Map<String, Value> = self
+                        .columns
+                        .iter()
+                        .cloned()
+                        .zip(row.iter().cloned())
+                        .collect();
+                    Value::Object(object)
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns the rows as one JSON object per line, with keys in column order.
+    pub fn to_ndjson(&self) -> String {
+        let mut out = String::new();
+        if let Value::Array(rows) = self.to_json() {
+            for row in rows {
+                out.push_str(&row.to_string());
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    /// Returns the rows as CSV under a header line of the column names.
+    /// Cells are written as in [`to_table`](Listing::to_table).
+    pub fn to_csv(&self) -> String {
+        self.csv(true)
+    }
+
+    /// Returns the rows as CSV without the header line, to follow
+    /// the output of [`to_csv`](Listing::to_csv) for earlier rows.
+    pub fn to_csv_rows(&self) -> String {
+        self.csv(false)
+    }
+
+    fn csv(&self, header: bool) -> String {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        if header {
+            writer
+                .write_record(&self.columns)
+                .expect("writing to memory cannot fail");
+        }
+        for row in &self.rows {
+            writer
+                .write_record(row.iter().map(cell_text))
+                .expect("writing to memory cannot fail");
+        }
+        let bytes = writer.into_inner().expect("writing to memory cannot fail");
+        String::from_utf8(bytes).expect("cells are UTF-8")
+    }
+
+    /// Returns the rows as text columns under a header line,
+    /// each column as wide as its widest cell.
+    pub fn to_table(&self) -> String {
+        let cells: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(cell_text).collect())
+            .collect();
+        let widths: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                cells
+                    .iter()
+                    .map(|row| row[i].chars().count())
+                    .chain(std::iter::once(column.chars().count()))
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        let mut out = String::new();
+        let mut line = |texts: &mut dyn Iterator<Item = &str>| {
+            let padded: Vec<String> = texts
+                .zip(&widths)
+                .map(|(text, width)| format!("{:width$}", text, width = width))
+                .collect();
+            out.push_str(padded.join("  ").trim_end());
+            out.push('\n');
+        };
+        line(&mut self.columns.iter().map(String::as_str));
+        let rules: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+        line(&mut rules.iter().map(String::as_str));
+        for row in &cells {
+            line(&mut row.iter().map(String::as_str));
+        }
+        out
+    }
+}
+
+/// Formats a timestamp as an RFC 3339 string, or `null` if it is missing.
+pub fn date_time(value: Option<&DateTime>) -> Value {
+    value
+        .and_then(|dt| dt.fmt(Format::DateTime).ok())
+        .map(Value::from)
+        .unwrap_or(Value::Null)
+}
+
+/// How a cell appears in a table: strings without quotes, and nothing for null.
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(values) => values.iter().map(cell_text).collect::<Vec<_>>().join(", "),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_aligns_columns() {
+        let mut listing = Listing::new(&["Name", "Versions", "Created"]);
+        listing.push(vec!["alpha-examples".into(), 3.into(), Value::Null]);
+        listing.push(vec![
+            "logs".into(),
+            vec!["a", "b"].into(),
+            date_time(Some(&DateTime::from_secs(1_634_169_600))),
+        ]);
+
+        assert_eq!(
+            listing.to_table(),
+            "Name            Versions  Created\n\
+             --------------  --------  --------------------\n\
+             alpha-examples  3\n\
+             logs            a, b      2021-10-14T00:00:00Z\n"
+        );
+    }
+
+    #[test]
+    fn csv_quotes_cells_that_need_it() {
+        let mut listing = Listing::new(&["Name", "Tags", "Size"]);
+        listing.push(vec!["a, \"b\"".into(), vec!["x", "y"].into(), Value::Null]);
+
+        assert_eq!(
+            listing.to_csv(),
+            "Name,Tags,Size\n\"a, \"\"b\"\"\",\"x, y\",\n"
+        );
+        assert_eq!(listing.to_csv_rows(), "\"a, \"\"b\"\"\",\"x, y\",\n");
+        assert_eq!(
+            listing.to_ndjson(),
+            "{\"Name\":\"a, \\\"b\\\"\",\"Tags\":[\"x\",\"y\"],\"Size\":null}\n"
+        );
+    }
+
+    #[test]
+    fn json_keeps_column_order_and_types() {
+        let mut listing = Listing::new(&["Name", "Size", "Public"]);
+        listing.push(vec!["notes.txt".into(), 42.into(), false.into()]);
+
+        assert_eq!(
+            serde_json::to_string(&listing.to_json()).unwrap(),
+            r#"[{"Name":"notes.txt","Size":42,"Public":false}]"#
+        );
+    }
+}
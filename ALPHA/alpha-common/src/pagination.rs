@@ -0,0 +1,213 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Following continuation tokens through every page of a list operation.
+//!
+//! A list example flattens [`PageOpt`] into its `Opt` struct, and passes it to
+//! [`paginate`] with a closure that sends one request for a page:
+//!
+//! ```ignore
+//! use alpha_common::pagination::{paginate, Page};
+//!
+//! let names = paginate(&pages, move |start: Option<String>, limit| {
+//!     let request = client
+//!         .list_tables()
+//!         .set_exclusive_start_table_name(start)
+//!         .set_limit(limit);
+//!     async move {
+//!         request.send().await.map(|resp| {
+//!             Page::new(
+//!                 resp.table_names.unwrap_or_default(),
+//!                 resp.last_evaluated_table_name,
+//!             )
+//!         })
+//!     }
+//! })
+//! .try_collect()
+//! .await?;
+//! ```
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_util::stream::{self, BoxStream, Stream, StreamExt, TryStreamExt};
+use structopt::StructOpt;
+
+/// Command-line options that limit how much of a listing is fetched.
+#[derive(Clone, Debug, Default, StructOpt)]
+pub struct PageOpt {
+    /// The most items to list. Lists every item if not supplied.
+    #[structopt(long)]
+    pub max_items: Option<usize>,
+
+    /// The number of items to ask for in each request.
+    /// Uses the service's default page size if not supplied.
+    #[structopt(long)]
+    pub page_size: Option<i32>,
+}
+
+/// One response of a list operation: its items, and the token that
+/// asks for the next page, if there is one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Page<T, K> {
+    /// The items in the page.
+    pub items: Vec<T>,
+    /// Where the next page starts, or `None` on the last page.
+    pub next: Option<K>,
+}
+
+impl<T, K> Page<T, K> {
+    /// Creates a page of `items` followed by the page starting at `next`.
+    pub fn new(items: Vec<T>, next: Option<K>) -> Self {
+        Page { items, next }
+    }
+}
+
+impl<T> Page<T, String> {
+    /// Creates a page whose token is a string, treating an empty string,
+    /// which some services return on the last page, as no token.
+    pub fn with_token(items: Vec<T>, next: Option<String>) -> Self {
+        Page::new(items, next.filter(|token| !token.is_empty()))
+    }
+}
+
+/// The items of every page of a list operation, as an async [`Stream`].
+///
+/// Pages are requested only as the stream is polled, so stopping early,
+/// as `--max-items` does, sends no more requests than needed.
+pub struct Paginator<T, E> {
+    items: BoxStream<'static, Result<T, E>>,
+}
+
+impl<T, E> fmt::Debug for Paginator<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Paginator").finish()
+    }
+}
+
+impl<T, E> Paginator<T, E> {
+    /// Fetches every remaining page, stopping at the first error.
+    pub async fn try_collect(self) -> Result<Vec<T>, E> {
+        TryStreamExt::try_collect(self.items).await
+    }
+}
+
+impl<T, E> Stream for Paginator<T, E> {
+    type Item = Result<T, E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.items.poll_next_unpin(cx)
+    }
+}
+
+/// Where the next request starts.
+enum Cursor<K> {
+    First,
+    Next(K),
+    Done,
+}
+
+/// Lists every item of a paginated operation.
+///
+/// `fetch` is called with the token from the previous page (`None` for the
+/// first page) and the `--page-size`, and returns a future for that page.
+pub fn paginate<T, K, E, F, Fut>(opt: &PageOpt, mut fetch: F) -> Paginator<T, E>
+where
+    T: Send + 'static,
+    K: Send + 'static,
+    E: Send + 'static,
+    F: FnMut(Option<K>, Option<i32>) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<Page<T, K>, E>> + Send + 'static,
+{
+    let page_size = opt.page_size;
+
+    let pages = stream::try_unfold(Cursor::First, move |cursor| {
+        let request = match cursor {
+            Cursor::First => Some(fetch(None, page_size)),
+            Cursor::Next(token) => Some(fetch(Some(token), page_size)),
+            Cursor::Done => None,
+        };
+        async move {
+            let page = match request {
+                Some(request) => request.await?,
+                None => return Ok(None),
+            };
+            let cursor = page.next.map_or(Cursor::Done, Cursor::Next);
+            Ok(Some((stream::iter(page.items.into_iter().map(Ok)), cursor)))
+        }
+    });
+    let items = pages.try_flatten();
+
+    Paginator {
+        items: match opt.max_items {
+            Some(max_items) => items.take(max_items).boxed(),
+            None => items.boxed(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// The token and page size of each request sent.
+    type Requests = Arc<Mutex<Vec<(Option<usize>, Option<i32>)>>>;
+
+    /// Pages of three numbers, tokens being the index of the next page,
+    /// recording the requests made.
+    fn numbers(opt: &PageOpt, requests: Requests) -> Paginator<u32, ()> {
+        let pages = [vec![1, 2, 3], vec![4, 5, 6], vec![7]];
+        paginate(opt, move |token: Option<usize>, limit| {
+            requests.lock().unwrap().push((token, limit));
+            let index = token.unwrap_or_default();
+            let page = Page::new(
+                pages[index].clone(),
+                Some(index + 1).filter(|next| *next < pages.len()),
+            );
+            async move { Ok(page) }
+        })
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    #[test]
+    fn follows_every_token() {
+        let requests = Arc::default();
+        let opt = PageOpt {
+            max_items: None,
+            page_size: Some(3),
+        };
+
+        let items = block_on(numbers(&opt, Arc::clone(&requests)).try_collect());
+
+        assert_eq!(items, Ok(vec![1, 2, 3, 4, 5, 6, 7]));
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [(None, Some(3)), (Some(1), Some(3)), (Some(2), Some(3))]
+        );
+    }
+
+    #[test]
+    fn max_items_stops_requesting_pages() {
+        let requests = Arc::default();
+        let opt = PageOpt {
+            max_items: Some(2),
+            page_size: None,
+        };
+
+        let items = block_on(numbers(&opt, Arc::clone(&requests)).try_collect());
+
+        assert_eq!(items, Ok(vec![1, 2]));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,189 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::process;
+#[cfg(feature = "testing")]
+use std::sync::{Mutex, PoisonError};
+
+use aws_smithy_client::erase::DynConnector;
+use aws_types::config::Config;
+use aws_types::region::Region;
+use http::Uri;
+
+use crate::output::Listing;
+#[cfg(feature = "testing")]
+use crate::traffic::Traffic;
+use crate::OutputFormat;
+
+/// The configuration loaded once per example by [`CommonOpt::load`](crate::CommonOpt::load).
+///
+/// Service clients are created from it with the per-service methods,
+/// such as `shared.dynamodb()`, each enabled by the feature of the same name.
+#[derive(Debug)]
+pub struct Shared {
+    pub(crate) config: Config,
+    pub(crate) region: Region,
+    pub(crate) endpoint_url: Option<Uri>,
+    pub(crate) output: OutputFormat,
+    pub(crate) verbose: bool,
+    pub(crate) raw_stdout: bool,
+    #[cfg(feature = "testing")]
+    pub(crate) traffic: Mutex<Option<Traffic>>,
+}
+
+impl Shared {
+    /// The cross-service configuration every client is built from.
+    pub fn sdk_config(&self) -> &Config {
+        &self.config
+    }
+
+    /// The resolved Region.
+    pub fn region(&self) -> &Region {
+        &self.region
+    }
+
+    /// The endpoint that overrides the Region's default endpoint, if any.
+    pub fn endpoint_url(&self) -> Option<&Uri> {
+        self.endpoint_url.as_ref()
+    }
+
+    /// The requested output format.
+    pub fn output(&self) -> OutputFormat {
+        self.output
+    }
+
+    /// Whether to display additional information.
+    pub fn verbose(&self) -> bool {
+        self.verbose
+    }
+
+    /// Marks stdout as holding only results for another program to read,
+    /// whatever `--output` is, such as query results passed through as they
+    /// come. The banner then goes to stderr.
+    pub fn raw_stdout(mut self) -> Self {
+        self.raw_stdout = true;
+        self
+    }
+
+    /// The connector that clients send their requests through.
+    ///
+    /// This is the recording or replaying connector when traffic is being
+    /// recorded or replayed, and an HTTPS connector otherwise. Examples that
+    /// build a raw `aws_hyper::Client` should use it so that they can be tested.
+    pub fn connector(&self) -> DynConnector {
+        match self.connector_override() {
+            Some(connector) => connector,
+            None => https_connector(),
+        }
+    }
+
+    /// The connector override for service clients, if any.
+    #[cfg(feature = "testing")]
+    pub(crate) fn connector_override(&self) -> Option<DynConnector> {
+        self.traffic
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .map(Traffic::connector)
+    }
+
+    /// Without the `testing` feature, traffic is never recorded or replayed.
+    #[cfg(not(feature = "testing"))]
+    pub(crate) fn connector_override(&self) -> Option<DynConnector> {
+        None
+    }
+
+    /// Prints `listing` in the format requested with `--output`,
+    /// calling `text` to print the example's usual output in text mode.
+    pub fn print(&self, listing: &Listing, text: impl FnOnce()) {
+        match self.output {
+            OutputFormat::Text => text(),
+            OutputFormat::Json => println!(
+                "{}",
+                serde_json::to_string_pretty(&listing.to_json()).expect("JSON values serialize")
+            ),
+            OutputFormat::Table => print!("{}", listing.to_table()),
+            OutputFormat::Csv => print!("{}", listing.to_csv()),
+            OutputFormat::Ndjson => print!("{}", listing.to_ndjson()),
+        }
+    }
+
+    /// In verbose mode, prints the client version, the Region, and the
+    /// example-specific `fields`, with the values lined up in one column.
+    ///
+    /// With `--output json`, `csv`, or `ndjson`, or after [`raw_stdout`](Shared::raw_stdout),
+    /// this goes to stderr, so that stdout holds only the results.
+    pub fn banner(&self, service: &str, version: &str, fields: &[(&str, &dyn Display)]) {
+        if !self.verbose {
+            return;
+        }
+
+        let mut out: Box<dyn Write> = if self.raw_stdout || self.output.is_machine_readable() {
+            Box::new(io::stderr())
+        } else {
+            Box::new(io::stdout())
+        };
+
+        let heading = format!("{} client version:", service);
+        let width = fields
+            .iter()
+            .map(|(label, _)| label.len() + 1)
+            .chain(std::iter::once(heading.len()))
+            .max()
+            .unwrap_or_default()
+            + 1;
+
+        let mut line = |label: &str, value: &dyn Display| {
+            let _ = writeln!(out, "{:width$}{}", label, value, width = width);
+        };
+        line(&heading, &version);
+        line("Region:", &self.region.as_ref());
+        if let Some(endpoint_url) = &self.endpoint_url {
+            line("Endpoint:", endpoint_url);
+        }
+        for (label, value) in fields {
+            line(&format!("{}:", label), value);
+        }
+        let _ = writeln!(out);
+    }
+
+    /// Exits the process with `code`, first saving the recorded traffic
+    /// as dropping `self` would, since `process::exit` runs no destructors.
+    /// Helpers that exit on an error take `&Shared` to call it.
+    pub fn exit(&self, code: i32) -> ! {
+        self.finish_traffic();
+        process::exit(code)
+    }
+
+    #[cfg(feature = "testing")]
+    fn finish_traffic(&self) {
+        let traffic = self
+            .traffic
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        if let Some(traffic) = traffic {
+            traffic.finish();
+        }
+    }
+
+    #[cfg(not(feature = "testing"))]
+    fn finish_traffic(&self) {}
+}
+
+impl Drop for Shared {
+    /// Saves the recorded traffic, or the requests sent during a replay.
+    fn drop(&mut self) {
+        self.finish_traffic();
+    }
+}
+
+fn https_connector() -> DynConnector {
+    DynConnector::new(
+        aws_smithy_client::hyper_ext::Adapter::builder().build(aws_smithy_client::conns::https()),
+    )
+}
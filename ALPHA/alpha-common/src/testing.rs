@@ -0,0 +1,445 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Runs an example binary offline against a recording of its HTTP traffic.
+//!
+//! Recordings are made by running the example with **ALPHA_RECORD_TRAFFIC**
+//! set (see [`traffic`](crate::traffic)), and are kept under each crate's
+//! `tests/fixtures` directory. An integration test then replays one:
+//!
+//! ```ignore
+//! use alpha_common::testing::Replay;
+//!
+//! let run = Replay::new(
+//!     env!("CARGO_BIN_EXE_list-buckets"),
+//!     concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/list-buckets.json"),
+//! )
+//! .run();
+//!
+//! run.assert_success();
+//! run.assert_requests_match(&["content-type"]);
+//! assert!(run.stdout.contains("Found 2 buckets in all regions."));
+//! ```
+//!
+//! Tests that need a service to answer differently each time, such as one
+//! that checks signatures or keeps what was stored, run the example with
+//! [`example`] against a [`StandIn`] instead.
+
+use std::collections::BTreeMap;
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use serde_json::Value;
+
+use crate::traffic::{RecordedRequest, RECORD_ENV, REPLAY_ENV, REQUESTS_ENV};
+use crate::ENDPOINT_URL_ENV;
+
+/// The Region the examples are run in, which recordings must be made in too.
+pub const REGION: &str = "us-west-2";
+
+/// The secret key examples are given, for tests that check signatures.
+pub const SECRET_ACCESS_KEY: &str = "notrealrnrELgWzOk3IfjzDKtFBhDby";
+
+/// An example binary to run against a recording.
+#[derive(Debug)]
+pub struct Replay {
+    binary: PathBuf,
+    fixture: PathBuf,
+    args: Vec<OsString>,
+    stdin: Vec<u8>,
+}
+
+/// The outcome of a [`Replay`].
+#[derive(Debug)]
+pub struct ReplayRun {
+    /// How the example exited.
+    pub status: ExitStatus,
+    /// Everything the example printed to stdout.
+    pub stdout: String,
+    /// Everything the example printed to stderr.
+    pub stderr: String,
+    /// The requests the example sent, in order.
+    pub requests: Vec<RecordedRequest>,
+    /// The requests in the recording, in order.
+    pub expected: Vec<RecordedRequest>,
+}
+
+/// A command that runs an example `binary` in [`REGION`], with dummy
+/// credentials and no access to the user's AWS configuration files, so
+/// that it cannot reach AWS by accident.
+pub fn example(binary: impl AsRef<OsStr>) -> Command {
+    let missing = std::env::temp_dir().join("alpha-no-aws-config");
+    let mut command = Command::new(binary);
+    command
+        .args(["--region", REGION])
+        .env_remove("AWS_PROFILE")
+        .env_remove("AWS_SESSION_TOKEN")
+        .env_remove(ENDPOINT_URL_ENV)
+        .env_remove(RECORD_ENV)
+        .env_remove(REPLAY_ENV)
+        .env("AWS_ACCESS_KEY_ID", "ANOTREAL")
+        .env("AWS_SECRET_ACCESS_KEY", SECRET_ACCESS_KEY)
+        .env("AWS_CONFIG_FILE", missing.join("config"))
+        .env("AWS_SHARED_CREDENTIALS_FILE", missing.join("credentials"));
+    command
+}
+
+impl Replay {
+    /// Prepares to run `binary`, usually `env!("CARGO_BIN_EXE_<name>")`,
+    /// answering its requests from the recording in `fixture`.
+    pub fn new(binary: impl Into<PathBuf>, fixture: impl Into<PathBuf>) -> Self {
+        Replay {
+            binary: binary.into(),
+            fixture: fixture.into(),
+            args: Vec::new(),
+            stdin: Vec::new(),
+        }
+    }
+
+    /// Adds command-line arguments. `--region` is always passed, before them,
+    /// so that it also works for examples with subcommands.
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<OsString>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets what the example reads from stdin, which is otherwise empty.
+    pub fn stdin(mut self, input: impl Into<Vec<u8>>) -> Self {
+        self.stdin = input.into();
+        self
+    }
+
+    /// Runs the example to completion, in the environment [`example`] gives it.
+    pub fn run(self) -> ReplayRun {
+        static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+        let scratch = std::env::temp_dir().join(format!(
+            "alpha-replay-{}-{}",
+            std::process::id(),
+            RUNS.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&scratch).expect("cannot create scratch directory");
+        let requests_path = scratch.join("requests.json");
+
+        let mut child = example(&self.binary)
+            .args(&self.args)
+            .env(REPLAY_ENV, &self.fixture)
+            .env(REQUESTS_ENV, &requests_path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap_or_else(|e| panic!("cannot run {}: {}", self.binary.display(), e));
+        // The example may exit without reading all of its input.
+        let _ = child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(&self.stdin);
+        let output = child
+            .wait_with_output()
+            .unwrap_or_else(|e| panic!("cannot run {}: {}", self.binary.display(), e));
+
+        let requests = match fs::read(&requests_path) {
+            Ok(json) => serde_json::from_slice(&json).expect("requests file is valid JSON"),
+            // The example exited without returning from main.
+            Err(_) => Vec::new(),
+        };
+        let _ = fs::remove_dir_all(&scratch);
+
+        ReplayRun {
+            status: output.status,
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            requests,
+            expected: expected_requests(&self.fixture),
+        }
+    }
+}
+
+impl ReplayRun {
+    /// Panics, showing the example's output, unless it exited successfully.
+    pub fn assert_success(&self) -> &Self {
+        assert!(
+            self.status.success(),
+            "example failed with {}\n--- stdout\n{}\n--- stderr\n{}",
+            self.status,
+            self.stdout,
+            self.stderr
+        );
+        self
+    }
+
+    /// Parses stdout as the one JSON document that `--output json` prints,
+    /// panicking if anything else, even a blank line, was printed with it.
+    pub fn json(&self) -> Value {
+        let value: Value = serde_json::from_str(&self.stdout)
+            .unwrap_or_else(|e| panic!("stdout is not JSON: {}\n{}", e, self.stdout));
+        let printed = serde_json::to_string_pretty(&value).expect("JSON values serialize");
+        assert_eq!(
+            self.stdout,
+            printed + "\n",
+            "stdout holds more than the JSON"
+        );
+        value
+    }
+
+    /// Panics unless the example sent exactly the recorded requests:
+    /// the same methods, URIs, bodies, and values of the `headers` given.
+    ///
+    /// JSON bodies are compared as JSON, so key order does not matter.
+    pub fn assert_requests_match(&self, headers: &[&str]) -> &Self {
+        assert_eq!(
+            self.requests.len(),
+            self.expected.len(),
+            "sent {} requests but the recording has {}\n--- sent\n{:#?}",
+            self.requests.len(),
+            self.expected.len(),
+            self.requests
+        );
+        for (i, (actual, expected)) in self.requests.iter().zip(&self.expected).enumerate() {
+            assert_eq!(actual.method, expected.method, "method of request {}", i);
+            assert_eq!(actual.uri, expected.uri, "URI of request {}", i);
+            for header in headers {
+                assert_eq!(
+                    actual.header(header),
+                    expected.header(header),
+                    "{} header of request {}",
+                    header,
+                    i
+                );
+            }
+            match (
+                serde_json::from_str::<Value>(&actual.body),
+                serde_json::from_str::<Value>(&expected.body),
+            ) {
+                (Ok(actual), Ok(expected)) => {
+                    assert_eq!(actual, expected, "JSON body of request {}", i)
+                }
+                _ => assert_eq!(actual.body, expected.body, "body of request {}", i),
+            }
+        }
+        self
+    }
+}
+
+/// Reads the requests out of a recording, reassembling each request's body
+/// from its data events.
+fn expected_requests(fixture: &std::path::Path) -> Vec<RecordedRequest> {
+    let json =
+        fs::read(fixture).unwrap_or_else(|e| panic!("cannot read {}: {}", fixture.display(), e));
+    let traffic: Value = serde_json::from_slice(&json)
+        .unwrap_or_else(|e| panic!("cannot parse {}: {}", fixture.display(), e));
+
+    let mut requests = BTreeMap::<u64, RecordedRequest>::new();
+    for event in traffic["events"].as_array().into_iter().flatten() {
+        let id = event["connection_id"].as_u64().unwrap_or_default();
+        let action = &event["action"];
+        if let Some(request) = action.pointer("/Request/request") {
+            let headers = request["headers"]
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(|(name, values)| {
+                    let values = values
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|v| v.as_str().map(String::from))
+                        .collect();
+                    (name.to_ascii_lowercase(), values)
+                })
+                .collect();
+            requests.insert(
+                id,
+                RecordedRequest {
+                    method: request["method"].as_str().unwrap_or_default().to_string(),
+                    uri: request["uri"].as_str().unwrap_or_default().to_string(),
+                    headers,
+                    body: String::new(),
+                },
+            );
+        } else if action.pointer("/Data/direction") == Some(&Value::from("Request")) {
+            let body = action
+                .pointer("/Data/data/Utf8")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            if let Some(request) = requests.get_mut(&id) {
+                request.body.push_str(body);
+            }
+        }
+    }
+    requests.into_values().collect()
+}
+
+/// A request as a [`StandIn`] receives it.
+#[derive(Clone, Debug)]
+pub struct StandInRequest {
+    pub method: String,
+    /// The path, as sent.
+    pub path: String,
+    /// The query parameters, decoded.
+    pub query: Vec<(String, String)>,
+    /// The headers, by lowercase name.
+    pub headers: BTreeMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl StandInRequest {
+    /// The value of the query parameter `name`, or an empty string.
+    pub fn param(&self, name: &str) -> &str {
+        self.query
+            .iter()
+            .find(|(k, _)| k == name)
+            .map_or("", |(_, v)| v.as_str())
+    }
+}
+
+/// A response for a [`StandIn`] to send.
+#[derive(Clone, Debug)]
+pub struct StandInResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl StandInResponse {
+    /// A response with no headers but its length.
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        StandInResponse {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Adds a header.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+/// A plain HTTP/1.1 server on a local port, standing in for a service, that
+/// answers each request with what `handler` returns until the test ends.
+/// Examples are pointed at it with `--endpoint-url`.
+#[derive(Debug)]
+pub struct StandIn {
+    endpoint: String,
+}
+
+impl StandIn {
+    /// Starts answering requests on a port of its own.
+    pub fn start<F>(mut handler: F) -> Self
+    where
+        F: FnMut(StandInRequest) -> StandInResponse + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("cannot listen on a local port");
+        let endpoint = format!("http://{}", listener.local_addr().expect("bound"));
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.expect("cannot accept a connection");
+                let request = read_request(&mut BufReader::new(&stream));
+                let response = handler(request);
+                let mut head = format!("HTTP/1.1 {} X\r\n", response.status);
+                for (name, value) in &response.headers {
+                    head.push_str(&format!("{}: {}\r\n", name, value));
+                }
+                head.push_str(&format!(
+                    "content-length: {}\r\nconnection: close\r\n\r\n",
+                    response.body.len()
+                ));
+                // The example may have given up on the request.
+                let _ = stream
+                    .write_all(head.as_bytes())
+                    .and_then(|()| stream.write_all(&response.body));
+            }
+        });
+        StandIn { endpoint }
+    }
+
+    /// The URL to pass as `--endpoint-url`.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+}
+
+fn read_request(reader: &mut impl BufRead) -> StandInRequest {
+    let mut line = String::new();
+    reader
+        .read_line(&mut line)
+        .expect("cannot read the request");
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(k), percent_decode(v))
+        })
+        .collect();
+
+    let mut headers = BTreeMap::new();
+    loop {
+        let mut line = String::new();
+        reader
+            .read_line(&mut line)
+            .expect("cannot read the request");
+        match line.trim_end().split_once(':') {
+            Some((name, value)) => {
+                headers.insert(name.to_ascii_lowercase(), value.trim().to_string())
+            }
+            None => break,
+        };
+    }
+    let len = headers
+        .get("content-length")
+        .map_or(0, |len| len.parse().expect("a numeric content-length"));
+    let mut body = vec![0; len];
+    reader.read_exact(&mut body).expect("cannot read the body");
+
+    StandInRequest {
+        method,
+        path: path.to_string(),
+        query,
+        headers,
+        body,
+    }
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = s
+            .get(i + 1..i + 3)
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
@@ -0,0 +1,265 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Recording and replaying the HTTP traffic of an example.
+//!
+//! Set **ALPHA_RECORD_TRAFFIC** to a file name to save every request and
+//! response an example exchanges with AWS to that file when the example exits.
+//! Set **ALPHA_REPLAY_TRAFFIC** to such a file to answer the example's requests
+//! from it instead of from AWS; see [`testing`](crate::testing) for running an
+//! example that way from a test.
+//!
+//! This module, and recording and replaying, need the `testing` feature,
+//! which the examples turn on only for their tests.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::PathBuf;
+use std::{env, fmt, thread};
+
+use aws_smithy_client::dvr::{NetworkTraffic, RecordingConnection, ReplayingConnection};
+use aws_smithy_client::erase::DynConnector;
+use aws_smithy_client::hyper_ext::Adapter;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The environment variable naming the file to record traffic to.
+pub const RECORD_ENV: &str = "ALPHA_RECORD_TRAFFIC";
+
+/// The environment variable naming the file to replay traffic from.
+pub const REPLAY_ENV: &str = "ALPHA_REPLAY_TRAFFIC";
+
+/// The environment variable naming the file that the requests an example
+/// actually sent during a replay are written to.
+pub const REQUESTS_ENV: &str = "ALPHA_REPLAY_REQUESTS";
+
+/// Request headers that are never written to a recording,
+/// because they identify the credentials that signed the request.
+const SECRET_HEADERS: &[&str] = &["authorization", "x-amz-security-token"];
+
+/// An HTTP request sent by an example.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    /// The HTTP method, such as `POST`.
+    pub method: String,
+    /// The full request URI.
+    pub uri: String,
+    /// The request headers, keyed by lowercase name.
+    pub headers: BTreeMap<String, Vec<String>>,
+    /// The request body, decoded as UTF-8.
+    pub body: String,
+}
+
+impl RecordedRequest {
+    /// Returns the values of header `name`, joined with commas, if it was sent.
+    pub fn header(&self, name: &str) -> Option<String> {
+        self.headers.get(name).map(|values| values.join(", "))
+    }
+
+    /// Parses the body as JSON.
+    pub fn json(&self) -> Value {
+        serde_json::from_str(&self.body)
+            .unwrap_or_else(|e| panic!("request body is not JSON ({}): {}", e, self.body))
+    }
+}
+
+/// Where an example's HTTP traffic goes when recording or replaying.
+pub(crate) enum Traffic {
+    Record {
+        path: PathBuf,
+        connection: Box<RecordingConnection<Adapter<aws_smithy_client::conns::Https>>>,
+    },
+    Replay {
+        requests_path: Option<PathBuf>,
+        connection: ReplayingConnection,
+    },
+}
+
+impl fmt::Debug for Traffic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Traffic::Record { path, .. } => f.debug_struct("Record").field("path", path).finish(),
+            Traffic::Replay { requests_path, .. } => f
+                .debug_struct("Replay")
+                .field("requests_path", requests_path)
+                .finish(),
+        }
+    }
+}
+
+impl Traffic {
+    /// Checks the environment for a recording to make or replay.
+    ///
+    /// Panics if the replay file cannot be read, since the example
+    /// would otherwise quietly send its requests to AWS.
+    pub(crate) fn from_env() -> Option<Traffic> {
+        if let Some(path) = env::var_os(REPLAY_ENV) {
+            let path = PathBuf::from(path);
+            let file = File::open(&path)
+                .unwrap_or_else(|e| panic!("cannot open {}: {}", path.display(), e));
+            let traffic: NetworkTraffic = serde_json::from_reader(file)
+                .unwrap_or_else(|e| panic!("cannot parse {}: {}", path.display(), e));
+            return Some(Traffic::Replay {
+                requests_path: env::var_os(REQUESTS_ENV).map(PathBuf::from),
+                connection: ReplayingConnection::new(traffic.events().clone()),
+            });
+        }
+
+        env::var_os(RECORD_ENV).map(|path| Traffic::Record {
+            path: PathBuf::from(path),
+            connection: Box::new(RecordingConnection::new(
+                Adapter::builder().build(aws_smithy_client::conns::https()),
+            )),
+        })
+    }
+
+    /// The connector every client sends its requests through.
+    pub(crate) fn connector(&self) -> DynConnector {
+        match self {
+            Traffic::Record { connection, .. } => DynConnector::new((**connection).clone()),
+            Traffic::Replay { connection, .. } => DynConnector::new(connection.clone()),
+        }
+    }
+
+    /// Writes the recording, or the requests sent during a replay.
+    pub(crate) fn finish(self) {
+        match self {
+            Traffic::Record { path, connection } => {
+                let mut traffic = serde_json::to_value(connection.network_traffic())
+                    .expect("network traffic is serializable");
+                scrub(&mut traffic);
+                // Note how the recording was made, so it can be made again.
+                traffic["docs"] = Value::from(env::args().collect::<Vec<_>>().join(" "));
+                write_json(&path, &traffic);
+            }
+            Traffic::Replay {
+                requests_path,
+                connection,
+            } => {
+                let requests_path = match requests_path {
+                    Some(path) => path,
+                    None => return,
+                };
+                let requests = block_on(move || connection.take_requests())
+                    .into_iter()
+                    .map(|request| RecordedRequest {
+                        method: request.method().to_string(),
+                        uri: request.uri().to_string(),
+                        headers: header_map(request.headers()),
+                        body: String::from_utf8_lossy(request.body()).into_owned(),
+                    })
+                    .collect::<Vec<_>>();
+                write_json(&requests_path, &requests);
+            }
+        }
+    }
+}
+
+/// Removes [`SECRET_HEADERS`] from every request in a serialized recording.
+fn scrub(traffic: &mut Value) {
+    let events = traffic["events"].as_array_mut().into_iter().flatten();
+    for event in events {
+        if let Some(headers) = event
+            .pointer_mut("/action/Request/request/headers")
+            .and_then(Value::as_object_mut)
+        {
+            for name in SECRET_HEADERS {
+                headers.remove(*name);
+            }
+        }
+    }
+}
+
+fn header_map(headers: &http::HeaderMap) -> BTreeMap<String, Vec<String>> {
+    let mut map = BTreeMap::<String, Vec<String>>::new();
+    for (name, value) in headers {
+        map.entry(name.to_string())
+            .or_default()
+            .push(String::from_utf8_lossy(value.as_bytes()).into_owned());
+    }
+    map
+}
+
+fn write_json(path: &std::path::Path, value: &impl Serialize) {
+    let file =
+        File::create(path).unwrap_or_else(|e| panic!("cannot create {}: {}", path.display(), e));
+    serde_json::to_writer_pretty(file, value)
+        .unwrap_or_else(|e| panic!("cannot write {}: {}", path.display(), e));
+}
+
+/// Runs the future that `make` returns to completion from synchronous code,
+/// such as a `Drop` impl that runs at the end of an example's `#[tokio::main]`
+/// function, whatever kind of runtime that is.
+///
+/// The future runs on a thread of its own, as a runtime's own thread cannot
+/// block on another future.
+fn block_on<F, Fut>(make: F) -> Fut::Output
+where
+    F: FnOnce() -> Fut + Send,
+    Fut: std::future::Future,
+    Fut::Output: Send,
+{
+    thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                tokio::runtime::Builder::new_current_thread()
+                    .build()
+                    .expect("a current-thread runtime can always be built")
+                    .block_on(make())
+            })
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_on_works_inside_any_runtime() {
+        for mut builder in [
+            tokio::runtime::Builder::new_current_thread(),
+            tokio::runtime::Builder::new_multi_thread(),
+        ] {
+            let runtime = builder.build().unwrap();
+            runtime.block_on(async { assert_eq!(block_on(|| async { 1 }), 1) });
+        }
+        assert_eq!(block_on(|| async { 2 }), 2);
+    }
+
+    #[test]
+    fn scrub_removes_credentials() {
+        let mut traffic = serde_json::json!({
+            "events": [
+                {
+                    "connection_id": 0,
+                    "action": {
+                        "Request": {
+                            "request": {
+                                "uri": "https://s3.us-west-2.amazonaws.com/",
+                                "method": "GET",
+                                "headers": {
+                                    "authorization": ["AWS4-HMAC-SHA256 Credential=AKID/..."],
+                                    "x-amz-security-token": ["token"],
+                                    "x-amz-date": ["20211014T000000Z"]
+                                }
+                            }
+                        }
+                    }
+                },
+                { "connection_id": 0, "action": { "Eof": { "ok": true, "direction": "Request" } } }
+            ]
+        });
+
+        scrub(&mut traffic);
+
+        let headers = &traffic["events"][0]["action"]["Request"]["request"]["headers"];
+        assert_eq!(
+            headers,
+            &serde_json::json!({ "x-amz-date": ["20211014T000000Z"] })
+        );
+    }
+}
@@ -0,0 +1,406 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Polling an operation until a resource reaches the state an example needs.
+//!
+//! A [`Waiter`] sends a request, asks an acceptor what the response means,
+//! and, while the answer is [`Acceptor::Retry`], sleeps with exponential
+//! backoff and jitter before polling again, until it succeeds, fails,
+//! runs out of time, or is cancelled. The submodules hold ready-made
+//! waiters for the resources the examples create and delete:
+//!
+//! ```ignore
+//! use alpha_common::waiter::{dynamodb, Waiter};
+//!
+//! client.create_table() /* ... */ .send().await?;
+//! dynamodb::table_active(&Waiter::new(), &client, "users").await?;
+//! ```
+
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures_util::future::{self, Either};
+use rand::Rng;
+use structopt::StructOpt;
+use tokio::sync::Notify;
+
+#[cfg(feature = "cloudformation")]
+pub mod cloudformation;
+#[cfg(feature = "dynamodb")]
+pub mod dynamodb;
+#[cfg(feature = "ec2")]
+pub mod ec2;
+#[cfg(feature = "kinesis")]
+pub mod kinesis;
+#[cfg(feature = "qldb")]
+pub mod qldb;
+
+/// What the outcome of one poll means.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Acceptor {
+    /// The resource is in the state being waited for.
+    Success,
+    /// The resource is in a state it will not leave, such as a failed stack,
+    /// or the request failed in a way that polling again will not fix.
+    Failure,
+    /// The resource may still reach the state; poll again.
+    Retry,
+}
+
+/// Why a [`Waiter`] stopped without success.
+#[derive(Debug)]
+pub enum WaitError<T, E> {
+    /// The acceptor reported [`Acceptor::Failure`] for this response.
+    Failed(T),
+    /// The acceptor reported [`Acceptor::Failure`] for this error.
+    Error(E),
+    /// The resource did not reach the state within the maximum wait time.
+    TimedOut(Duration),
+    /// The wait was cancelled.
+    Cancelled,
+}
+
+impl<T, E: fmt::Display> fmt::Display for WaitError<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WaitError::Failed(_) => write!(f, "the resource reached a state it cannot leave"),
+            WaitError::Error(e) => write!(f, "{}", e),
+            WaitError::TimedOut(waited) => {
+                write!(f, "gave up waiting after {} seconds", waited.as_secs())
+            }
+            WaitError::Cancelled => write!(f, "stopped waiting"),
+        }
+    }
+}
+
+impl<T: fmt::Debug, E: std::error::Error + 'static> std::error::Error for WaitError<T, E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WaitError::Error(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// A handle that stops every [`Waiter`] it was given to.
+#[derive(Clone, Debug, Default)]
+pub struct Cancel {
+    inner: Arc<CancelInner>,
+}
+
+#[derive(Debug, Default)]
+struct CancelInner {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl Cancel {
+    /// Creates a handle that has not been cancelled.
+    pub fn new() -> Self {
+        Cancel::default()
+    }
+
+    /// Creates a handle that is cancelled when the user presses Ctrl-C.
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub fn on_ctrl_c() -> Self {
+        let cancel = Cancel::new();
+        let handle = cancel.clone();
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                handle.cancel();
+            }
+        });
+        cancel
+    }
+
+    /// Stops the waiters using this handle.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        self.inner.notify.notify_waiters();
+    }
+
+    /// Whether [`cancel`](Cancel::cancel) has been called.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    async fn cancelled(&self) {
+        loop {
+            let notified = self.inner.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// How long and how often to poll.
+#[derive(Clone, Debug)]
+pub struct Waiter {
+    min_delay: Duration,
+    max_delay: Duration,
+    max_wait: Duration,
+    cancel: Option<Cancel>,
+    on_retry: Option<OnRetry>,
+}
+
+/// A callback told how long a [`Waiter`] has waited so far.
+#[derive(Clone)]
+struct OnRetry(Arc<dyn Fn(Duration) + Send + Sync>);
+
+impl fmt::Debug for OnRetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OnRetry")
+    }
+}
+
+impl Default for Waiter {
+    fn default() -> Self {
+        Waiter {
+            min_delay: Duration::from_secs(2),
+            max_delay: Duration::from_secs(120),
+            max_wait: Duration::from_secs(600),
+            cancel: None,
+            on_retry: None,
+        }
+    }
+}
+
+impl Waiter {
+    /// Creates a waiter that first retries after 2 seconds, backs off to at
+    /// most 2 minutes between polls, and gives up after 10 minutes.
+    pub fn new() -> Self {
+        Waiter::default()
+    }
+
+    /// Sets the shortest delay between polls.
+    pub fn min_delay(mut self, delay: Duration) -> Self {
+        self.min_delay = delay;
+        self
+    }
+
+    /// Sets the longest delay between polls.
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Sets how long to poll before giving up.
+    pub fn max_wait(mut self, wait: Duration) -> Self {
+        self.max_wait = wait;
+        self
+    }
+
+    /// Stops waiting as soon as `cancel` is cancelled.
+    pub fn cancel_with(mut self, cancel: Cancel) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Calls `on_retry` with the time waited so far each time a poll finds
+    /// the resource not ready yet, such as to show progress on a long wait.
+    pub fn on_retry(mut self, on_retry: impl Fn(Duration) + Send + Sync + 'static) -> Self {
+        self.on_retry = Some(OnRetry(Arc::new(on_retry)));
+        self
+    }
+
+    /// Calls `poll` until `accept` reports success or failure for its outcome.
+    pub async fn wait<T, E, F, Fut, A>(&self, mut poll: F, accept: A) -> Result<(), WaitError<T, E>>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        A: Fn(&Result<T, E>) -> Acceptor,
+    {
+        let start = Instant::now();
+        let cancelled = async {
+            match &self.cancel {
+                Some(cancel) => cancel.cancelled().await,
+                None => future::pending().await,
+            }
+        };
+        futures_util::pin_mut!(cancelled);
+
+        for attempt in 1.. {
+            let outcome = match future::select(Box::pin(poll()), cancelled.as_mut()).await {
+                Either::Left((outcome, _)) => outcome,
+                Either::Right(_) => return Err(WaitError::Cancelled),
+            };
+            match (accept(&outcome), outcome) {
+                (Acceptor::Success, _) => return Ok(()),
+                (Acceptor::Failure, Ok(output)) => return Err(WaitError::Failed(output)),
+                (Acceptor::Failure, Err(e)) => return Err(WaitError::Error(e)),
+                (Acceptor::Retry, _) => (),
+            }
+
+            let waited = start.elapsed();
+            if let Some(OnRetry(on_retry)) = &self.on_retry {
+                on_retry(waited);
+            }
+            let remaining = self.max_wait.saturating_sub(waited);
+            if remaining < self.min_delay {
+                return Err(WaitError::TimedOut(waited));
+            }
+            let delay = self.delay(attempt).min(remaining);
+            let sleep = Box::pin(tokio::time::sleep(delay));
+            if let Either::Right(_) = future::select(sleep, cancelled.as_mut()).await {
+                return Err(WaitError::Cancelled);
+            }
+        }
+        unreachable!("attempts are unbounded")
+    }
+
+    /// The delay after poll number `attempt`, counting from 1: a random time
+    /// between the minimum delay and a ceiling that doubles with each attempt.
+    fn delay(&self, attempt: u32) -> Duration {
+        let ceiling = self
+            .min_delay
+            .checked_mul(1 << (attempt - 1).min(16))
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+        if ceiling <= self.min_delay {
+            return self.min_delay;
+        }
+        rand::thread_rng().gen_range(self.min_delay..=ceiling)
+    }
+}
+
+/// Command-line options for examples that can wait for what they create or delete.
+#[derive(Clone, Debug, Default, StructOpt)]
+pub struct WaitOpt {
+    /// Whether to wait until the change is complete.
+    #[structopt(long)]
+    pub wait: bool,
+
+    /// The most seconds to wait. Defaults to 600.
+    #[structopt(long)]
+    pub max_wait: Option<u64>,
+}
+
+impl WaitOpt {
+    /// Returns a waiter that stops on Ctrl-C, if `--wait` was given.
+    pub fn waiter(&self) -> Option<Waiter> {
+        if !self.wait {
+            return None;
+        }
+        let waiter = Waiter::new().cancel_with(Cancel::on_ctrl_c());
+        Some(match self.max_wait {
+            Some(seconds) => waiter.max_wait(Duration::from_secs(seconds)),
+            None => waiter,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    fn quick() -> Waiter {
+        Waiter::new()
+            .min_delay(Duration::from_millis(1))
+            .max_delay(Duration::from_millis(4))
+    }
+
+    /// Accepts 3 as success, a negative number as failure, and errors as failures.
+    fn accept(outcome: &Result<i32, String>) -> Acceptor {
+        match outcome {
+            Ok(3) => Acceptor::Success,
+            Ok(n) if *n < 0 => Acceptor::Failure,
+            Ok(_) => Acceptor::Retry,
+            Err(_) => Acceptor::Failure,
+        }
+    }
+
+    #[test]
+    fn polls_until_success() {
+        let polls = Cell::new(0);
+        let result = block_on(quick().wait(
+            || {
+                polls.set(polls.get() + 1);
+                future::ready(Ok(polls.get()))
+            },
+            accept,
+        ));
+
+        assert!(result.is_ok());
+        assert_eq!(polls.get(), 3);
+    }
+
+    #[test]
+    fn reports_each_retry() {
+        let retries = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = retries.clone();
+        let polls = Cell::new(0);
+        let waiter = quick().on_retry(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let result = block_on(waiter.wait(
+            || {
+                polls.set(polls.get() + 1);
+                future::ready(Ok(polls.get()))
+            },
+            accept,
+        ));
+
+        assert!(result.is_ok());
+        assert_eq!(retries.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn stops_on_failure() {
+        let result = block_on(quick().wait(|| future::ready(Ok(-1)), accept));
+        assert!(matches!(result, Err(WaitError::Failed(-1))));
+
+        let result = block_on(quick().wait(|| future::ready(Err("gone".to_string())), accept));
+        assert!(matches!(result, Err(WaitError::Error(e)) if e == "gone"));
+    }
+
+    #[test]
+    fn gives_up_after_max_wait() {
+        let waiter = quick().max_wait(Duration::from_millis(20));
+        let result = block_on(waiter.wait(|| future::ready(Ok(0)), accept));
+        assert!(matches!(result, Err(WaitError::TimedOut(_))));
+    }
+
+    #[test]
+    fn stops_when_cancelled() {
+        let cancel = Cancel::new();
+        let waiter = quick().cancel_with(cancel.clone());
+        let result = block_on(waiter.wait(
+            || {
+                cancel.cancel();
+                future::ready(Ok(0))
+            },
+            accept,
+        ));
+        assert!(matches!(result, Err(WaitError::Cancelled)));
+    }
+
+    #[test]
+    fn delays_back_off_within_bounds() {
+        let waiter = Waiter::new()
+            .min_delay(Duration::from_secs(2))
+            .max_delay(Duration::from_secs(30));
+        for attempt in 1..40 {
+            let delay = waiter.delay(attempt);
+            assert!(delay >= Duration::from_secs(2), "{:?}", delay);
+            assert!(delay <= Duration::from_secs(30), "{:?}", delay);
+        }
+        assert_eq!(waiter.delay(1), Duration::from_secs(2));
+    }
+}
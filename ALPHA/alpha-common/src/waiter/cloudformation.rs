@@ -0,0 +1,65 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Waiters for AWS CloudFormation stacks.
+
+use aws_sdk_cloudformation::error::DescribeStacksError;
+use aws_sdk_cloudformation::model::StackStatus;
+use aws_sdk_cloudformation::output::DescribeStacksOutput;
+use aws_sdk_cloudformation::{Client, SdkError};
+
+use super::{Acceptor, WaitError, Waiter};
+
+/// What a stack waiter returns.
+pub type StackResult = Result<(), WaitError<DescribeStacksOutput, SdkError<DescribeStacksError>>>;
+
+/// Waits until the stack is `CREATE_COMPLETE`.
+pub async fn stack_create_complete(
+    waiter: &Waiter,
+    client: &Client,
+    stack_name: &str,
+) -> StackResult {
+    waiter
+        .wait(
+            || client.describe_stacks().stack_name(stack_name).send(),
+            |outcome| match outcome.as_ref().map(stack_status) {
+                Ok(Some(StackStatus::CreateComplete)) => Acceptor::Success,
+                Ok(Some(StackStatus::CreateInProgress)) | Ok(None) => Acceptor::Retry,
+                // Failed, rolling back, or being deleted.
+                Ok(Some(_)) => Acceptor::Failure,
+                Err(_) => Acceptor::Failure,
+            },
+        )
+        .await
+}
+
+/// Waits until the stack is `DELETE_COMPLETE`, or no longer exists.
+pub async fn stack_delete_complete(
+    waiter: &Waiter,
+    client: &Client,
+    stack_name: &str,
+) -> StackResult {
+    waiter
+        .wait(
+            || client.describe_stacks().stack_name(stack_name).send(),
+            |outcome| match outcome.as_ref().map(stack_status) {
+                Ok(Some(StackStatus::DeleteComplete)) => Acceptor::Success,
+                Ok(Some(StackStatus::DeleteFailed)) => Acceptor::Failure,
+                Ok(_) => Acceptor::Retry,
+                // DescribeStacks reports a stack that does not exist as a validation error.
+                Err(SdkError::ServiceError { err, .. })
+                    if err.code() == Some("ValidationError") =>
+                {
+                    Acceptor::Success
+                }
+                Err(_) => Acceptor::Failure,
+            },
+        )
+        .await
+}
+
+fn stack_status(output: &DescribeStacksOutput) -> Option<&StackStatus> {
+    output.stacks.as_ref()?.first()?.stack_status.as_ref()
+}
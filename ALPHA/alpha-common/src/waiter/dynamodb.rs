@@ -0,0 +1,90 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Waiters for DynamoDB tables.
+
+use aws_sdk_dynamodb::error::{DescribeBackupError, DescribeTableError};
+use aws_sdk_dynamodb::model::{BackupStatus, IndexStatus, TableDescription, TableStatus};
+use aws_sdk_dynamodb::output::{DescribeBackupOutput, DescribeTableOutput};
+use aws_sdk_dynamodb::{Client, SdkError};
+
+use super::{Acceptor, WaitError, Waiter};
+
+/// What a table waiter returns.
+pub type TableResult = Result<(), WaitError<DescribeTableOutput, SdkError<DescribeTableError>>>;
+
+/// What a backup waiter returns.
+pub type BackupResult = Result<(), WaitError<DescribeBackupOutput, SdkError<DescribeBackupError>>>;
+
+fn is_active(table: &TableDescription) -> bool {
+    table.table_status == Some(TableStatus::Active)
+        && table
+            .global_secondary_indexes
+            .iter()
+            .flatten()
+            .all(|index| index.index_status == Some(IndexStatus::Active))
+}
+
+/// Waits until the table and its global secondary indexes are `ACTIVE`.
+pub async fn table_active(waiter: &Waiter, client: &Client, table_name: &str) -> TableResult {
+    waiter
+        .wait(
+            || client.describe_table().table_name(table_name).send(),
+            |outcome| match outcome {
+                Ok(output) if output.table.as_ref().is_some_and(is_active) => Acceptor::Success,
+                Ok(_) => Acceptor::Retry,
+                // A new table may not be visible yet.
+                Err(SdkError::ServiceError { err, .. })
+                    if err.is_resource_not_found_exception() =>
+                {
+                    Acceptor::Retry
+                }
+                Err(_) => Acceptor::Failure,
+            },
+        )
+        .await
+}
+
+/// Waits until the table no longer exists.
+pub async fn table_deleted(waiter: &Waiter, client: &Client, table_name: &str) -> TableResult {
+    waiter
+        .wait(
+            || client.describe_table().table_name(table_name).send(),
+            |outcome| match outcome {
+                Ok(_) => Acceptor::Retry,
+                Err(SdkError::ServiceError { err, .. })
+                    if err.is_resource_not_found_exception() =>
+                {
+                    Acceptor::Success
+                }
+                Err(_) => Acceptor::Failure,
+            },
+        )
+        .await
+}
+
+/// Waits until the on-demand backup is `AVAILABLE`.
+pub async fn backup_available(waiter: &Waiter, client: &Client, backup_arn: &str) -> BackupResult {
+    waiter
+        .wait(
+            || client.describe_backup().backup_arn(backup_arn).send(),
+            |outcome| match outcome {
+                Ok(output) => {
+                    let status = output
+                        .backup_description
+                        .as_ref()
+                        .and_then(|d| d.backup_details.as_ref())
+                        .and_then(|d| d.backup_status.as_ref());
+                    match status {
+                        Some(BackupStatus::Available) => Acceptor::Success,
+                        Some(BackupStatus::Deleted) => Acceptor::Failure,
+                        _ => Acceptor::Retry,
+                    }
+                }
+                Err(_) => Acceptor::Failure,
+            },
+        )
+        .await
+}
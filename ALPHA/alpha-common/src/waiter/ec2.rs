@@ -0,0 +1,113 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Waiters for Amazon EC2 instances and EBS snapshots.
+
+use aws_sdk_ec2::error::{DescribeInstancesError, DescribeSnapshotsError};
+use aws_sdk_ec2::model::{Instance, InstanceStateName, SnapshotState};
+use aws_sdk_ec2::output::{DescribeInstancesOutput, DescribeSnapshotsOutput};
+use aws_sdk_ec2::{Client, SdkError};
+
+use super::{Acceptor, WaitError, Waiter};
+
+/// What an instance waiter returns.
+pub type InstanceResult =
+    Result<(), WaitError<DescribeInstancesOutput, SdkError<DescribeInstancesError>>>;
+
+/// What a snapshot waiter returns.
+pub type SnapshotResult =
+    Result<(), WaitError<DescribeSnapshotsOutput, SdkError<DescribeSnapshotsError>>>;
+
+/// Waits until the instance is `running`.
+pub async fn instance_running(
+    waiter: &Waiter,
+    client: &Client,
+    instance_id: &str,
+) -> InstanceResult {
+    wait_for_instance(waiter, client, instance_id, |state| match state {
+        InstanceStateName::Running => Acceptor::Success,
+        InstanceStateName::ShuttingDown
+        | InstanceStateName::Terminated
+        | InstanceStateName::Stopping => Acceptor::Failure,
+        _ => Acceptor::Retry,
+    })
+    .await
+}
+
+/// Waits until the instance is `stopped`.
+pub async fn instance_stopped(
+    waiter: &Waiter,
+    client: &Client,
+    instance_id: &str,
+) -> InstanceResult {
+    wait_for_instance(waiter, client, instance_id, |state| match state {
+        InstanceStateName::Stopped => Acceptor::Success,
+        InstanceStateName::Pending | InstanceStateName::Terminated => Acceptor::Failure,
+        _ => Acceptor::Retry,
+    })
+    .await
+}
+
+async fn wait_for_instance(
+    waiter: &Waiter,
+    client: &Client,
+    instance_id: &str,
+    accept: impl Fn(&InstanceStateName) -> Acceptor,
+) -> InstanceResult {
+    waiter
+        .wait(
+            || client.describe_instances().instance_ids(instance_id).send(),
+            |outcome| match outcome {
+                Ok(output) => {
+                    let state = output
+                        .reservations
+                        .iter()
+                        .flatten()
+                        .flat_map(|r| r.instances.iter().flatten())
+                        .find(|i| i.instance_id.as_deref() == Some(instance_id))
+                        .and_then(|i: &Instance| i.state.as_ref())
+                        .and_then(|s| s.name.as_ref());
+                    state.map_or(Acceptor::Retry, &accept)
+                }
+                // A new instance may not be visible yet.
+                Err(SdkError::ServiceError { err, .. })
+                    if err.code() == Some("InvalidInstanceID.NotFound") =>
+                {
+                    Acceptor::Retry
+                }
+                Err(_) => Acceptor::Failure,
+            },
+        )
+        .await
+}
+
+/// Waits until the snapshot is `completed`.
+pub async fn snapshot_completed(
+    waiter: &Waiter,
+    client: &Client,
+    snapshot_id: &str,
+) -> SnapshotResult {
+    waiter
+        .wait(
+            || client.describe_snapshots().snapshot_ids(snapshot_id).send(),
+            |outcome| match outcome {
+                Ok(output) => {
+                    let state = output
+                        .snapshots
+                        .iter()
+                        .flatten()
+                        .find(|s| s.snapshot_id.as_deref() == Some(snapshot_id))
+                        .and_then(|s| s.state.as_ref());
+                    match state {
+                        Some(SnapshotState::Completed) => Acceptor::Success,
+                        Some(SnapshotState::Error) => Acceptor::Failure,
+                        _ => Acceptor::Retry,
+                    }
+                }
+                Err(_) => Acceptor::Failure,
+            },
+        )
+        .await
+}
@@ -0,0 +1,37 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Waiters for Amazon Kinesis data streams.
+
+use aws_sdk_kinesis::error::DescribeStreamError;
+use aws_sdk_kinesis::model::StreamStatus;
+use aws_sdk_kinesis::output::DescribeStreamOutput;
+use aws_sdk_kinesis::{Client, SdkError};
+
+use super::{Acceptor, WaitError, Waiter};
+
+/// What a stream waiter returns.
+pub type StreamResult = Result<(), WaitError<DescribeStreamOutput, SdkError<DescribeStreamError>>>;
+
+/// Waits until the stream is `ACTIVE`.
+pub async fn stream_active(waiter: &Waiter, client: &Client, stream_name: &str) -> StreamResult {
+    waiter
+        .wait(
+            || client.describe_stream().stream_name(stream_name).send(),
+            |outcome| match outcome {
+                Ok(output) => match output
+                    .stream_description
+                    .as_ref()
+                    .and_then(|d| d.stream_status.as_ref())
+                {
+                    Some(StreamStatus::Active) => Acceptor::Success,
+                    Some(StreamStatus::Deleting) => Acceptor::Failure,
+                    _ => Acceptor::Retry,
+                },
+                Err(_) => Acceptor::Failure,
+            },
+        )
+        .await
+}
@@ -0,0 +1,33 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Waiters for Amazon QLDB ledgers.
+
+use aws_sdk_qldb::error::DescribeLedgerError;
+use aws_sdk_qldb::model::LedgerState;
+use aws_sdk_qldb::output::DescribeLedgerOutput;
+use aws_sdk_qldb::{Client, SdkError};
+
+use super::{Acceptor, WaitError, Waiter};
+
+/// What a ledger waiter returns.
+pub type LedgerResult = Result<(), WaitError<DescribeLedgerOutput, SdkError<DescribeLedgerError>>>;
+
+/// Waits until the ledger is `ACTIVE`.
+pub async fn ledger_active(waiter: &Waiter, client: &Client, name: &str) -> LedgerResult {
+    waiter
+        .wait(
+            || client.describe_ledger().name(name).send(),
+            |outcome| match outcome.as_ref().map(|output| output.state.as_ref()) {
+                Ok(Some(LedgerState::Active)) => Acceptor::Success,
+                Ok(Some(LedgerState::Deleting)) | Ok(Some(LedgerState::Deleted)) => {
+                    Acceptor::Failure
+                }
+                Ok(_) => Acceptor::Retry,
+                Err(_) => Acceptor::Failure,
+            },
+        )
+        .await
+}
@@ -3,40 +3,68 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_apigateway::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::{date_time, Listing};
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::{CommonOpt, Shared};
+use aws_sdk_apigateway::{Client, Error, PKG_VERSION};
+use aws_smithy_types_convert::date_time::DateTimeExt;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 // Displays the Amazon API Gateway REST APIs in the Region.
-async fn show_apis(client: &Client) -> Result<(), Error> {
-    let resp = client.get_rest_apis().send().await?;
+async fn show_apis(shared: &Shared, pages: &PageOpt, client: &Client) -> Result<(), Error> {
+    let client = client.clone();
+    let apis = paginate(pages, move |position, limit| {
+        let request = client
+            .get_rest_apis()
+            .set_position(position)
+            .set_limit(limit);
+        async move {
+            request
+                .send()
+                .await
+                .map(|resp| Page::with_token(resp.items.unwrap_or_default(), resp.position))
+        }
+    })
+    .try_collect()
+    .await?;
 
-    for api in resp.items.unwrap_or_default() {
-        println!("ID:          {}", api.id.as_deref().unwrap_or_default());
-        println!("Name:        {}", api.name.as_deref().unwrap_or_default());
-        println!(
-            "Description: {}",
-            api.description.as_deref().unwrap_or_default()
-        );
-        println!(
-            "Version:     {}",
-            api.version.as_deref().unwrap_or_default()
-        );
-        println!("Created:     {}", api.created_date.unwrap().to_chrono());
-        println!();
+    let mut listing = Listing::new(&["Id", "Name", "Description", "Version", "CreatedDate"]);
+    for api in &apis {
+        listing.push(vec![
+            api.id.as_deref().into(),
+            api.name.as_deref().into(),
+            api.description.as_deref().into(),
+            api.version.as_deref().into(),
+            date_time(api.created_date.as_ref()),
+        ]);
     }
 
+    shared.print(&listing, || {
+        for api in apis {
+            println!("ID:          {}", api.id.as_deref().unwrap_or_default());
+            println!("Name:        {}", api.name.as_deref().unwrap_or_default());
+            println!(
+                "Description: {}",
+                api.description.as_deref().unwrap_or_default()
+            );
+            println!(
+                "Version:     {}",
+                api.version.as_deref().unwrap_or_default()
+            );
+            println!("Created:     {}", api.created_date.unwrap().to_chrono_utc());
+            println!();
+        }
+    });
+
     Ok(())
 }
 
@@ -44,32 +72,21 @@ async fn show_apis(client: &Client) -> Result<(), Error> {
 ///
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///   If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///   If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-    let Opt { region, verbose } = Opt::from_args();
-
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let Opt { pages, common } = Opt::from_args();
 
-    if verbose {
-        println!("APIGateway client version: {}", PKG_VERSION);
-        println!(
-            "Region:                    {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
+    let shared = common.load().await;
 
-        println!();
-    }
+    shared.banner("APIGateway", PKG_VERSION, &[]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.apigateway();
 
-    show_apis(&client).await
+    show_apis(&shared, &pages, &client).await
 }
@@ -3,36 +3,71 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
+use alpha_common::output::{date_time, Listing};
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::{CommonOpt, Shared};
 use aws_sdk_applicationautoscaling::model::ServiceNamespace;
-use aws_sdk_applicationautoscaling::{Client, Error, Region, PKG_VERSION};
+use aws_sdk_applicationautoscaling::{Client, Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 // Lists the Application Auto Scaling policies.
-async fn show_policies(client: &Client) -> Result<(), Error> {
-    let response = client
-        .describe_scaling_policies()
-        .service_namespace(ServiceNamespace::Ec2)
-        .send()
-        .await?;
-    if let Some(policies) = response.scaling_policies {
+async fn show_policies(shared: &Shared, pages: &PageOpt, client: &Client) -> Result<(), Error> {
+    let client = client.clone();
+    let policies = paginate(pages, move |token, max_results| {
+        let request = client
+            .describe_scaling_policies()
+            .service_namespace(ServiceNamespace::Ec2)
+            .set_next_token(token)
+            .set_max_results(max_results);
+        async move {
+            request.send().await.map(|resp| {
+                Page::with_token(resp.scaling_policies.unwrap_or_default(), resp.next_token)
+            })
+        }
+    })
+    .try_collect()
+    .await?;
+
+    let mut listing = Listing::new(&[
+        "PolicyName",
+        "PolicyARN",
+        "ServiceNamespace",
+        "ResourceId",
+        "ScalableDimension",
+        "PolicyType",
+        "CreationTime",
+    ]);
+    for policy in &policies {
+        listing.push(vec![
+            policy.policy_name.as_deref().into(),
+            policy.policy_arn.as_deref().into(),
+            policy.service_namespace.as_ref().map(|n| n.as_str()).into(),
+            policy.resource_id.as_deref().into(),
+            policy
+                .scalable_dimension
+                .as_ref()
+                .map(|d| d.as_str())
+                .into(),
+            policy.policy_type.as_ref().map(|t| t.as_str()).into(),
+            date_time(policy.creation_time.as_ref()),
+        ]);
+    }
+
+    shared.print(&listing, || {
         println!("Auto Scaling Policies:");
         for policy in policies {
             println!("{:?}\n", policy);
         }
-    }
-    println!("Next token: {:?}", response.next_token);
+    });
 
     Ok(())
 }
@@ -40,31 +75,21 @@ async fn show_policies(client: &Client) -> Result<(), Error> {
 /// Lists your Application Auto Scaling policies in the Region.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The region containing the buckets.
 ///   If not supplied, uses the value of the **AWS_DEFAULT_REGION** environment variable.
 ///   If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
-    let Opt { region, verbose } = Opt::from_args();
+    let Opt { pages, common } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
+    let shared = common.load().await;
 
-    if verbose {
-        println!("Application Auto Scaling client version: {}", PKG_VERSION);
-        println!(
-            "Region:                                  {:?}",
-            shared_config.region().unwrap()
-        );
-        println!();
-    }
+    shared.banner("Application Auto Scaling", PKG_VERSION, &[]);
 
-    let client = Client::new(&shared_config);
+    let client = shared.applicationautoscaling();
 
-    show_policies(&client).await
+    show_policies(&shared, &pages, &client).await
 }
@@ -3,8 +3,8 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_autoscaling::{Client, Error, Region, PKG_VERSION};
+use alpha_common::CommonOpt;
+use aws_sdk_autoscaling::{Client, Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -17,13 +17,8 @@ struct Opt {
     #[structopt(short, long)]
     instance_id: String,
 
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 // Creates a group.
@@ -53,34 +48,24 @@ async fn create_group(client: &Client, name: &str, id: &str) -> Result<(), Error
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
         autoscaling_name,
         instance_id,
-        region,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
-
-    if verbose {
-        println!("Auto Scaling client version: {}", PKG_VERSION);
-        println!(
-            "Region:                      {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Auto Scaling group name:     {}", &autoscaling_name);
-        println!("Instance ID:                 {}", &instance_id);
+    let shared = common.load().await;
 
-        println!();
-    }
+    shared.banner(
+        "Auto Scaling",
+        PKG_VERSION,
+        &[
+            ("Auto Scaling group name", &autoscaling_name),
+            ("Instance ID", &instance_id),
+        ],
+    );
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.autoscaling();
 
     create_group(&client, &autoscaling_name, &instance_id).await
 }
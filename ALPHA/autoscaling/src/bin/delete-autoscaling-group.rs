@@ -3,8 +3,8 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_autoscaling::{Client, Error, Region, PKG_VERSION};
+use alpha_common::CommonOpt;
+use aws_sdk_autoscaling::{Client, Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -17,13 +17,8 @@ struct Opt {
     #[structopt(short, long)]
     force: bool,
 
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 // Deletes a group.
@@ -31,7 +26,7 @@ async fn delete_group(client: &Client, name: &str, force: bool) -> Result<(), Er
     client
         .delete_auto_scaling_group()
         .auto_scaling_group_name(name)
-        .set_force_delete(force.then(|| true))
+        .set_force_delete(force.then_some(true))
         .send()
         .await?;
 
@@ -51,35 +46,24 @@ async fn delete_group(client: &Client, name: &str, force: bool) -> Result<(), Er
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
         autoscaling_name,
         force,
-        region,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
-
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("Auto Scaling client version: {}", PKG_VERSION);
-        println!(
-            "Region:                      {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Auto Scaling group name:     {}", &autoscaling_name);
-        println!("Force deletion?:             {}", &force);
-        println!();
-    }
+    shared.banner(
+        "Auto Scaling",
+        PKG_VERSION,
+        &[
+            ("Auto Scaling group name", &autoscaling_name),
+            ("Force deletion?", &force),
+        ],
+    );
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.autoscaling();
 
     delete_group(&client, &autoscaling_name, force).await
 }
@@ -3,44 +3,75 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_autoscaling::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::Listing;
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::{CommonOpt, Shared};
+use aws_sdk_autoscaling::{Client, Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 // Lists your groups.
-async fn list_groups(client: &Client) -> Result<(), Error> {
-    let resp = client.describe_auto_scaling_groups().send().await?;
-
-    println!("Groups:");
-
-    let groups = resp.auto_scaling_groups.unwrap_or_default();
-
+async fn list_groups(shared: &Shared, pages: &PageOpt, client: &Client) -> Result<(), Error> {
+    let client = client.clone();
+    let groups = paginate(pages, move |token, max_records| {
+        let request = client
+            .describe_auto_scaling_groups()
+            .set_next_token(token)
+            .set_max_records(max_records);
+        async move {
+            request.send().await.map(|resp| {
+                Page::with_token(
+                    resp.auto_scaling_groups.unwrap_or_default(),
+                    resp.next_token,
+                )
+            })
+        }
+    })
+    .try_collect()
+    .await?;
+
+    let mut listing = Listing::new(&[
+        "AutoScalingGroupName",
+        "AutoScalingGroupARN",
+        "MinSize",
+        "MaxSize",
+    ]);
     for group in &groups {
-        println!(
-            "  {}",
-            group.auto_scaling_group_name.as_deref().unwrap_or_default()
-        );
-        println!(
-            "  ARN:          {}",
-            group.auto_scaling_group_arn.as_deref().unwrap_or_default()
-        );
-        println!("  Minimum size: {}", group.min_size.unwrap_or_default());
-        println!("  Maximum size: {}", group.max_size.unwrap_or_default());
-        println!();
+        listing.push(vec![
+            group.auto_scaling_group_name.as_deref().into(),
+            group.auto_scaling_group_arn.as_deref().into(),
+            group.min_size.into(),
+            group.max_size.into(),
+        ]);
     }
 
-    println!("Found {} group(s)", groups.len());
+    shared.print(&listing, || {
+        println!("Groups:");
+
+        for group in &groups {
+            println!(
+                "  {}",
+                group.auto_scaling_group_name.as_deref().unwrap_or_default()
+            );
+            println!(
+                "  ARN:          {}",
+                group.auto_scaling_group_arn.as_deref().unwrap_or_default()
+            );
+            println!("  Minimum size: {}", group.min_size.unwrap_or_default());
+            println!("  Maximum size: {}", group.max_size.unwrap_or_default());
+            println!();
+        }
+
+        println!("Found {} group(s)", groups.len());
+    });
 
     Ok(())
 }
@@ -48,33 +79,21 @@ async fn list_groups(client: &Client) -> Result<(), Error> {
 /// Lists your Amazon EC2 Auto Scaling groups in the Region.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { pages, common } = Opt::from_args();
 
-    let Opt { region, verbose } = Opt::from_args();
+    let shared = common.load().await;
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
-
-    if verbose {
-        println!("Auto Scaling client version: {}", PKG_VERSION);
-        println!(
-            "Region:                      {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-
-        println!();
-    }
+    shared.banner("Auto Scaling", PKG_VERSION, &[]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.autoscaling();
 
-    list_groups(&client).await
+    list_groups(&shared, &pages, &client).await
 }
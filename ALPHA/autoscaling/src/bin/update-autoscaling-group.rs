@@ -3,8 +3,8 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_autoscaling::{Client, Error, Region, PKG_VERSION};
+use alpha_common::CommonOpt;
+use aws_sdk_autoscaling::{Client, Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -17,13 +17,8 @@ struct Opt {
     #[structopt(short, long)]
     max_size: i32,
 
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 // Updates the size of a group.
@@ -51,33 +46,24 @@ async fn update_group(client: &Client, name: &str, size: i32) -> Result<(), Erro
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
         autoscaling_name,
         max_size,
-        region,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("Auto Scaling client version: {}", PKG_VERSION);
-        println!(
-            "Region:                      {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("AutoScaling group name:      {}", &autoscaling_name);
-        println!("Max size:                    {}", &max_size);
-        println!();
-    }
+    shared.banner(
+        "Auto Scaling",
+        PKG_VERSION,
+        &[
+            ("AutoScaling group name", &autoscaling_name),
+            ("Max size", &max_size),
+        ],
+    );
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.autoscaling();
 
     update_group(&client, &autoscaling_name, max_size).await
 }
@@ -3,19 +3,14 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_batch::{Client, Error, Region, PKG_VERSION};
+use alpha_common::CommonOpt;
+use aws_sdk_batch::{Client, Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 // Lists your AWS Batch compute environments.
@@ -45,27 +40,13 @@ async fn show_envs(client: &Client) -> Result<(), Error> {
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-    let Opt { region, verbose } = Opt::from_args();
-
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-
-    println!();
+    let Opt { common } = Opt::from_args();
 
-    if verbose {
-        println!("Batch client version: {}", PKG_VERSION);
-        println!(
-            "Region:               {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
+    let shared = common.load().await;
 
-        println!();
-    }
+    shared.banner("Batch", PKG_VERSION, &[]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.batch();
 
     show_envs(&client).await
 }
@@ -3,62 +3,59 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_cloudformation::{Client, Error, Region, PKG_VERSION};
+use alpha_common::waiter::{cloudformation, WaitOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_cloudformation::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The name of the AWS CloudFormation stack.
     #[structopt(short, long)]
     stack_name: String,
 
-    /// Whether to display additional runtime information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    wait: WaitOpt,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Deletes a CloudFormation stack.
 /// # Arguments
 ///
 /// * `-s STACK-NAME` - The name of the stack.
+/// * `[--wait]` - Whether to wait until the stack is deleted.
+/// * `[--max-wait SECONDS]` - The most seconds to wait. Defaults to 600.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
-        region,
         stack_name,
-        verbose,
+        wait,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("CloudFormation client version: {}", PKG_VERSION);
-        println!(
-            "Region:                        {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Stack:                         {}", &stack_name);
-        println!();
-    }
+    shared.banner("CloudFormation", PKG_VERSION, &[("Stack", &stack_name)]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.cloudformation();
 
-    client.delete_stack().stack_name(stack_name).send().await?;
+    client.delete_stack().stack_name(&stack_name).send().await?;
+
+    if let Some(waiter) = wait.waiter() {
+        println!("Waiting for the stack to be deleted.");
+        if let Err(e) = cloudformation::stack_delete_complete(&waiter, &client, &stack_name).await {
+            println!("Got an error waiting for the stack:");
+            println!("{}", e);
+            shared.exit(1);
+        }
+        println!("Stack is gone.");
+    }
 
     println!("Stack deleted");
     println!();
@@ -3,23 +3,19 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_cloudformation::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::{date_time, Listing};
+use alpha_common::CommonOpt;
+use aws_sdk_cloudformation::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The name of the AWS CloudFormation stack.
     #[structopt(short, long)]
     stack_name: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Retrieves the status of a CloudFormation stack in the Region.
@@ -32,31 +28,13 @@ struct Opt {
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { stack_name, common } = Opt::from_args();
 
-    let Opt {
-        region,
-        stack_name,
-        verbose,
-    } = Opt::from_args();
+    let shared = common.load().await;
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    shared.banner("CloudFormation", PKG_VERSION, &[("Stack", &stack_name)]);
 
-    if verbose {
-        println!("CloudFormation version: {}", PKG_VERSION);
-        println!(
-            "Region:                 {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Stack:                  {}", &stack_name);
-        println!();
-    }
-
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.cloudformation();
 
     // Return an error if stack_name does not exist
     let resp = client
@@ -67,11 +45,21 @@ async fn main() -> Result<(), Error> {
 
     // Otherwise we get a list of stacks that match the stack_name.
     // The list should only have one item, so just access is via pop().
-    let status = resp.stacks.unwrap_or_default().pop().unwrap().stack_status;
+    let stack = resp.stacks.unwrap_or_default().pop().unwrap();
+
+    let mut listing = Listing::new(&["StackName", "StackId", "StackStatus", "CreationTime"]);
+    listing.push(vec![
+        stack.stack_name.as_deref().into(),
+        stack.stack_id.as_deref().into(),
+        stack.stack_status.as_ref().map(|s| s.as_str()).into(),
+        date_time(stack.creation_time.as_ref()),
+    ]);
 
-    println!("Stack status: {}", status.unwrap().as_ref());
+    shared.print(&listing, || {
+        println!("Stack status: {}", stack.stack_status.unwrap().as_ref());
 
-    println!();
+        println!();
+    });
 
     Ok(())
 }
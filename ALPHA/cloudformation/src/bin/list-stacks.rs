@@ -3,61 +3,69 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_cloudformation::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::{date_time, Listing};
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_cloudformation::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional runtime information
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists the name and status of your AWS CloudFormation stacks in the Region.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { pages, common } = Opt::from_args();
 
-    let Opt { region, verbose } = Opt::from_args();
+    let shared = common.load().await;
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    shared.banner("CloudFormation", PKG_VERSION, &[]);
 
-    println!();
+    let client = shared.cloudformation();
 
-    if verbose {
-        println!("CloudFormation client version: {}", PKG_VERSION);
-        println!(
-            "Region:                        {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
+    // ListStacks has no page size.
+    let summaries = paginate(&pages, move |token, _| {
+        let request = client.list_stacks().set_next_token(token);
+        async move {
+            request.send().await.map(|resp| {
+                Page::with_token(resp.stack_summaries.unwrap_or_default(), resp.next_token)
+            })
+        }
+    })
+    .try_collect()
+    .await?;
 
-        println!();
+    let mut listing = Listing::new(&["StackName", "StackId", "StackStatus", "CreationTime"]);
+    for s in &summaries {
+        listing.push(vec![
+            s.stack_name.as_deref().into(),
+            s.stack_id.as_deref().into(),
+            s.stack_status.as_ref().map(|s| s.as_str()).into(),
+            date_time(s.creation_time.as_ref()),
+        ]);
     }
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
-
-    let stacks = client.list_stacks().send().await?;
-
-    for s in stacks.stack_summaries.unwrap_or_default() {
-        println!("{}", s.stack_name.as_deref().unwrap_or_default());
-        println!("  Status: {:?}", s.stack_status.unwrap());
-        println!();
-    }
+    shared.print(&listing, || {
+        for s in summaries {
+            println!("{}", s.stack_name.as_deref().unwrap_or_default());
+            println!("  Status: {:?}", s.stack_status.unwrap());
+            println!();
+        }
+    });
 
     Ok(())
 }
@@ -3,56 +3,65 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_cognitoidentity::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::Listing;
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_cognitoidentity::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists your Amazon Cognito identity pools in the Region.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///   If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///   If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { pages, common } = Opt::from_args();
 
-    let Opt { region, verbose } = Opt::from_args();
+    let shared = common.load().await;
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    shared.banner("Cognito", PKG_VERSION, &[]);
 
-    if verbose {
-        println!("Cognito client version: {}", PKG_VERSION);
-        println!(
-            "Region:                 {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
+    let client = shared.cognitoidentity();
 
-        println!();
-    }
-
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let pools = paginate(&pages, move |token, max_results| {
+        let request = client
+            .list_identity_pools()
+            .set_next_token(token)
+            // The service requires a page size.
+            .max_results(max_results.unwrap_or(60));
+        async move {
+            request.send().await.map(|resp| {
+                Page::with_token(resp.identity_pools.unwrap_or_default(), resp.next_token)
+            })
+        }
+    })
+    .try_collect()
+    .await?;
 
-    let response = client.list_identity_pools().max_results(10).send().await?;
+    let mut listing = Listing::new(&["IdentityPoolId", "IdentityPoolName"]);
+    for pool in &pools {
+        listing.push(vec![
+            pool.identity_pool_id.as_deref().into(),
+            pool.identity_pool_name.as_deref().into(),
+        ]);
+    }
 
-    // Print IDs and names of pools.
-    if let Some(pools) = response.identity_pools {
+    shared.print(&listing, || {
+        // Print IDs and names of pools.
         println!("Identity pools:");
         for pool in pools {
             let id = pool.identity_pool_id.unwrap_or_default();
@@ -61,9 +70,7 @@ async fn main() -> Result<(), Error> {
             println!("  Identity pool name: {}", name);
             println!();
         }
-    }
-
-    println!("Next token: {:?}", response.next_token);
+    });
 
     Ok(())
 }
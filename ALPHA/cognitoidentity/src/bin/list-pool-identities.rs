@@ -3,74 +3,87 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_cognitoidentity::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::{date_time, Listing};
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_cognitoidentity::{Error, PKG_VERSION};
+use aws_smithy_types_convert::date_time::DateTimeExt;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The ID of the identity pool to describe.
     #[structopt(short, long)]
     identity_pool_id: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    pages: PageOpt,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists the identities in an Amazon Cognito identity pool.
 /// # Arguments
 ///
 /// * `-i IDENTITY-POOL-ID` - The ID of the identity pool.
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///   If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///   If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
         identity_pool_id,
-        region,
-        verbose,
+        pages,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
-
-    if verbose {
-        println!("Cognito client version: {}", PKG_VERSION);
-        println!(
-            "Region:                 {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Identity pool ID:       {}", identity_pool_id);
-        println!();
+    let shared = common.load().await;
+
+    shared.banner(
+        "Cognito",
+        PKG_VERSION,
+        &[("Identity pool ID", &identity_pool_id)],
+    );
+
+    let client = shared.cognitoidentity();
+
+    let ids = paginate(&pages, move |token, max_results| {
+        let request = client
+            .list_identities()
+            .identity_pool_id(&identity_pool_id)
+            .set_next_token(token)
+            // The service requires a page size.
+            .max_results(max_results.unwrap_or(60));
+        async move {
+            request
+                .send()
+                .await
+                .map(|resp| Page::with_token(resp.identities.unwrap_or_default(), resp.next_token))
+        }
+    })
+    .try_collect()
+    .await?;
+
+    let mut listing = Listing::new(&["IdentityId", "Logins", "CreationDate", "LastModifiedDate"]);
+    for id in &ids {
+        listing.push(vec![
+            id.identity_id.as_deref().into(),
+            id.logins.clone().into(),
+            date_time(id.creation_date.as_ref()),
+            date_time(id.last_modified_date.as_ref()),
+        ]);
     }
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
-
-    let response = client
-        .list_identities()
-        .identity_pool_id(identity_pool_id)
-        .max_results(10)
-        .send()
-        .await?;
-
-    if let Some(ids) = response.identities {
+    shared.print(&listing, || {
         println!("Identitities:");
         for id in ids {
-            let creation_timestamp = id.creation_date.unwrap().to_chrono();
+            let creation_timestamp = id.creation_date.unwrap().to_chrono_utc();
             let idid = id.identity_id.unwrap_or_default();
-            let mod_timestamp = id.last_modified_date.unwrap().to_chrono();
+            let mod_timestamp = id.last_modified_date.unwrap().to_chrono_utc();
             println!("  Creation date:      {}", creation_timestamp);
             println!("  ID:                 {}", idid);
             println!("  Last modified date: {}", mod_timestamp);
@@ -82,11 +95,9 @@ async fn main() -> Result<(), Error> {
 
             println!();
         }
-    }
-
-    println!("Next token: {:?}", response.next_token);
 
-    println!();
+        println!();
+    });
 
     Ok(())
 }
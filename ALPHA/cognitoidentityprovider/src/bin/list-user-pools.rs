@@ -3,54 +3,69 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_cognitoidentityprovider::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::{date_time, Listing};
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_cognitoidentityprovider::{Error, PKG_VERSION};
+use aws_smithy_types_convert::date_time::DateTimeExt;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists your Amazon Cognito user pools in the Region.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The region containing the buckets.
 ///   If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///   If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { pages, common } = Opt::from_args();
 
-    let Opt { region, verbose } = Opt::from_args();
+    let shared = common.load().await;
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    shared.banner("Cognito", PKG_VERSION, &[]);
 
-    if verbose {
-        println!("Cognito client version: {}", PKG_VERSION);
-        println!(
-            "Region:                 {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
+    let client = shared.cognitoidentityprovider();
 
-        println!();
-    }
+    let pools = paginate(&pages, move |token, max_results| {
+        let request = client
+            .list_user_pools()
+            .set_next_token(token)
+            // The service requires a page size.
+            .max_results(max_results.unwrap_or(60));
+        async move {
+            request
+                .send()
+                .await
+                .map(|resp| Page::with_token(resp.user_pools.unwrap_or_default(), resp.next_token))
+        }
+    })
+    .try_collect()
+    .await?;
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let mut listing = Listing::new(&["Id", "Name", "Status", "LastModifiedDate", "CreationDate"]);
+    for pool in &pools {
+        listing.push(vec![
+            pool.id.as_deref().into(),
+            pool.name.as_deref().into(),
+            pool.status.as_ref().map(|s| s.as_str()).into(),
+            date_time(pool.last_modified_date.as_ref()),
+            date_time(pool.creation_date.as_ref()),
+        ]);
+    }
 
-    let response = client.list_user_pools().max_results(10).send().await?;
-    if let Some(pools) = response.user_pools {
+    shared.print(&listing, || {
         println!("User pools:");
         for pool in pools {
             println!("  ID:              {}", pool.id.unwrap_or_default());
@@ -59,16 +74,15 @@ async fn main() -> Result<(), Error> {
             println!("  Lambda Config:   {:?}", pool.lambda_config.unwrap());
             println!(
                 "  Last modified:   {}",
-                pool.last_modified_date.unwrap().to_chrono()
+                pool.last_modified_date.unwrap().to_chrono_utc()
             );
             println!(
                 "  Creation date:   {:?}",
-                pool.creation_date.unwrap().to_chrono()
+                pool.creation_date.unwrap().to_chrono_utc()
             );
             println!();
         }
-    }
-    println!("Next token: {}", response.next_token.unwrap_or_default());
+    });
 
     Ok(())
 }
@@ -3,24 +3,27 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_cognitosync::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::{date_time, Listing};
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_cognitosync::{Error, PKG_VERSION};
+use aws_smithy_types_convert::date_time::DateTimeExt;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists the identity pools registered with Amazon Cognito in the Region.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The region in which the client is created.
 ///   If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///   If the environment variable is not set, defaults to **us-west-2**.
@@ -28,35 +31,47 @@ struct Opt {
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { pages, common } = Opt::from_args();
 
-    let Opt { region, verbose } = Opt::from_args();
+    let shared = common.load().await;
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    shared.banner("Cognito", PKG_VERSION, &[]);
 
-    if verbose {
-        println!("Cognito client version: {}", PKG_VERSION);
-        println!(
-            "Region:                 {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
+    let client = shared.cognitosync();
 
-        println!();
-    }
-
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let pools = paginate(&pages, move |token, max_results| {
+        let request = client
+            .list_identity_pool_usage()
+            .set_next_token(token)
+            .set_max_results(max_results);
+        async move {
+            request.send().await.map(|resp| {
+                Page::with_token(
+                    resp.identity_pool_usages.unwrap_or_default(),
+                    resp.next_token,
+                )
+            })
+        }
+    })
+    .try_collect()
+    .await?;
 
-    let response = client
-        .list_identity_pool_usage()
-        .max_results(10)
-        .send()
-        .await?;
+    let mut listing = Listing::new(&[
+        "IdentityPoolId",
+        "DataStorage",
+        "SyncSessionsCount",
+        "LastModifiedDate",
+    ]);
+    for pool in &pools {
+        listing.push(vec![
+            pool.identity_pool_id.as_deref().into(),
+            pool.data_storage.into(),
+            pool.sync_sessions_count.into(),
+            date_time(pool.last_modified_date.as_ref()),
+        ]);
+    }
 
-    if let Some(pools) = response.identity_pool_usages {
+    shared.print(&listing, || {
         println!("Identity pools:");
 
         for pool in pools {
@@ -74,13 +89,11 @@ async fn main() -> Result<(), Error> {
             );
             println!(
                 "  Last modified:       {}",
-                pool.last_modified_date.unwrap().to_chrono()
+                pool.last_modified_date.unwrap().to_chrono_utc()
             );
             println!();
         }
-    }
-
-    println!("Next token: {}", response.next_token.unwrap_or_default());
+    });
 
     Ok(())
 }
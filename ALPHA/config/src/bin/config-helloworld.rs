@@ -3,17 +3,13 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
+use alpha_common::CommonOpt;
 use aws_sdk_config::model::ResourceType;
-use aws_sdk_config::{Client, Error, Region, PKG_VERSION};
+use aws_sdk_config::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The resource id.
     #[structopt(long)]
     resource_id: String,
@@ -22,9 +18,8 @@ struct Opt {
     #[structopt(long)]
     resource_type: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 // Retrieves the configuration history for a resource.
@@ -62,31 +57,17 @@ async fn get_history(
 /// * `[-v]` - Whether to display information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
     let Opt {
-        region,
         resource_id,
         resource_type,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("Config client version: {}", PKG_VERSION);
-        println!(
-            "Region:                {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!();
-    }
+    shared.banner("Config", PKG_VERSION, &[]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.config();
 
     // parse resource type from user input
     let parsed = ResourceType::from(resource_type.as_str());
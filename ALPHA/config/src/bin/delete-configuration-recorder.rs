@@ -3,23 +3,18 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_config::{Client, Error, Region, PKG_VERSION};
+use alpha_common::CommonOpt;
+use aws_sdk_config::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The name of the configuration recorder to delete.
     #[structopt(short, long)]
     name: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 // Deletes a configuration recorder.
@@ -51,31 +46,13 @@ async fn delete_recorder(
 /// * `[-v]` - Whether to display information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-    let Opt {
-        name,
-        region,
-        verbose,
-    } = Opt::from_args();
-
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let Opt { name, common } = Opt::from_args();
 
-    if verbose {
-        println!("Config client version:  {}", PKG_VERSION);
-        println!(
-            "Region:                 {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Configuration recorder: {}", &name);
+    let shared = common.load().await;
 
-        println!();
-    }
+    shared.banner("Config", PKG_VERSION, &[("Configuration recorder", &name)]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.config();
 
     delete_recorder(&client, &name).await
 }
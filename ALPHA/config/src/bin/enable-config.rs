@@ -3,21 +3,17 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
+use alpha_common::CommonOpt;
 use aws_sdk_config::model::{
     ConfigSnapshotDeliveryProperties, ConfigurationRecorder, DeliveryChannel,
     MaximumExecutionFrequency, RecordingGroup, ResourceType,
 };
-use aws_sdk_config::{Client, Error, Region, PKG_VERSION};
+use aws_sdk_config::{Error, PKG_VERSION};
 use std::process;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The name of the Amazon bucket.
     #[structopt(short, long)]
     bucket: String,
@@ -46,9 +42,8 @@ struct Opt {
     #[structopt(default_value = "AWS::DynamoDB::Table", short, long)]
     type_: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 // Enables config.
@@ -162,9 +157,7 @@ async fn enable_config(
 /// Need: s3 key prefix AND kms key
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
     let Opt {
-        region,
         bucket,
         iam_arn,
         kms_arn,
@@ -172,33 +165,26 @@ async fn main() -> Result<(), Error> {
         prefix,
         sns_arn,
         type_,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-
-    println!();
-
-    if verbose {
-        println!("Config client version:          {}", PKG_VERSION);
-        println!(
-            "Region:                {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Resource type:                  {}", type_);
-        println!("Config (delivery channel) name: {}", name);
-        println!("Bucket:                         {}", bucket);
-        println!("Prefix:                         {}", prefix);
-        println!("SNS ARN:                        {}", sns_arn);
-        println!("IAM ARN:                        {}", iam_arn);
-        println!("KMS ARN:                        {}", kms_arn);
-        println!();
-    }
-
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let shared = common.load().await;
+
+    shared.banner(
+        "Config",
+        PKG_VERSION,
+        &[
+            ("Resource type", &type_),
+            ("Config (delivery channel) name", &name),
+            ("Bucket", &bucket),
+            ("Prefix", &prefix),
+            ("SNS ARN", &sns_arn),
+            ("IAM ARN", &iam_arn),
+            ("KMS ARN", &kms_arn),
+        ],
+    );
+
+    let client = shared.config();
 
     enable_config(
         &client, &name, &kms_arn, &bucket, &sns_arn, &iam_arn, &prefix,
@@ -3,36 +3,45 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_config::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::Listing;
+use alpha_common::{CommonOpt, Shared};
+use aws_sdk_config::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 // Lists your recorders.
-async fn show_recorders(client: &aws_sdk_config::Client) -> Result<(), aws_sdk_config::Error> {
+async fn show_recorders(
+    shared: &Shared,
+    client: &aws_sdk_config::Client,
+) -> Result<(), aws_sdk_config::Error> {
     let resp = client.describe_configuration_recorders().send().await?;
 
     let recorders = resp.configuration_recorders.unwrap_or_default();
 
-    if recorders.is_empty() {
-        println!("You have no configuration recorders")
-    } else {
-        for recorder in recorders {
-            println!("Recorder: {}", recorder.name.as_deref().unwrap_or_default());
-        }
+    let mut listing = Listing::new(&["Name", "RoleARN"]);
+    for recorder in &recorders {
+        listing.push(vec![
+            recorder.name.as_deref().into(),
+            recorder.role_arn.as_deref().into(),
+        ]);
     }
 
-    println!();
+    shared.print(&listing, || {
+        if recorders.is_empty() {
+            println!("You have no configuration recorders")
+        } else {
+            for recorder in &recorders {
+                println!("Recorder: {}", recorder.name.as_deref().unwrap_or_default());
+            }
+        }
+
+        println!();
+    });
 
     Ok(())
 }
@@ -47,26 +56,13 @@ async fn show_recorders(client: &aws_sdk_config::Client) -> Result<(), aws_sdk_c
 /// * `[-v]` - Whether to display information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-    let Opt { region, verbose } = Opt::from_args();
-
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let Opt { common } = Opt::from_args();
 
-    if verbose {
-        println!("Config client version: {}", PKG_VERSION);
-        println!(
-            "Region:                {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
+    let shared = common.load().await;
 
-        println!();
-    }
+    shared.banner("Config", PKG_VERSION, &[]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.config();
 
-    show_recorders(&client).await
+    show_recorders(&shared, &client).await
 }
@@ -3,38 +3,48 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_config::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::Listing;
+use alpha_common::{CommonOpt, Shared};
+use aws_sdk_config::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 // Lists your deliver channels.
-async fn show_channels(client: &aws_sdk_config::Client) -> Result<(), aws_sdk_config::Error> {
+async fn show_channels(
+    shared: &Shared,
+    client: &aws_sdk_config::Client,
+) -> Result<(), aws_sdk_config::Error> {
     let resp = client.describe_delivery_channels().send().await?;
 
     let channels = resp.delivery_channels.unwrap_or_default();
 
     let num_channels = channels.len();
 
-    if num_channels == 0 {
-        println!("You have no delivery channels")
-    } else {
-        for channel in channels {
-            println!("  Channel: {}", channel.name.as_deref().unwrap_or_default());
-        }
+    let mut listing = Listing::new(&["Name", "S3BucketName", "SnsTopicARN"]);
+    for channel in &channels {
+        listing.push(vec![
+            channel.name.as_deref().into(),
+            channel.s3_bucket_name.as_deref().into(),
+            channel.sns_topic_arn.as_deref().into(),
+        ]);
     }
 
-    println!();
+    shared.print(&listing, || {
+        if num_channels == 0 {
+            println!("You have no delivery channels")
+        } else {
+            for channel in &channels {
+                println!("  Channel: {}", channel.name.as_deref().unwrap_or_default());
+            }
+        }
+
+        println!();
+    });
 
     Ok(())
 }
@@ -49,26 +59,13 @@ async fn show_channels(client: &aws_sdk_config::Client) -> Result<(), aws_sdk_co
 /// * `[-v]` - Whether to display information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-    let Opt { region, verbose } = Opt::from_args();
-
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let Opt { common } = Opt::from_args();
 
-    if verbose {
-        println!("Config client version: {}", PKG_VERSION);
-        println!(
-            "Region:                {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
+    let shared = common.load().await;
 
-        println!();
-    }
+    shared.banner("Config", PKG_VERSION, &[]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.config();
 
-    show_channels(&client).await
+    show_channels(&shared, &client).await
 }
@@ -3,52 +3,79 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
+use alpha_common::output::Listing;
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::{CommonOpt, OutputFormat, Shared};
 use aws_sdk_config::model::ResourceType;
-use aws_sdk_config::{Client, Error, Region, PKG_VERSION};
+use aws_sdk_config::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 // Lists your resources.
 async fn show_resources(
-    verbose: bool,
+    shared: &Shared,
+    pages: &PageOpt,
     client: &aws_sdk_config::Client,
 ) -> Result<(), aws_sdk_config::Error> {
-    for value in ResourceType::values() {
-        let parsed = ResourceType::from(*value);
-
-        let resp = client
-            .list_discovered_resources()
-            .resource_type(parsed)
-            .send()
-            .await?;
-
-        let resources = resp.resource_identifiers.unwrap_or_default();
+    let mut by_type = Vec::new();
+    let mut listing = Listing::new(&["ResourceType", "ResourceId", "ResourceName"]);
 
-        if !resources.is_empty() || verbose {
-            println!();
-            println!("Resources of type {}:", value);
+    for value in ResourceType::values() {
+        let client = client.clone();
+        let resources = paginate(pages, move |token, limit| {
+            let request = client
+                .list_discovered_resources()
+                .resource_type(ResourceType::from(*value))
+                .set_next_token(token)
+                .set_limit(limit);
+            async move {
+                request.send().await.map(|resp| {
+                    Page::with_token(
+                        resp.resource_identifiers.unwrap_or_default(),
+                        resp.next_token,
+                    )
+                })
+            }
+        })
+        .try_collect()
+        .await?;
+
+        for resource in &resources {
+            listing.push(vec![
+                (*value).into(),
+                resource.resource_id.as_deref().into(),
+                resource.resource_name.as_deref().into(),
+            ]);
         }
 
-        for resource in resources {
-            println!(
-                "  Resource ID: {}",
-                resource.resource_id.as_deref().unwrap_or_default()
-            );
-        }
+        by_type.push((value, resources));
     }
 
-    println!();
+    shared.print(&listing, || {
+        for (value, resources) in by_type {
+            if !resources.is_empty() || shared.verbose() {
+                println!();
+                println!("Resources of type {}:", value);
+            }
+
+            for resource in resources {
+                println!(
+                    "  Resource ID: {}",
+                    resource.resource_id.as_deref().unwrap_or_default()
+                );
+            }
+        }
+
+        println!();
+    });
 
     Ok(())
 }
@@ -57,36 +84,25 @@ async fn show_resources(
 ///
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most resources to list of each type.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///   If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///   If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-    let Opt { region, verbose } = Opt::from_args();
-
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let Opt { pages, common } = Opt::from_args();
 
-    if verbose {
-        println!("Config client version: {}", PKG_VERSION);
-        println!(
-            "Region:                {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
+    let shared = common.load().await;
 
-        println!();
-    }
+    shared.banner("Config", PKG_VERSION, &[]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.config();
 
-    if !verbose {
+    if !shared.verbose() && shared.output() == OutputFormat::Text {
         println!("You won't see any output if you don't have any resources defined in the region.");
     }
 
-    show_resources(verbose, &client).await
+    show_resources(&shared, &pages, &client).await
 }
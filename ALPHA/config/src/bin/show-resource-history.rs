@@ -3,17 +3,14 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
+use alpha_common::output::{date_time, Listing};
+use alpha_common::{CommonOpt, Shared};
 use aws_sdk_config::model::ResourceType;
-use aws_sdk_config::{Client, Error, Region, PKG_VERSION};
+use aws_sdk_config::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The ID of the resource.
     #[structopt(short, long)]
     id: String,
@@ -22,13 +19,13 @@ struct Opt {
     #[structopt(long)]
     resource_type: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 // Shows the history for a resource.
 async fn show_history(
+    shared: &Shared,
     client: &aws_sdk_config::Client,
     id: &str,
     res: ResourceType,
@@ -39,11 +36,33 @@ async fn show_history(
         .resource_type(res)
         .send()
         .await?;
-    println!("configuration history for {}:", id);
-    for item in rsp.configuration_items.unwrap_or_default() {
-        println!("item: {:?}", item);
+    let items = rsp.configuration_items.unwrap_or_default();
+
+    let mut listing = Listing::new(&[
+        "ConfigurationItemCaptureTime",
+        "ConfigurationItemStatus",
+        "ConfigurationStateId",
+        "Configuration",
+    ]);
+    for item in &items {
+        listing.push(vec![
+            date_time(item.configuration_item_capture_time.as_ref()),
+            item.configuration_item_status
+                .as_ref()
+                .map(|s| s.as_str())
+                .into(),
+            item.configuration_state_id.as_deref().into(),
+            item.configuration.as_deref().into(),
+        ]);
     }
 
+    shared.print(&listing, || {
+        println!("configuration history for {}:", id);
+        for item in items {
+            println!("item: {:?}", item);
+        }
+    });
+
     Ok(())
 }
 
@@ -60,29 +79,19 @@ async fn show_history(
 /// * `[-v]` - Whether to display information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
     let Opt {
-        region,
         id,
         resource_type,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("Config client version: {}", PKG_VERSION);
-        println!(
-            "Region:                {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Resource ID:           {}", &id);
-        println!("Resource type:         {}", &resource_type);
-        println!();
-    }
+    shared.banner(
+        "Config",
+        PKG_VERSION,
+        &[("Resource ID", &id), ("Resource type", &resource_type)],
+    );
 
     // Parse resource type from user input.
     let parsed = ResourceType::from(resource_type.as_str());
@@ -96,8 +105,7 @@ async fn main() -> Result<(), Error> {
         )
     }
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.config();
 
-    show_history(&client, &id, parsed).await
+    show_history(&shared, &client, &id, parsed).await
 }
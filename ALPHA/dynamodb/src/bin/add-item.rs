@@ -3,12 +3,23 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_dynamodb::model::AttributeValue;
-use aws_sdk_dynamodb::{Client, Error, Region, PKG_VERSION};
+use alpha_common::CommonOpt;
+use aws_sdk_dynamodb::{Error, PKG_VERSION};
+use dynamodb_code_examples::item::to_item;
+use serde::Serialize;
 use std::process;
 use structopt::StructOpt;
 
+/// A user, as stored in the table.
+#[derive(Serialize)]
+struct User {
+    username: String,
+    account_type: String,
+    age: u32,
+    first_name: String,
+    last_name: String,
+}
+
 #[derive(Debug, StructOpt)]
 struct Opt {
     /// The permission type of the user, standard_user or admin.
@@ -17,7 +28,7 @@ struct Opt {
 
     /// The user's age.
     #[structopt(short, long)]
-    age: String,
+    age: u32,
 
     /// The user's username.
     #[structopt(short, long)]
@@ -35,13 +46,8 @@ struct Opt {
     #[structopt(short, long)]
     table: String,
 
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Adds an item to an Amazon DynamoDB table.
@@ -67,8 +73,7 @@ async fn main() -> Result<(), Error> {
         age,
         first,
         last,
-        region,
-        verbose,
+        common,
     } = Opt::from_args();
 
     if p_type != "standard_user" && p_type != "admin" {
@@ -78,44 +83,33 @@ async fn main() -> Result<(), Error> {
         process::exit(1);
     }
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
-
-    if verbose {
-        println!("DynamoDB client version: {}", PKG_VERSION);
-        println!(
-            "Region:                  {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Table:  {}", table);
-        println!("User:   {}", username);
-        println!("Type:   {}", p_type);
-        println!("Age:    {}", age);
-        println!("First:  {}", first);
-        println!("Last:   {}\n", last);
-
-        println!();
-    }
+    let shared = common.load().await;
+
+    shared.banner(
+        "DynamoDB",
+        PKG_VERSION,
+        &[
+            ("Table", &table),
+            ("User", &username),
+            ("Type", &p_type),
+            ("Age", &age),
+            ("First", &first),
+            ("Last", &last),
+        ],
+    );
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.dynamodb();
 
-    let user_av = AttributeValue::S(String::from(&username));
-    let type_av = AttributeValue::S(String::from(&p_type));
-    let age_av = AttributeValue::S(String::from(&age));
-    let first_av = AttributeValue::S(String::from(&first));
-    let last_av = AttributeValue::S(String::from(&last));
+    let user = User {
+        username,
+        account_type: p_type,
+        age,
+        first_name: first,
+        last_name: last,
+    };
+    let item = to_item(&user).expect("a user is always an item");
 
-    let request = client
-        .put_item()
-        .table_name(table)
-        .item("username", user_av)
-        .item("account_type", type_av)
-        .item("age", age_av)
-        .item("first_name", first_av)
-        .item("last_name", last_av);
+    let request = client.put_item().table_name(table).set_item(Some(item));
 
     println!("Executing request [{:?}] to add item...", request);
 
@@ -123,7 +117,7 @@ async fn main() -> Result<(), Error> {
 
     println!(
         "Added user {}, {} {}, age {} as {} user",
-        username, first, last, age, p_type
+        user.username, user.first_name, user.last_name, user.age, user.account_type
     );
 
     Ok(())
@@ -0,0 +1,286 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::waiter::{dynamodb, Cancel, Waiter};
+use alpha_common::{CommonOpt, Shared};
+use aws_sdk_dynamodb::model::{
+    CreateGlobalSecondaryIndexAction, DeleteGlobalSecondaryIndexAction, GlobalSecondaryIndexUpdate,
+    ProvisionedThroughput, TimeToLiveSpecification, UpdateGlobalSecondaryIndexAction,
+};
+use aws_sdk_dynamodb::{Client, Error, SdkError, PKG_VERSION};
+use dynamodb_code_examples::schema::{plan, stream_specification, Billing, Change, TableSpec};
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The YAML or JSON file describing the table.
+    #[structopt(short, long)]
+    file: PathBuf,
+
+    /// Only show the changes that would be made.
+    #[structopt(long)]
+    dry_run: bool,
+
+    /// The most seconds to wait for each change. Defaults to 600.
+    #[structopt(long)]
+    max_wait: Option<u64>,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+// Waits for the last change to finish, as UpdateTable fails while the table
+// or any of its indexes is still being changed.
+async fn wait_for_table(shared: &Shared, waiter: &Waiter, client: &Client, table: &str) {
+    if let Err(e) = dynamodb::table_active(waiter, client, table).await {
+        println!("Got an error waiting for the table:");
+        println!("{}", e);
+        shared.exit(1);
+    }
+}
+
+async fn apply(client: &Client, spec: &TableSpec, change: &Change) -> Result<(), Error> {
+    let update = client.update_table().table_name(&spec.name);
+    match change {
+        Change::CreateTable => {
+            let (billing_mode, throughput) = spec.billing_mode();
+            let indexes: Vec<_> = spec
+                .global_secondary_indexes
+                .iter()
+                .map(|index| spec.global_index(index))
+                .collect();
+            client
+                .create_table()
+                .table_name(&spec.name)
+                .set_key_schema(Some(spec.key_schema()))
+                .set_attribute_definitions(Some(spec.attribute_definitions()))
+                .billing_mode(billing_mode)
+                .set_provisioned_throughput(throughput)
+                .set_global_secondary_indexes(Some(indexes).filter(|i| !i.is_empty()))
+                .set_local_secondary_indexes(spec.local_indexes())
+                .set_stream_specification(spec.stream.map(|view| stream_specification(Some(view))))
+                .send()
+                .await?;
+        }
+        Change::SetBilling(billing) => {
+            let (billing_mode, throughput) = spec.billing_mode();
+            // Every index of a provisioned table needs its own throughput.
+            let indexes = match billing {
+                Billing::PayPerRequest => None,
+                Billing::Provisioned(_) => {
+                    let described = client
+                        .describe_table()
+                        .table_name(&spec.name)
+                        .send()
+                        .await?;
+                    let existing = described
+                        .table
+                        .and_then(|t| t.global_secondary_indexes)
+                        .unwrap_or_default();
+                    let updates: Vec<_> = spec
+                        .global_secondary_indexes
+                        .iter()
+                        .filter(|index| {
+                            existing
+                                .iter()
+                                .any(|e| e.index_name.as_deref() == Some(&index.name))
+                        })
+                        .map(|index| {
+                            let action = UpdateGlobalSecondaryIndexAction::builder()
+                                .index_name(&index.name)
+                                .set_provisioned_throughput(
+                                    spec.global_index(index).provisioned_throughput,
+                                )
+                                .build();
+                            GlobalSecondaryIndexUpdate::builder().update(action).build()
+                        })
+                        .collect();
+                    Some(updates).filter(|u| !u.is_empty())
+                }
+            };
+            update
+                .billing_mode(billing_mode)
+                .set_provisioned_throughput(throughput)
+                .set_global_secondary_index_updates(indexes)
+                .send()
+                .await?;
+        }
+        Change::DeleteIndex(name) => {
+            let action = DeleteGlobalSecondaryIndexAction::builder()
+                .index_name(name)
+                .build();
+            update
+                .global_secondary_index_updates(
+                    GlobalSecondaryIndexUpdate::builder().delete(action).build(),
+                )
+                .send()
+                .await?;
+        }
+        Change::CreateIndex(index) => {
+            let gsi = spec.global_index(index);
+            let action = CreateGlobalSecondaryIndexAction::builder()
+                .set_index_name(gsi.index_name)
+                .set_key_schema(gsi.key_schema)
+                .set_projection(gsi.projection)
+                .set_provisioned_throughput(gsi.provisioned_throughput)
+                .build();
+            update
+                .set_attribute_definitions(Some(spec.index_attribute_definitions(index)))
+                .global_secondary_index_updates(
+                    GlobalSecondaryIndexUpdate::builder().create(action).build(),
+                )
+                .send()
+                .await?;
+        }
+        Change::SetIndexThroughput(name, throughput) => {
+            let throughput = ProvisionedThroughput::builder()
+                .read_capacity_units(throughput.read)
+                .write_capacity_units(throughput.write)
+                .build();
+            let action = UpdateGlobalSecondaryIndexAction::builder()
+                .index_name(name)
+                .provisioned_throughput(throughput)
+                .build();
+            update
+                .global_secondary_index_updates(
+                    GlobalSecondaryIndexUpdate::builder().update(action).build(),
+                )
+                .send()
+                .await?;
+        }
+        Change::SetStream(view) => {
+            update
+                .stream_specification(stream_specification(*view))
+                .send()
+                .await?;
+        }
+        Change::SetTtl(attribute) => {
+            let enabled = attribute.is_some();
+            // Turning TTL off still names the attribute it was on.
+            let attribute = match attribute {
+                Some(attribute) => Some(attribute.clone()),
+                None => client
+                    .describe_time_to_live()
+                    .table_name(&spec.name)
+                    .send()
+                    .await?
+                    .time_to_live_description
+                    .and_then(|ttl| ttl.attribute_name),
+            };
+            let ttl = TimeToLiveSpecification::builder()
+                .enabled(enabled)
+                .set_attribute_name(attribute)
+                .build();
+            client
+                .update_time_to_live()
+                .table_name(&spec.name)
+                .time_to_live_specification(ttl)
+                .send()
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Creates or updates a DynamoDB table to match a YAML or JSON spec,
+/// covering its keys, billing mode, global and local secondary indexes,
+/// time to live, and stream.
+/// Shows the changes to be made, then makes them one at a time,
+/// waiting for each to finish.
+/// See `dynamodb_code_examples::schema` for the spec format.
+/// # Arguments
+///
+/// * `-f FILE` - The file describing the table.
+/// * `[--dry-run]` - Only show the changes that would be made.
+/// * `[--max-wait SECONDS]` - The most seconds to wait for each change. Defaults to 600.
+/// * `[-r REGION]` - The region in which the client is created.
+///    If not supplied, uses the value of the **AWS_REGION** environment variable.
+///    If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt {
+        file,
+        dry_run,
+        max_wait,
+        common,
+    } = Opt::from_args();
+
+    let spec = match std::fs::read_to_string(&file)
+        .map_err(|e| e.to_string())
+        .and_then(|yaml| TableSpec::from_yaml(&yaml).map_err(|e| e.to_string()))
+    {
+        Ok(spec) => spec,
+        Err(e) => {
+            println!("Got an error reading {}:", file.display());
+            println!("{}", e);
+            process::exit(1);
+        }
+    };
+
+    let shared = common.load().await;
+
+    shared.banner("DynamoDB", PKG_VERSION, &[("Table", &spec.name)]);
+
+    let client = shared.dynamodb();
+
+    let table = match client.describe_table().table_name(&spec.name).send().await {
+        Ok(resp) => resp.table,
+        Err(SdkError::ServiceError { err, .. }) if err.is_resource_not_found_exception() => None,
+        Err(e) => return Err(e.into()),
+    };
+    let ttl = match table {
+        Some(_) => {
+            client
+                .describe_time_to_live()
+                .table_name(&spec.name)
+                .send()
+                .await?
+                .time_to_live_description
+        }
+        None => None,
+    };
+
+    let changes = match plan(&spec, table.as_ref(), ttl.as_ref()) {
+        Ok(changes) => changes,
+        Err(e) => {
+            println!("Got an error planning the changes:");
+            println!("{}", e);
+            shared.exit(1);
+        }
+    };
+
+    if changes.is_empty() {
+        println!("Table {} matches {}", spec.name, file.display());
+        return Ok(());
+    }
+    println!("Changes to table {}:", spec.name);
+    for change in &changes {
+        println!("  {}", change);
+    }
+    if dry_run {
+        return Ok(());
+    }
+
+    let waiter = Waiter::new().cancel_with(Cancel::on_ctrl_c());
+    let waiter = match max_wait {
+        Some(seconds) => waiter.max_wait(Duration::from_secs(seconds)),
+        None => waiter,
+    };
+    for change in &changes {
+        if *change != Change::CreateTable {
+            wait_for_table(&shared, &waiter, &client, &spec.name).await;
+        }
+        println!("Applying {}", change);
+        apply(&client, &spec, change).await?;
+    }
+    wait_for_table(&shared, &waiter, &client, &spec.name).await;
+    println!("Table {} matches {}", spec.name, file.display());
+
+    Ok(())
+}
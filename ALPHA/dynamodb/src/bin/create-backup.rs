@@ -0,0 +1,87 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::waiter::{dynamodb, Cancel, Waiter};
+use alpha_common::CommonOpt;
+use aws_sdk_dynamodb::{Error, PKG_VERSION};
+use std::time::Duration;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The name of the table.
+    #[structopt(short, long)]
+    table: String,
+
+    /// The name of the backup.
+    #[structopt(short, long)]
+    backup: String,
+
+    /// The most seconds to wait for the backup. Defaults to 600.
+    #[structopt(long)]
+    max_wait: Option<u64>,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+/// Creates an on-demand backup of a DynamoDB table, and waits until it is available.
+/// The backup is kept until it is deleted with `delete-backup`.
+/// # Arguments
+///
+/// * `-t TABLE` - The name of the table.
+/// * `-b BACKUP` - The name of the backup.
+/// * `[--max-wait SECONDS]` - The most seconds to wait. Defaults to 600.
+/// * `[-r REGION]` - The region in which the client is created.
+///    If not supplied, uses the value of the **AWS_REGION** environment variable.
+///    If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt {
+        table,
+        backup,
+        max_wait,
+        common,
+    } = Opt::from_args();
+
+    let shared = common.load().await;
+
+    shared.banner(
+        "DynamoDB",
+        PKG_VERSION,
+        &[("Table", &table), ("Backup", &backup)],
+    );
+
+    let client = shared.dynamodb();
+
+    let arn = client
+        .create_backup()
+        .table_name(&table)
+        .backup_name(&backup)
+        .send()
+        .await?
+        .backup_details
+        .and_then(|d| d.backup_arn)
+        .unwrap_or_default();
+    println!("Creating backup {}", arn);
+
+    let waiter = Waiter::new()
+        .cancel_with(Cancel::on_ctrl_c())
+        .on_retry(|waited| println!("  still creating after {}s", waited.as_secs()));
+    let waiter = match max_wait {
+        Some(seconds) => waiter.max_wait(Duration::from_secs(seconds)),
+        None => waiter,
+    };
+    if let Err(e) = dynamodb::backup_available(&waiter, &client, &arn).await {
+        println!("Got an error waiting for the backup:");
+        println!("{}", e);
+        shared.exit(1);
+    }
+
+    println!("Backup {} of table {} is available", backup, table);
+
+    Ok(())
+}
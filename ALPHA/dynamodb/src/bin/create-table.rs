@@ -3,20 +3,16 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
+use alpha_common::waiter::{dynamodb, WaitOpt};
+use alpha_common::CommonOpt;
 use aws_sdk_dynamodb::model::{
     AttributeDefinition, KeySchemaElement, KeyType, ProvisionedThroughput, ScalarAttributeType,
 };
-use aws_sdk_dynamodb::{Client, Error, Region, PKG_VERSION};
-use std::process;
+use aws_sdk_dynamodb::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The region
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The table name
     #[structopt(short, long)]
     table: String,
@@ -25,9 +21,11 @@ struct Opt {
     #[structopt(short, long)]
     key: String,
 
-    /// Activate verbose mode
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    wait: WaitOpt,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Creates a DynamoDB table.
@@ -35,39 +33,26 @@ struct Opt {
 ///
 /// * `-k KEY` - The primary key for the table.
 /// * `-t TABLE` - The name of the table.
+/// * `[--wait]` - Whether to wait until the table is active.
+/// * `[--max-wait SECONDS]` - The most seconds to wait. Defaults to 600.
 /// * `[-d DEFAULT-REGION]` - The region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_DEFAULT_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
     let Opt {
         table,
         key,
-        region,
-        verbose,
+        wait,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
-
-    if verbose {
-        println!("DynamoDB client version: {}", PKG_VERSION);
-        println!(
-            "Region:                  {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Table:                   {}", table);
-        println!("Key:                     {}", key);
+    let shared = common.load().await;
 
-        println!();
-    }
+    shared.banner("DynamoDB", PKG_VERSION, &[("Table", &table), ("Key", &key)]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.dynamodb();
 
     let ad = AttributeDefinition::builder()
         .attribute_name(String::from(&key))
@@ -97,9 +82,19 @@ async fn main() -> Result<(), Error> {
         Err(e) => {
             println!("Got an error creating table:");
             println!("{}", e);
-            process::exit(1);
+            shared.exit(1);
         }
     };
 
+    if let Some(waiter) = wait.waiter() {
+        println!("Waiting for the table to be active.");
+        if let Err(e) = dynamodb::table_active(&waiter, &client, &table).await {
+            println!("Got an error waiting for the table:");
+            println!("{}", e);
+            shared.exit(1);
+        }
+        println!("Table {} is active", table);
+    }
+
     Ok(())
 }
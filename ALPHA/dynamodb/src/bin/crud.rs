@@ -3,26 +3,21 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_http::AwsErrorRetryPolicy;
-use aws_hyper::{SdkError, SdkSuccess};
-use aws_sdk_dynamodb::error::DescribeTableError;
-use aws_sdk_dynamodb::input::DescribeTableInput;
+use alpha_common::waiter::{dynamodb, Waiter};
+use alpha_common::{CommonOpt, Shared};
 use aws_sdk_dynamodb::model::{
     AttributeDefinition, AttributeValue, KeySchemaElement, KeyType, ProvisionedThroughput,
-    ScalarAttributeType, Select, TableStatus,
+    ScalarAttributeType, Select,
 };
-use aws_sdk_dynamodb::operation::DescribeTable;
-use aws_sdk_dynamodb::output::DescribeTableOutput;
-use aws_sdk_dynamodb::{Client, Config, Error, Region, PKG_VERSION};
+use aws_sdk_dynamodb::{Client, Error, SdkError, PKG_VERSION};
+use dynamodb_code_examples::expression::{self, attr, Expressions, Update};
+use dynamodb_code_examples::item::{from_item, to_item};
+use dynamodb_code_examples::transaction::{versioned_delete, versioned_put, versioned_update};
 use rand::distributions::Alphanumeric;
 use rand::{thread_rng, Rng};
-use smithy_http::operation::Operation;
-use smithy_http::retry::ClassifyResponse;
-use smithy_types::retry::RetryKind;
+use serde::{Deserialize, Serialize};
 use std::io::{stdin, Read};
-use std::time::Duration;
-use std::{iter, process};
+use std::iter;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -31,13 +26,20 @@ struct Opt {
     #[structopt(short, long)]
     interactive: bool,
 
-    /// The AWS Region
+    /// The name of the table to create (a random 10-character name by default).
     #[structopt(short, long)]
-    region: Option<String>,
+    table: Option<String>,
 
-    /// Activate verbose mode    
+    /// The name of the primary key (a random 6-character name by default).
     #[structopt(short, long)]
-    verbose: bool,
+    key: Option<String>,
+
+    /// The value of the primary key (a random 12-character value by default).
+    #[structopt(long)]
+    key_value: Option<String>,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Create a random, n-length string
@@ -55,7 +57,7 @@ async fn make_table(
     client: &Client,
     table: &str,
     key: &str,
-) -> Result<(), aws_hyper::SdkError<aws_sdk_dynamodb::error::CreateTableError>> {
+) -> Result<(), SdkError<aws_sdk_dynamodb::error::CreateTableError>> {
     let ad = AttributeDefinition::builder()
         .attribute_name(key)
         .attribute_type(ScalarAttributeType::S)
@@ -85,118 +87,106 @@ async fn make_table(
     }
 }
 
-/// For add_item and query_item
-#[derive(Clone)]
-struct Item {
-    table: String,
-    key: String,
-    value: String,
+/// The attributes of a user, other than its primary key.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct User {
     first_name: String,
     last_name: String,
-    age: String,
-    utype: String,
+    age: u32,
+    account_type: String,
 }
 
-/// Add an item to the table.
+/// Add a user to the table, under the primary key `key` with the value `value`,
+/// unless there is already an item with that key. The new item is at version 1.
 async fn add_item(
     client: &Client,
-    item: Item,
+    table: &str,
+    key: &str,
+    value: &str,
+    user: &User,
 ) -> Result<(), SdkError<aws_sdk_dynamodb::error::PutItemError>> {
-    let user_av = AttributeValue::S(item.value);
-    let type_av = AttributeValue::S(item.utype);
-    let age_av = AttributeValue::S(item.age);
-    let first_av = AttributeValue::S(item.first_name);
-    let last_av = AttributeValue::S(item.last_name);
+    let mut item = to_item(user).expect("a user is always an item");
+    item.insert(key.to_string(), AttributeValue::S(value.to_string()));
 
-    match client
-        .put_item()
-        .table_name(item.table)
-        .item(item.key, user_av)
-        .item("account_type", type_av)
-        .item("age", age_av)
-        .item("first_name", first_av)
-        .item("last_name", last_av)
+    versioned_put(&mut item, None)
+        .put_item(client.put_item().table_name(table))
+        .set_item(Some(item))
         .send()
-        .await
-    {
-        Ok(_) => Ok(()),
-        Err(e) => Err(e),
-    }
-}
+        .await?;
 
-/// Query the table for an item matching the input values.
-/// Returns true if the item is found; otherwise false.
-async fn query_item(client: &Client, item: Item) -> bool {
-    let value = &item.value;
-    let key = &item.key;
-    let user_av = AttributeValue::S(value.to_string());
+    Ok(())
+}
 
-    match client
-        .query()
-        .table_name(item.table)
-        .key_condition_expression("#key = :value".to_string())
-        .expression_attribute_names("#key".to_string(), key.to_string())
-        .expression_attribute_values(":value".to_string(), user_av)
-        .select(Select::AllAttributes)
-        .send()
-        .await
-    {
-        Ok(resp) => {
-            if resp.count > 0 {
+/// Query the table for the user with the primary key value, and compare it with `expected`.
+/// Returns true if the user is found and matches; otherwise false.
+async fn query_item(
+    shared: &Shared,
+    client: &Client,
+    table: &str,
+    key: &str,
+    value: &str,
+    expected: &User,
+) -> bool {
+    let query = Expressions::new()
+        .key_condition(expression::key(key).eq(expression::value(value)))
+        .query(client.query().table_name(table));
+
+    match query.select(Select::AllAttributes).send().await {
+        Ok(resp) => match resp.items.unwrap_or_default().pop() {
+            Some(item) => {
+                let user: User = match from_item(item) {
+                    Ok(user) => user,
+                    Err(e) => {
+                        println!("Got an error reading the item:");
+                        println!("{}", e);
+                        shared.exit(1);
+                    }
+                };
                 println!("Found a matching entry in the table:");
-                println!("{:?}", resp.items.unwrap_or_default().pop());
-                true
-            } else {
+                println!("{:?}", user);
+                if &user == expected {
+                    println!("It matches the original value.");
+                    true
+                } else {
+                    println!("It does not match the original value.");
+                    false
+                }
+            }
+            None => {
                 println!("Did not find a match.");
                 false
             }
-        }
+        },
         Err(e) => {
             println!("Got an error querying table:");
             println!("{}", e);
-            process::exit(1);
+            shared.exit(1);
         }
     }
 }
 
-/// Hand-written waiter to retry every second until the table is out of `Creating` state
-#[derive(Clone)]
-struct WaitForReadyTable<R> {
-    inner: R,
-}
+/// Change the age of the user with the primary key value, if nobody has
+/// written the user since it was at version `version`.
+async fn update_age(
+    client: &Client,
+    table: &str,
+    key: &str,
+    value: &str,
+    age: u32,
+    version: u64,
+) -> Result<(), SdkError<aws_sdk_dynamodb::error::UpdateItemError>> {
+    let update = versioned_update(
+        Update::new().set(attr("age"), expression::value(&age)),
+        Some(version),
+    )
+    .update_item(client.update_item().table_name(table));
+
+    update
+        .key(key, AttributeValue::S(value.to_string()))
+        .send()
+        .await?;
 
-impl<R> ClassifyResponse<SdkSuccess<DescribeTableOutput>, SdkError<DescribeTableError>>
-    for WaitForReadyTable<R>
-where
-    R: ClassifyResponse<SdkSuccess<DescribeTableOutput>, SdkError<DescribeTableError>>,
-{
-    fn classify(
-        &self,
-        response: Result<&SdkSuccess<DescribeTableOutput>, &SdkError<DescribeTableError>>,
-    ) -> RetryKind {
-        match self.inner.classify(response) {
-            RetryKind::NotRetryable => (),
-            other => return other,
-        };
-        match response {
-            Ok(SdkSuccess { parsed, .. }) => {
-                if parsed
-                    .table
-                    .as_ref()
-                    .unwrap()
-                    .table_status
-                    .as_ref()
-                    .unwrap()
-                    == &TableStatus::Creating
-                {
-                    RetryKind::Explicit(Duration::from_secs(1))
-                } else {
-                    RetryKind::NotRetryable
-                }
-            }
-            _ => RetryKind::NotRetryable,
-        }
-    }
+    Ok(())
 }
 
 /// Wait for the user to press Enter.
@@ -207,65 +197,64 @@ fn pause() {
 
 /// Performs CRUD (create, read, update, delete) operations on a DynamoDB table and table item.
 /// It creates a table, adds an item to the table, updates the item, deletes the item, and deletes the table.
-/// The table name, primary key, and primary key value are all created as random strings.
+/// Each write is conditional on the item's version, so it fails rather than
+/// overwrite a change that someone else made in between.
+/// The table name, primary key, and primary key value are random strings unless given.
 ///
 /// # Arguments
 ///
 /// * `[-i]` - Whether to pause between operations.
+/// * `[-t TABLE]` - The name of the table.
+/// * `[-k KEY]` - The name of the primary key.
+/// * `[--key-value VALUE]` - The value of the primary key.
 /// * `[-r REGION]` - The region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
     let Opt {
         interactive,
-        region,
-        verbose,
+        table,
+        key,
+        key_value,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
     // Create 10-character random table name
-    let table = random_string(10);
+    let table = table.unwrap_or_else(|| random_string(10));
 
     // Create a 6-character random key name
-    let key = random_string(6);
+    let key = key.unwrap_or_else(|| random_string(6));
 
     // Create a 12-character random key value
-    let value = random_string(12);
+    let value = key_value.unwrap_or_else(|| random_string(12));
 
     // Specify first name, last name, age, and type
-    let first_name = "DummyFirstName";
-    let last_name = "DummyLastName";
-    let age = "33";
-    let utype = "standard_user";
-
-    println!();
-
-    if verbose {
-        println!("DynamoDB client version: {}", PKG_VERSION);
-        println!(
-            "Region:                  {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Table:                   {}", table);
-        println!("Key:                     {}", key);
-        println!("Value:                   {}", value);
-        println!("First name:              {}", first_name);
-        println!("Last name:               {}", last_name);
-        println!("Age:                     {}", age);
-        println!("User type:               {}", utype);
-
-        println!();
-    }
+    let mut user = User {
+        first_name: "DummyFirstName".to_string(),
+        last_name: "DummyLastName".to_string(),
+        age: 33,
+        account_type: "standard_user".to_string(),
+    };
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    shared.banner(
+        "DynamoDB",
+        PKG_VERSION,
+        &[
+            ("Table", &table),
+            ("Key", &key),
+            ("Value", &value),
+            ("First name", &user.first_name),
+            ("Last name", &user.last_name),
+            ("Age", &user.age),
+            ("User type", &user.account_type),
+        ],
+    );
+
+    let client = shared.dynamodb();
 
     /* Create table */
     println!("Creating the table.");
@@ -273,7 +262,7 @@ async fn main() -> Result<(), Error> {
         Err(e) => {
             println!("Got an error creating the table:");
             println!("{}", e);
-            process::exit(1);
+            shared.exit(1);
         }
         Ok(_) => {
             println!("Created the table.");
@@ -282,12 +271,13 @@ async fn main() -> Result<(), Error> {
 
     println!("Waiting for table to be ready.");
 
-    let raw_client = aws_hyper::Client::https();
+    let waiter = Waiter::new();
 
-    raw_client
-        .call(wait_for_ready_table(&table, client.conf()))
-        .await
-        .expect("table should become ready.");
+    if let Err(e) = dynamodb::table_active(&waiter, &client, &table).await {
+        println!("Got an error waiting for the table:");
+        println!("{}", e);
+        shared.exit(1);
+    }
 
     println!("Table is now ready to use.");
 
@@ -297,29 +287,20 @@ async fn main() -> Result<(), Error> {
 
     println!("Adding item to table.");
 
-    let mut item = Item {
-        table: table.clone(),
-        key: key.clone(),
-        value: value.clone(),
-        first_name: first_name.to_string(),
-        last_name: last_name.to_string(),
-        age: age.to_string(),
-        utype: utype.to_string(),
-    };
-
-    add_item(&client, item.clone()).await?;
+    add_item(&client, &table, &key, &value, &user).await?;
     println!("Added item to table.");
 
     if interactive {
         pause();
     }
 
-    item.age = "44".to_string();
+    user.age = 44;
 
     /* Update the item */
     println!("Modifying table item to change age to 44.");
 
-    add_item(&client, item.clone()).await?;
+    // The item was added at version 1.
+    update_age(&client, &table, &key, &value, user.age, 1).await?;
 
     println!("Modified table item.");
 
@@ -330,7 +311,7 @@ async fn main() -> Result<(), Error> {
     /* Get item and compare it with the one we added */
     println!("Comparing table item to original value.");
 
-    query_item(&client, item).await;
+    query_item(&shared, &client, &table, &key, &value, &user).await;
 
     if interactive {
         pause();
@@ -338,10 +319,10 @@ async fn main() -> Result<(), Error> {
 
     /* Delete item */
     println!("Deleting item.");
+    // The update moved the item to version 2.
     let user_av = AttributeValue::S(value);
-    client
-        .delete_item()
-        .table_name(&table)
+    versioned_delete(Some(2))
+        .delete_item(client.delete_item().table_name(&table))
         .key(key, user_av)
         .send()
         .await?;
@@ -355,26 +336,15 @@ async fn main() -> Result<(), Error> {
     /* Delete table */
     println!("Deleting table.");
     client.delete_table().table_name(&table).send().await?;
+
+    if let Err(e) = dynamodb::table_deleted(&waiter, &client, &table).await {
+        println!("Got an error waiting for the table to be deleted:");
+        println!("{}", e);
+        shared.exit(1);
+    }
+
     println!("Deleted table.");
     println!();
 
     Ok(())
 }
-
-/// Construct a `DescribeTable` request with a policy to retry every second until the table
-/// is ready
-fn wait_for_ready_table(
-    table_name: &str,
-    conf: &Config,
-) -> Operation<DescribeTable, WaitForReadyTable<AwsErrorRetryPolicy>> {
-    let operation = DescribeTableInput::builder()
-        .table_name(table_name)
-        .build()
-        .expect("valid input")
-        .make_operation(conf)
-        .expect("valid operation");
-    let waiting_policy = WaitForReadyTable {
-        inner: operation.retry_policy().clone(),
-    };
-    operation.with_retry_policy(waiting_policy)
-}
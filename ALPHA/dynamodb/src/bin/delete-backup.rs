@@ -0,0 +1,51 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::CommonOpt;
+use aws_sdk_dynamodb::{Error, PKG_VERSION};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The ARN of the backup, as shown by `list-backups`.
+    #[structopt(short, long)]
+    arn: String,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+/// Deletes an on-demand DynamoDB backup.
+/// # Arguments
+///
+/// * `-a ARN` - The ARN of the backup.
+/// * `[-r REGION]` - The region in which the client is created.
+///    If not supplied, uses the value of the **AWS_REGION** environment variable.
+///    If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt { arn, common } = Opt::from_args();
+
+    let shared = common.load().await;
+
+    shared.banner("DynamoDB", PKG_VERSION, &[("Backup", &arn)]);
+
+    let client = shared.dynamodb();
+
+    let name = client
+        .delete_backup()
+        .backup_arn(&arn)
+        .send()
+        .await?
+        .backup_description
+        .and_then(|d| d.backup_details)
+        .and_then(|d| d.backup_name)
+        .unwrap_or_default();
+
+    println!("Deleted backup {}", name);
+
+    Ok(())
+}
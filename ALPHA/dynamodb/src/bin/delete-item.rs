@@ -3,18 +3,14 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
+use alpha_common::CommonOpt;
 use aws_sdk_dynamodb::model::AttributeValue;
-use aws_sdk_dynamodb::{Client, Error, Region, PKG_VERSION};
+use aws_sdk_dynamodb::{Error, PKG_VERSION};
 use std::process;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The name of the table.
     #[structopt(short, long)]
     table: String,
@@ -27,9 +23,8 @@ struct Opt {
     #[structopt(short, long)]
     value: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    info: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Deletes an item from an Amazon DynamoDB table.
@@ -45,34 +40,18 @@ struct Opt {
 /// * `[-i]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
-        info,
         key,
-        region,
         table,
         value,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
-    if info {
-        println!("DynamoDB client version: {}", PKG_VERSION);
-        println!(
-            "Region:                  {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Table:                   {}", &table);
-        println!("Key:                     {}", &key);
-        println!();
-    }
+    shared.banner("DynamoDB", PKG_VERSION, &[("Table", &table), ("Key", &key)]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.dynamodb();
 
     match client
         .delete_item()
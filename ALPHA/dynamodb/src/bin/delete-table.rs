@@ -3,29 +3,30 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_dynamodb::{Client, Error, Region, PKG_VERSION};
+use alpha_common::waiter::{dynamodb, WaitOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_dynamodb::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The name of the table.
     #[structopt(short, long)]
     table: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    wait: WaitOpt,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Deletes a DynamoDB table.
 /// # Arguments
 ///
 /// * `-t TABLE` - The name of the table.
+/// * `[--wait]` - Whether to wait until the table no longer exists.
+/// * `[--max-wait SECONDS]` - The most seconds to wait. Defaults to 600.
 /// * `[-r REGION]` - The region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
@@ -34,29 +35,26 @@ struct Opt {
 async fn main() -> Result<(), Error> {
     let Opt {
         table,
-        region,
-        verbose,
+        wait,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("DynamoDB client version: {}", PKG_VERSION);
-        println!(
-            "Region:                  {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Table:                   {}", &table);
-        println!();
-    }
+    shared.banner("DynamoDB", PKG_VERSION, &[("Table", &table)]);
+
+    let client = shared.dynamodb();
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    client.delete_table().table_name(&table).send().await?;
 
-    client.delete_table().table_name(table).send().await?;
+    if let Some(waiter) = wait.waiter() {
+        println!("Waiting for the table to be deleted.");
+        if let Err(e) = dynamodb::table_deleted(&waiter, &client, &table).await {
+            println!("Got an error waiting for the table:");
+            println!("{}", e);
+            shared.exit(1);
+        }
+    }
 
     println!("Deleted table");
 
@@ -0,0 +1,255 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::pagination::PageOpt;
+use alpha_common::CommonOpt;
+use aws_sdk_dynamodb::model::{PutRequest, WriteRequest};
+use aws_sdk_dynamodb::{Client, Error, PKG_VERSION};
+use dynamodb_code_examples::json::{
+    item_to_typed, item_to_value, parse_item, typed_to_item, InvalidItem, Item,
+};
+use dynamodb_code_examples::scan::{parallel_scan, ScanOpt};
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use rand::Rng;
+use serde_json::{json, Value};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::time::Duration;
+use structopt::StructOpt;
+
+/// The most put requests DynamoDB accepts in one BatchWriteItem call.
+const BATCH_SIZE: usize = 25;
+
+/// How many times to resend the items DynamoDB leaves unprocessed.
+const MAX_RETRIES: u32 = 8;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    #[structopt(subcommand)]
+    command: Command,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Puts the items in a JSON or NDJSON file into a table.
+    Import {
+        /// The name of the table.
+        #[structopt(short, long)]
+        table: String,
+
+        /// The file to read: a JSON array of items, or one item per line.
+        #[structopt(short, long)]
+        file: String,
+
+        /// The number of BatchWriteItem requests to send at once.
+        #[structopt(long, default_value = "4")]
+        concurrency: usize,
+    },
+
+    /// Writes every item in a table to an NDJSON file.
+    Export {
+        /// The name of the table.
+        #[structopt(short, long)]
+        table: String,
+
+        /// The file to write.
+        #[structopt(short, long)]
+        file: String,
+
+        /// The number of segments to scan in parallel.
+        #[structopt(long, default_value = "4")]
+        segments: i32,
+
+        /// Whether to write plain JSON instead of DynamoDB JSON.
+        /// Plain JSON does not record sets, binary values, or which strings are numbers.
+        #[structopt(long)]
+        plain: bool,
+    },
+}
+
+// Reads a JSON array of items, or a series of JSON items such as NDJSON.
+// An item of the form {"Item": {...}}, as export writes, is read as DynamoDB JSON;
+// any other object is read as plain JSON.
+fn read_items(file: &str) -> Result<Vec<Item>, Box<dyn std::error::Error>> {
+    let contents = fs::read_to_string(file)?;
+
+    let values: Vec<Value> = if contents.trim_start().starts_with('[') {
+        serde_json::from_str(&contents)?
+    } else {
+        serde_json::Deserializer::from_str(&contents)
+            .into_iter()
+            .collect::<Result<_, _>>()?
+    };
+
+    Ok(values
+        .into_iter()
+        .map(to_item)
+        .collect::<Result<_, InvalidItem>>()?)
+}
+
+fn to_item(value: Value) -> Result<Item, InvalidItem> {
+    match value {
+        Value::Object(mut o) if o.len() == 1 && o.contains_key("Item") => {
+            typed_to_item(o.remove("Item").unwrap())
+        }
+        plain => parse_item(plain),
+    }
+}
+
+// Writes a batch of items, resending the ones DynamoDB leaves unprocessed
+// with exponential backoff and jitter.
+// Returns the number of items that still could not be written.
+async fn write_batch(client: &Client, table: &str, items: Vec<Item>) -> Result<usize, Error> {
+    let mut requests: Vec<WriteRequest> = items
+        .into_iter()
+        .map(|item| {
+            WriteRequest::builder()
+                .put_request(PutRequest::builder().set_item(Some(item)).build())
+                .build()
+        })
+        .collect();
+
+    for attempt in 0..=MAX_RETRIES {
+        if attempt > 0 {
+            let ceiling = 50 << attempt;
+            let delay = rand::thread_rng().gen_range(ceiling / 2..=ceiling);
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+        }
+
+        let resp = client
+            .batch_write_item()
+            .request_items(table, requests)
+            .send()
+            .await?;
+
+        requests = resp
+            .unprocessed_items
+            .and_then(|mut unprocessed| unprocessed.remove(table))
+            .unwrap_or_default();
+        if requests.is_empty() {
+            break;
+        }
+    }
+
+    Ok(requests.len())
+}
+
+async fn import(
+    client: &Client,
+    table: &str,
+    file: &str,
+    concurrency: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let items = read_items(file)?;
+    let count = items.len();
+
+    let unwritten: Vec<usize> = stream::iter(items.chunks(BATCH_SIZE).map(<[Item]>::to_vec))
+        .map(|batch| write_batch(client, table, batch))
+        .buffer_unordered(concurrency.max(1))
+        .try_collect()
+        .await?;
+    let unwritten: usize = unwritten.into_iter().sum();
+
+    println!("Imported {} items into {}", count - unwritten, table);
+    if unwritten > 0 {
+        return Err(format!("{} items were still unprocessed after retrying", unwritten).into());
+    }
+
+    Ok(())
+}
+
+async fn export(
+    client: &Client,
+    table: &str,
+    file: &str,
+    segments: i32,
+    plain: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let scan = ScanOpt {
+        segments,
+        ..ScanOpt::default()
+    };
+    let mut items = parallel_scan(client, table, &scan, &PageOpt::default());
+
+    let mut out = BufWriter::new(File::create(file)?);
+    let mut count = 0;
+    while let Some(item) = items.try_next().await? {
+        let line = if plain {
+            item_to_value(&item)
+        } else {
+            json!({ "Item": item_to_typed(&item) })
+        };
+        writeln!(out, "{}", line)?;
+        count += 1;
+    }
+    out.flush()?;
+
+    println!("Exported {} items from {} to {}", count, table, file);
+
+    Ok(())
+}
+
+/// Imports items from a JSON file into a DynamoDB table, or exports a table to NDJSON.
+/// # Arguments
+///
+/// * `import -t TABLE -f FILE` - Puts the items in FILE into the table.
+///   FILE holds a JSON array of items, or one item per line (NDJSON).
+///   Items are plain JSON, or DynamoDB JSON wrapped as `{"Item": {...}}`.
+///   * `[--concurrency N]` - The number of BatchWriteItem requests to send at once.
+/// * `export -t TABLE -f FILE` - Writes every item in the table to FILE, one per line,
+///   as DynamoDB JSON wrapped as `{"Item": {...}}`, which import reads back unchanged.
+///   * `[--segments N]` - The number of segments to scan in parallel.
+///   * `[--plain]` - Whether to write plain JSON instead.
+/// * `[-r REGION]` - The region in which the client is created.
+///   If not supplied, uses the value of the **AWS_REGION** environment variable.
+///   If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+///
+/// The common options come before the command, as in `json-items -r us-east-1 export -t users -f users.json`.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt { command, common } = Opt::from_args();
+
+    let shared = common.load().await;
+
+    let result = match command {
+        Command::Import {
+            table,
+            file,
+            concurrency,
+        } => {
+            shared.banner(
+                "DynamoDB",
+                PKG_VERSION,
+                &[("Table", &table), ("File", &file)],
+            );
+            import(&shared.dynamodb(), &table, &file, concurrency).await
+        }
+        Command::Export {
+            table,
+            file,
+            segments,
+            plain,
+        } => {
+            shared.banner(
+                "DynamoDB",
+                PKG_VERSION,
+                &[("Table", &table), ("File", &file), ("Segments", &segments)],
+            );
+            export(&shared.dynamodb(), &table, &file, segments, plain).await
+        }
+    };
+
+    if let Err(e) = result {
+        println!("Got an error:");
+        println!("{}", e);
+        shared.exit(1);
+    }
+
+    Ok(())
+}
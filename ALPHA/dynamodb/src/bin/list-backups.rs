@@ -0,0 +1,100 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::output::{date_time, Listing};
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_dynamodb::{Error, PKG_VERSION};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// Only list the backups of this table.
+    #[structopt(short, long)]
+    table: Option<String>,
+
+    #[structopt(flatten)]
+    pages: PageOpt,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+/// Lists your on-demand DynamoDB backups.
+/// # Arguments
+///
+/// * `[-t TABLE]` - Only list the backups of this table.
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
+/// * `[-r REGION]` - The region in which the client is created.
+///    If not supplied, uses the value of the **AWS_REGION** environment variable.
+///    If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt {
+        table,
+        pages,
+        common,
+    } = Opt::from_args();
+
+    let shared = common.load().await;
+
+    shared.banner("DynamoDB", PKG_VERSION, &[]);
+
+    let client = shared.dynamodb();
+
+    let backups = paginate(&pages, move |start, limit| {
+        let request = client
+            .list_backups()
+            .set_table_name(table.clone())
+            .set_exclusive_start_backup_arn(start)
+            .set_limit(limit);
+        async move {
+            request.send().await.map(|resp| {
+                Page::new(
+                    resp.backup_summaries.unwrap_or_default(),
+                    resp.last_evaluated_backup_arn,
+                )
+            })
+        }
+    })
+    .try_collect()
+    .await?;
+    let len = backups.len();
+
+    let mut listing = Listing::new(&["TableName", "BackupName", "Status", "Created", "BackupArn"]);
+    for backup in &backups {
+        listing.push(vec![
+            backup.table_name.as_deref().into(),
+            backup.backup_name.as_deref().into(),
+            backup.backup_status.as_ref().map(|s| s.as_str()).into(),
+            date_time(backup.backup_creation_date_time.as_ref()),
+            backup.backup_arn.as_deref().into(),
+        ]);
+    }
+
+    shared.print(&listing, || {
+        println!("Backups:");
+
+        for backup in &backups {
+            println!(
+                "  {} of {} ({}): {}",
+                backup.backup_name.as_deref().unwrap_or_default(),
+                backup.table_name.as_deref().unwrap_or_default(),
+                backup
+                    .backup_status
+                    .as_ref()
+                    .map(|s| s.as_str())
+                    .unwrap_or_default(),
+                backup.backup_arn.as_deref().unwrap_or_default()
+            );
+        }
+
+        println!("Found {} backups", len);
+    });
+
+    Ok(())
+}
@@ -3,69 +3,132 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_dynamodb::{Client, Error, Region, PKG_VERSION};
+use std::collections::{BTreeSet, HashMap};
+
+use alpha_common::output::Listing;
+use alpha_common::pagination::PageOpt;
+use alpha_common::{CommonOpt, OutputFormat};
+use aws_sdk_dynamodb::model::AttributeValue;
+use aws_sdk_dynamodb::{Error, PKG_VERSION};
+use dynamodb_code_examples::json::{attribute_to_value, item_to_value};
+use dynamodb_code_examples::scan::{parallel_scan, ScanOpt};
+use futures_util::TryStreamExt;
+use serde_json::Value;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The name of the table.
     #[structopt(short, long)]
     table: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    scan: ScanOpt,
+
+    #[structopt(flatten)]
+    pages: PageOpt,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
-/// Lists the items in a DynamoDB table.
+// One row per item, with a column for each projected attribute,
+// or else for every attribute name in any item.
+fn to_listing(items: &[HashMap<String, AttributeValue>], projection: &[String]) -> Listing {
+    let names: Vec<&str> = if projection.is_empty() {
+        let names: BTreeSet<&str> = items
+            .iter()
+            .flat_map(|i| i.keys())
+            .map(String::as_str)
+            .collect();
+        names.into_iter().collect()
+    } else {
+        projection.iter().map(String::as_str).collect()
+    };
+
+    let mut listing = Listing::new(&names);
+    for item in items {
+        listing.push(
+            names
+                .iter()
+                .map(|name| {
+                    item.get(*name)
+                        .map(attribute_to_value)
+                        .unwrap_or(Value::Null)
+                })
+                .collect(),
+        );
+    }
+    listing
+}
+
+/// Lists the items in a DynamoDB table, scanning it in parallel segments.
+/// With `--output ndjson`, items are written as they arrive. So are they with
+/// `--output csv` and `--projection`; otherwise the CSV header has a column for
+/// every attribute found, which means scanning the whole table first.
 /// # Arguments
 ///
 /// * `-t TABLE` - The name of the table.
+/// * `[--segments N]` - The number of segments to scan in parallel. Defaults to 1.
+/// * `[--max-rcu UNITS]` - The most read capacity units to consume per second.
+/// * `[--filter EXPRESSION]` - Only list the items that match, such as "age > :min".
+/// * `[--name #NAME=ATTRIBUTE]...` - An attribute name placeholder for the filter.
+/// * `[--value :NAME=VALUE]...` - A value placeholder for the filter, such as :min=40.
+/// * `[--projection ATTRIBUTE,...]` - The attributes to list. Lists every attribute if not supplied.
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
         table,
-        region,
-        verbose,
+        scan,
+        pages,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("DynamoDB client version: {}", PKG_VERSION);
-        println!(
-            "Region:                  {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Table:                   {}", &table);
+    shared.banner(
+        "DynamoDB",
+        PKG_VERSION,
+        &[("Table", &table), ("Segments", &scan.segments)],
+    );
 
-        println!();
-    }
+    let client = shared.dynamodb();
+
+    let mut items = parallel_scan(&client, &table, &scan, &pages);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    match shared.output() {
+        OutputFormat::Ndjson => {
+            while let Some(item) = items.try_next().await? {
+                print!("{}", to_listing(&[item], &scan.projection).to_ndjson());
+            }
+            return Ok(());
+        }
+        OutputFormat::Csv if !scan.projection.is_empty() => {
+            print!("{}", to_listing(&[], &scan.projection).to_csv());
+            while let Some(item) = items.try_next().await? {
+                print!("{}", to_listing(&[item], &scan.projection).to_csv_rows());
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
 
-    let resp = client.scan().table_name(table).send().await?;
+    let items: Vec<_> = items.try_collect().await?;
 
-    println!("Items in table:");
+    shared.print(&to_listing(&items, &scan.projection), || {
+        println!("Items in table:");
 
-    if let Some(item) = resp.items {
-        println!("   {:?}", item);
-    }
+        for item in &items {
+            println!("   {}", item_to_value(item));
+        }
+        println!("Found {} items", items.len());
+    });
 
     Ok(())
 }
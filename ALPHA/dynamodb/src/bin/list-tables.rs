@@ -3,63 +3,72 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_dynamodb::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::Listing;
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_dynamodb::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists your DynamoDB tables.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { pages, common } = Opt::from_args();
 
-    let Opt { region, verbose } = Opt::from_args();
+    let shared = common.load().await;
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    shared.banner("DynamoDB", PKG_VERSION, &[]);
 
-    if verbose {
-        println!("DynamoDB client version: {}", PKG_VERSION);
-        println!(
-            "Region:                  {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!();
-    }
-
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
-
-    let resp = client.list_tables().send().await?;
+    let client = shared.dynamodb();
 
-    println!("Tables:");
-
-    let names = resp.table_names.unwrap_or_default();
+    let names = paginate(&pages, move |start, limit| {
+        let request = client
+            .list_tables()
+            .set_exclusive_start_table_name(start)
+            .set_limit(limit);
+        async move {
+            request.send().await.map(|resp| {
+                Page::new(
+                    resp.table_names.unwrap_or_default(),
+                    resp.last_evaluated_table_name,
+                )
+            })
+        }
+    })
+    .try_collect()
+    .await?;
     let len = names.len();
 
-    for name in names {
-        println!("  {}", name);
+    let mut listing = Listing::new(&["TableName"]);
+    for name in &names {
+        listing.push(vec![name.as_str().into()]);
     }
 
-    println!("Found {} tables", len);
+    shared.print(&listing, || {
+        println!("Tables:");
+
+        for name in names {
+            println!("  {}", name);
+        }
+
+        println!("Found {} tables", len);
+    });
 
     Ok(())
 }
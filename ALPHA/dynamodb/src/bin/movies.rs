@@ -3,40 +3,27 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_http::AwsErrorRetryPolicy;
-use aws_hyper::{SdkError, SdkSuccess};
+use alpha_common::waiter::{dynamodb, Waiter};
+use alpha_common::CommonOpt;
 use aws_sdk_dynamodb::client::fluent_builders::Query;
-use aws_sdk_dynamodb::error::DescribeTableError;
-use aws_sdk_dynamodb::input::DescribeTableInput;
 use aws_sdk_dynamodb::model::{
     AttributeDefinition, AttributeValue, KeySchemaElement, KeyType, ProvisionedThroughput,
-    ScalarAttributeType, TableStatus,
+    ScalarAttributeType,
 };
-use aws_sdk_dynamodb::operation::DescribeTable;
-use aws_sdk_dynamodb::output::DescribeTableOutput;
-use aws_sdk_dynamodb::{Client, Config, Error, Region, PKG_VERSION};
+use aws_sdk_dynamodb::{Client, Error, PKG_VERSION};
+use dynamodb_code_examples::expression::{key, value, Expressions};
+use dynamodb_code_examples::json::parse_item;
 use serde_json::Value;
-use smithy_http::operation::Operation;
-use smithy_http::retry::ClassifyResponse;
-use smithy_types::retry::RetryKind;
-use std::collections::HashMap;
-use std::time::Duration;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The name of the table.
     #[structopt(short, long)]
     table: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// A partial reimplementation of https://docs.amazonaws.cn/en_us/amazondynamodb/latest/developerguide/GettingStarted.Ruby.html
@@ -48,33 +35,13 @@ struct Opt {
 /// - Query for those rows
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { table, common } = Opt::from_args();
 
-    let Opt {
-        region,
-        table,
-        verbose,
-    } = Opt::from_args();
-
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
-
-    if verbose {
-        println!("DynamoDB client version: {}", PKG_VERSION);
-        println!(
-            "Region:                  {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Table:                   {}", &table);
-        println!();
-    }
+    let shared = common.load().await;
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    shared.banner("DynamoDB", PKG_VERSION, &[("Table", &table)]);
 
-    let raw_client = aws_hyper::Client::https();
+    let client = shared.dynamodb();
 
     let table_exists = client
         .list_tables()
@@ -93,8 +60,7 @@ async fn main() -> Result<(), Error> {
             .expect("failed to create table");
     }
 
-    raw_client
-        .call(wait_for_ready_table(&table.to_string(), client.conf()))
+    dynamodb::table_active(&Waiter::new(), &client, &table)
         .await
         .expect("table should become ready");
 
@@ -108,7 +74,9 @@ async fn main() -> Result<(), Error> {
         client
             .put_item()
             .table_name(&table)
-            .set_item(Some(parse_item(value)))
+            .set_item(Some(
+                parse_item(value).expect("data.json should hold objects"),
+            ))
             .send()
             .await
             .expect("failed to insert item");
@@ -181,89 +149,8 @@ fn create_table(
         )
 }
 
-fn parse_item(value: Value) -> HashMap<String, AttributeValue> {
-    match value_to_item(value) {
-        AttributeValue::M(map) => map,
-        other => panic!("can only insert top level values, got {:?}", other),
-    }
-}
-
-fn value_to_item(value: Value) -> AttributeValue {
-    match value {
-        Value::Null => AttributeValue::Null(true),
-        Value::Bool(b) => AttributeValue::Bool(b),
-        Value::Number(n) => AttributeValue::N(n.to_string()),
-        Value::String(s) => AttributeValue::S(s),
-        Value::Array(a) => AttributeValue::L(a.into_iter().map(value_to_item).collect()),
-        Value::Object(o) => {
-            AttributeValue::M(o.into_iter().map(|(k, v)| (k, value_to_item(v))).collect())
-        }
-    }
-}
-
 fn movies_in_year(client: &Client, table_name: &str, year: u16) -> Query {
-    client
-        .query()
-        .table_name(table_name)
-        .key_condition_expression("#yr = :yyyy")
-        .expression_attribute_names("#yr", "year")
-        .expression_attribute_values(":yyyy", AttributeValue::N(year.to_string()))
-}
-
-/// Hand-written waiter to retry every second until the table is out of `Creating` state
-#[derive(Clone)]
-struct WaitForReadyTable<R> {
-    inner: R,
-}
-
-impl<R> ClassifyResponse<SdkSuccess<DescribeTableOutput>, SdkError<DescribeTableError>>
-    for WaitForReadyTable<R>
-where
-    R: ClassifyResponse<SdkSuccess<DescribeTableOutput>, SdkError<DescribeTableError>>,
-{
-    fn classify(
-        &self,
-        response: Result<&SdkSuccess<DescribeTableOutput>, &SdkError<DescribeTableError>>,
-    ) -> RetryKind {
-        match self.inner.classify(response) {
-            RetryKind::NotRetryable => (),
-            other => return other,
-        };
-        match response {
-            Ok(SdkSuccess { parsed, .. }) => {
-                if parsed
-                    .table
-                    .as_ref()
-                    .unwrap()
-                    .table_status
-                    .as_ref()
-                    .unwrap()
-                    == &TableStatus::Creating
-                {
-                    RetryKind::Explicit(Duration::from_secs(1))
-                } else {
-                    RetryKind::NotRetryable
-                }
-            }
-            _ => RetryKind::NotRetryable,
-        }
-    }
-}
-
-/// Construct a `DescribeTable` request with a policy to retry every second until the table
-/// is ready
-fn wait_for_ready_table(
-    table_name: &str,
-    conf: &Config,
-) -> Operation<DescribeTable, WaitForReadyTable<AwsErrorRetryPolicy>> {
-    let operation = DescribeTableInput::builder()
-        .table_name(table_name)
-        .build()
-        .expect("valid input")
-        .make_operation(conf)
-        .expect("valid operation");
-    let waiting_policy = WaitForReadyTable {
-        inner: operation.retry_policy().clone(),
-    };
-    operation.with_retry_policy(waiting_policy)
+    Expressions::new()
+        .key_condition(key("year").eq(value(&year)))
+        .query(client.query().table_name(table_name))
 }
@@ -0,0 +1,108 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::output::date_time;
+use alpha_common::CommonOpt;
+use aws_sdk_dynamodb::model::{
+    ContinuousBackupsDescription, PointInTimeRecoverySpecification, PointInTimeRecoveryStatus,
+};
+use aws_sdk_dynamodb::{Error, PKG_VERSION};
+use serde_json::Value;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The name of the table.
+    #[structopt(short, long)]
+    table: String,
+
+    /// Turn point-in-time recovery on.
+    #[structopt(long, conflicts_with = "disable")]
+    enable: bool,
+
+    /// Turn point-in-time recovery off.
+    #[structopt(long)]
+    disable: bool,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+fn show(table: &str, backups: Option<ContinuousBackupsDescription>) {
+    let pitr = backups.and_then(|b| b.point_in_time_recovery_description);
+    let status = pitr
+        .as_ref()
+        .and_then(|p| p.point_in_time_recovery_status.clone())
+        .unwrap_or(PointInTimeRecoveryStatus::Disabled);
+    println!(
+        "Point-in-time recovery for {} is {}",
+        table,
+        status.as_str()
+    );
+    if let Some(pitr) = pitr.filter(|_| status == PointInTimeRecoveryStatus::Enabled) {
+        let time = |t| match date_time(t) {
+            Value::String(s) => s,
+            _ => "unknown".to_string(),
+        };
+        println!(
+            "It can be restored to any time from {} to {}",
+            time(pitr.earliest_restorable_date_time.as_ref()),
+            time(pitr.latest_restorable_date_time.as_ref())
+        );
+    }
+}
+
+/// Shows, turns on, or turns off point-in-time recovery for a DynamoDB table,
+/// which keeps the last 35 days of changes so that `restore-table` can
+/// restore the table as it was at any second in that time.
+/// # Arguments
+///
+/// * `-t TABLE` - The name of the table.
+/// * `[--enable]` - Turn point-in-time recovery on.
+/// * `[--disable]` - Turn point-in-time recovery off.
+///    Without either, only shows whether it is on.
+/// * `[-r REGION]` - The region in which the client is created.
+///    If not supplied, uses the value of the **AWS_REGION** environment variable.
+///    If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt {
+        table,
+        enable,
+        disable,
+        common,
+    } = Opt::from_args();
+
+    let shared = common.load().await;
+
+    shared.banner("DynamoDB", PKG_VERSION, &[("Table", &table)]);
+
+    let client = shared.dynamodb();
+
+    let backups = if enable || disable {
+        let spec = PointInTimeRecoverySpecification::builder()
+            .point_in_time_recovery_enabled(enable)
+            .build();
+        client
+            .update_continuous_backups()
+            .table_name(&table)
+            .point_in_time_recovery_specification(spec)
+            .send()
+            .await?
+            .continuous_backups_description
+    } else {
+        client
+            .describe_continuous_backups()
+            .table_name(&table)
+            .send()
+            .await?
+            .continuous_backups_description
+    };
+
+    show(&table, backups);
+
+    Ok(())
+}
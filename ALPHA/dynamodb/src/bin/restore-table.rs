@@ -0,0 +1,126 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::waiter::{dynamodb, Cancel, Waiter};
+use alpha_common::CommonOpt;
+use aws_sdk_dynamodb::{Error, PKG_VERSION};
+use aws_smithy_types::date_time::Format;
+use aws_smithy_types::DateTime;
+use std::time::Duration;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The name of the new table.
+    #[structopt(short, long)]
+    table: String,
+
+    /// The ARN of the backup to restore, as shown by `list-backups`.
+    #[structopt(long, required_unless = "source", conflicts_with = "source")]
+    backup_arn: Option<String>,
+
+    /// The table to restore to a point in time. It must have point-in-time recovery on.
+    #[structopt(long)]
+    source: Option<String>,
+
+    /// The time to restore the source table to, such as 2021-10-14T09:30:00Z.
+    /// Defaults to the latest time that can be restored, a few minutes ago.
+    #[structopt(long, requires = "source", parse(try_from_str = parse_time))]
+    time: Option<DateTime>,
+
+    /// The most seconds to wait for the new table. Defaults to 3600.
+    #[structopt(long)]
+    max_wait: Option<u64>,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+fn parse_time(s: &str) -> Result<DateTime, String> {
+    DateTime::from_str(s, Format::DateTime).map_err(|e| format!("{}: {:?}", s, e))
+}
+
+/// Restores a DynamoDB table, from an on-demand backup or from point-in-time
+/// recovery, to a new table, and waits until the new table is active.
+/// Restoring can take an hour or more for large tables.
+/// # Arguments
+///
+/// * `-t TABLE` - The name of the new table.
+/// * `[--backup-arn ARN]` - The backup to restore.
+/// * `[--source TABLE]` - The table to restore to a point in time, instead of a backup.
+/// * `[--time TIME]` - The time to restore the source table to.
+///    Defaults to the latest time that can be restored.
+/// * `[--max-wait SECONDS]` - The most seconds to wait. Defaults to 3600.
+/// * `[-r REGION]` - The region in which the client is created.
+///    If not supplied, uses the value of the **AWS_REGION** environment variable.
+///    If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt {
+        table,
+        backup_arn,
+        source,
+        time,
+        max_wait,
+        common,
+    } = Opt::from_args();
+
+    let shared = common.load().await;
+
+    shared.banner("DynamoDB", PKG_VERSION, &[("Table", &table)]);
+
+    let client = shared.dynamodb();
+
+    match (backup_arn, source) {
+        (Some(arn), _) => {
+            println!("Restoring backup {} to table {}", arn, table);
+            client
+                .restore_table_from_backup()
+                .backup_arn(arn)
+                .target_table_name(&table)
+                .send()
+                .await?;
+        }
+        (None, Some(source)) => {
+            match &time {
+                Some(time) => println!(
+                    "Restoring table {} as of {} to table {}",
+                    source,
+                    time.fmt(Format::DateTime).unwrap_or_default(),
+                    table
+                ),
+                None => println!(
+                    "Restoring table {} as of the latest restorable time to table {}",
+                    source, table
+                ),
+            }
+            client
+                .restore_table_to_point_in_time()
+                .source_table_name(source)
+                .target_table_name(&table)
+                .set_use_latest_restorable_time(time.is_none().then_some(true))
+                .set_restore_date_time(time)
+                .send()
+                .await?;
+        }
+        (None, None) => unreachable!("structopt requires --backup-arn or --source"),
+    }
+
+    let waiter = Waiter::new()
+        .max_wait(Duration::from_secs(max_wait.unwrap_or(3600)))
+        .max_delay(Duration::from_secs(60))
+        .cancel_with(Cancel::on_ctrl_c())
+        .on_retry(|waited| println!("  still restoring after {}s", waited.as_secs()));
+    if let Err(e) = dynamodb::table_active(&waiter, &client, &table).await {
+        println!("Got an error waiting for the table:");
+        println!("{}", e);
+        shared.exit(1);
+    }
+
+    println!("Restored table {}", table);
+
+    Ok(())
+}
@@ -0,0 +1,354 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_dynamodb::model::{
+    AttributeDefinition, KeySchemaElement, KeyType, ProvisionedThroughput, ScalarAttributeType,
+};
+use aws_sdk_dynamodb::{Client, Error, PKG_VERSION};
+use dynamodb_code_examples::json::{item_to_value, parse_item, value_to_item, Item};
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use serde_json::Value;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// The shell's own commands. Any other line is run as a PartiQL statement.
+const COMMANDS: &[&str] = &[
+    ".tables", ".scan", ".get", ".put", ".delete", ".create", ".drop", ".help", ".quit",
+];
+
+const HELP: &str = "\
+.tables                       List the tables.
+.scan TABLE [LIMIT]           List the items in a table.
+.get TABLE KEY=VALUE...       Get the item with the given key.
+.put TABLE {JSON}             Put an item, given as a JSON object.
+.delete TABLE KEY=VALUE...    Delete the item with the given key.
+.create TABLE KEY             Create a table with a string partition key.
+.drop TABLE                   Delete a table.
+.help                         Show this help.
+.quit                         Leave the shell (or press Ctrl-D).
+Any other line is run as a PartiQL statement, such as
+  SELECT * FROM \"users\" WHERE username = 'jdoe'";
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The file to keep the command history in.
+    /// Defaults to .dynamodb_history in the home directory.
+    #[structopt(long)]
+    history: Option<PathBuf>,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+/// Completes command names, and table names everywhere else.
+#[derive(Default)]
+struct ShellHelper {
+    tables: Vec<String>,
+}
+
+impl Completer for ShellHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let start = line[..pos]
+            .rfind(|c: char| c.is_whitespace() || c == '"')
+            .map_or(0, |i| i + 1);
+        let word = &line[start..pos];
+
+        let candidates: Vec<&str> = if start == 0 && word.starts_with('.') {
+            COMMANDS.to_vec()
+        } else {
+            self.tables.iter().map(String::as_str).collect()
+        };
+        Ok((
+            start,
+            candidates
+                .into_iter()
+                .filter(|c| c.starts_with(word))
+                .map(str::to_string)
+                .collect(),
+        ))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+/// What went wrong running one line.
+type LineResult = Result<(), Box<dyn std::error::Error>>;
+
+async fn list_tables(client: &Client) -> Result<Vec<String>, Error> {
+    let client = client.clone();
+    let names = paginate(&PageOpt::default(), move |start, limit| {
+        let request = client
+            .list_tables()
+            .set_exclusive_start_table_name(start)
+            .set_limit(limit);
+        async move {
+            request.send().await.map(|resp| {
+                Page::new(
+                    resp.table_names.unwrap_or_default(),
+                    resp.last_evaluated_table_name,
+                )
+            })
+        }
+    })
+    .try_collect()
+    .await?;
+    Ok(names)
+}
+
+// Parses KEY=VALUE arguments. A VALUE that is JSON, such as 2013 or "2013",
+// is read as JSON; anything else is a string.
+fn parse_key(args: &[&str]) -> Result<Item, String> {
+    if args.is_empty() {
+        return Err("give the key as KEY=VALUE".into());
+    }
+    args.iter()
+        .map(|arg| match arg.split_once('=') {
+            Some((name, value)) => {
+                let value =
+                    serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.into()));
+                Ok((name.to_string(), value_to_item(value)))
+            }
+            None => Err(format!("expected KEY=VALUE, got {}", arg)),
+        })
+        .collect()
+}
+
+fn print_items(items: &[Item]) -> LineResult {
+    for item in items {
+        println!("{}", serde_json::to_string_pretty(&item_to_value(item))?);
+    }
+    println!(
+        "{} item{}",
+        items.len(),
+        if items.len() == 1 { "" } else { "s" }
+    );
+    Ok(())
+}
+
+async fn execute_statement(client: &Client, statement: &str) -> LineResult {
+    let client = client.clone();
+    let statement = statement.to_string();
+    let items = paginate(&PageOpt::default(), move |token, _| {
+        let request = client
+            .execute_statement()
+            .statement(&statement)
+            .set_next_token(token);
+        async move {
+            request
+                .send()
+                .await
+                .map(|resp| Page::with_token(resp.items.unwrap_or_default(), resp.next_token))
+        }
+    })
+    .try_collect()
+    .await?;
+    print_items(&items)
+}
+
+// Runs a line, returning Ok(false) when the user asks to quit.
+async fn run_line(
+    client: &Client,
+    helper: &mut ShellHelper,
+    line: &str,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let (command, rest) = match line.split_once(char::is_whitespace) {
+        Some((command, rest)) => (command, rest.trim()),
+        None => (line, ""),
+    };
+    let args: Vec<&str> = rest.split_whitespace().collect();
+
+    match (command, args.as_slice()) {
+        (".quit", _) | (".exit", _) => return Ok(false),
+        (".help", _) => println!("{}", HELP),
+        (".tables", []) => {
+            helper.tables = list_tables(client).await?;
+            for table in &helper.tables {
+                println!("{}", table);
+            }
+        }
+        (".scan", [table]) | (".scan", [table, _]) => {
+            let limit = match args.get(1) {
+                Some(limit) => Some(limit.parse::<usize>()?),
+                None => None,
+            };
+            let pages = PageOpt {
+                max_items: limit,
+                page_size: None,
+            };
+            let client = client.clone();
+            let table = table.to_string();
+            let items = paginate(&pages, move |start, limit| {
+                let request = client
+                    .scan()
+                    .table_name(&table)
+                    .set_exclusive_start_key(start)
+                    .set_limit(limit);
+                async move {
+                    request.send().await.map(|resp| {
+                        Page::new(resp.items.unwrap_or_default(), resp.last_evaluated_key)
+                    })
+                }
+            })
+            .try_collect()
+            .await?;
+            print_items(&items)?;
+        }
+        (".get", [table, key @ ..]) => {
+            let resp = client
+                .get_item()
+                .table_name(*table)
+                .set_key(Some(parse_key(key)?))
+                .send()
+                .await?;
+            match resp.item {
+                Some(item) => print_items(&[item])?,
+                None => println!("No such item"),
+            }
+        }
+        (".put", [table, ..]) => {
+            let json = rest[table.len()..].trim();
+            let item = parse_item(serde_json::from_str(json)?)?;
+            client
+                .put_item()
+                .table_name(*table)
+                .set_item(Some(item))
+                .send()
+                .await?;
+            println!("Put 1 item");
+        }
+        (".delete", [table, key @ ..]) => {
+            client
+                .delete_item()
+                .table_name(*table)
+                .set_key(Some(parse_key(key)?))
+                .send()
+                .await?;
+            println!("Deleted 1 item");
+        }
+        (".create", [table, key]) => {
+            let ad = AttributeDefinition::builder()
+                .attribute_name(*key)
+                .attribute_type(ScalarAttributeType::S)
+                .build();
+            let ks = KeySchemaElement::builder()
+                .attribute_name(*key)
+                .key_type(KeyType::Hash)
+                .build();
+            let pt = ProvisionedThroughput::builder()
+                .read_capacity_units(10)
+                .write_capacity_units(5)
+                .build();
+            client
+                .create_table()
+                .table_name(*table)
+                .key_schema(ks)
+                .attribute_definitions(ad)
+                .provisioned_throughput(pt)
+                .send()
+                .await?;
+            helper.tables.push(table.to_string());
+            println!("Creating table {}", table);
+        }
+        (".drop", [table]) => {
+            client.delete_table().table_name(*table).send().await?;
+            helper.tables.retain(|t| t != table);
+            println!("Deleting table {}", table);
+        }
+        (command, _) if command.starts_with('.') => {
+            return Err(format!("unknown command or wrong arguments: {}; try .help", line).into())
+        }
+        _ => execute_statement(client, line).await?,
+    }
+    Ok(true)
+}
+
+/// Runs an interactive shell for working with DynamoDB tables and items.
+/// Table names are completed with Tab, and the command history is kept between sessions.
+/// Enter `.help` to list the commands; any other line is run as a PartiQL statement.
+/// # Arguments
+///
+/// * `[--history FILE]` - The file to keep the command history in.
+///   Defaults to .dynamodb_history in the home directory.
+/// * `[-r REGION]` - The region in which the client is created.
+///   If not supplied, uses the value of the **AWS_REGION** environment variable.
+///   If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt { history, common } = Opt::from_args();
+
+    let shared = common.load().await;
+
+    shared.banner("DynamoDB", PKG_VERSION, &[]);
+
+    let client = shared.dynamodb();
+
+    let history = history.or_else(|| {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".dynamodb_history"))
+    });
+
+    let mut editor = Editor::<ShellHelper>::new();
+    editor.set_helper(Some(ShellHelper {
+        // Without the table names, completion still works for commands.
+        tables: list_tables(&client).await.unwrap_or_default(),
+    }));
+    if let Some(history) = &history {
+        let _ = editor.load_history(history);
+    }
+
+    println!("Enter .help for the commands, or a PartiQL statement.");
+    loop {
+        let line = match editor.readline("dynamodb> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => {
+                println!("Got an error reading the command:");
+                println!("{}", e);
+                break;
+            }
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line);
+
+        let helper = editor.helper_mut().expect("the helper is set");
+        match run_line(&client, helper, line).await {
+            Ok(true) => (),
+            Ok(false) => break,
+            Err(e) => println!("Error: {}", e),
+        }
+    }
+
+    if let Some(history) = &history {
+        let _ = editor.save_history(history);
+    }
+
+    Ok(())
+}
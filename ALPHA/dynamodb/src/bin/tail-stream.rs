@@ -0,0 +1,346 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::waiter::{dynamodb, Cancel, Waiter};
+use alpha_common::{CommonOpt, Shared};
+use aws_sdk_dynamodb::model::{StreamSpecification, StreamViewType};
+use aws_sdk_dynamodb::{Client, Error, PKG_VERSION};
+use aws_sdk_dynamodbstreams::model::{Shard, ShardIteratorType};
+use aws_sdk_dynamodbstreams::{Client as StreamsClient, Error as StreamsError, SdkError};
+use dynamodb_code_examples::stream::{is_closed, ready_shards, record_to_value, Checkpoint};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The name of the table.
+    #[structopt(short, long)]
+    table: String,
+
+    /// The file that records how far the stream has been read.
+    /// Defaults to TABLE.checkpoint.json.
+    #[structopt(long)]
+    checkpoint: Option<PathBuf>,
+
+    /// Without a checkpoint, start from the oldest record in the stream
+    /// rather than from new changes.
+    #[structopt(long)]
+    from_start: bool,
+
+    /// What the stream records, if it has to be enabled.
+    #[structopt(long, default_value = "NEW_AND_OLD_IMAGES",
+                possible_values = &["KEYS_ONLY", "NEW_IMAGE", "OLD_IMAGE", "NEW_AND_OLD_IMAGES"])]
+    view: String,
+
+    /// Stop once every shard has been read up to date, instead of waiting for more changes.
+    #[structopt(long)]
+    once: bool,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+// Returns the ARN of the table's stream, enabling the stream first if needed.
+async fn stream_arn(
+    shared: &Shared,
+    client: &Client,
+    table: &str,
+    view: &str,
+) -> Result<String, Error> {
+    let described = client.describe_table().table_name(table).send().await?;
+    let enabled = described
+        .table
+        .as_ref()
+        .and_then(|t| t.stream_specification.as_ref())
+        .and_then(|s| s.stream_enabled)
+        .unwrap_or_default();
+    if enabled {
+        if let Some(arn) = described.table.and_then(|t| t.latest_stream_arn) {
+            return Ok(arn);
+        }
+    }
+
+    eprintln!("Enabling the stream on table {}.", table);
+    let spec = StreamSpecification::builder()
+        .stream_enabled(true)
+        .stream_view_type(StreamViewType::from(view))
+        .build();
+    client
+        .update_table()
+        .table_name(table)
+        .stream_specification(spec)
+        .send()
+        .await?;
+
+    let waiter = Waiter::new().cancel_with(Cancel::on_ctrl_c());
+    if let Err(e) = dynamodb::table_active(&waiter, client, table).await {
+        eprintln!("Got an error waiting for the table:");
+        eprintln!("{}", e);
+        shared.exit(1);
+    }
+    let described = client.describe_table().table_name(table).send().await?;
+    Ok(described
+        .table
+        .and_then(|t| t.latest_stream_arn)
+        .expect("a table with a stream has a stream ARN"))
+}
+
+async fn list_shards(client: &StreamsClient, stream_arn: &str) -> Result<Vec<Shard>, StreamsError> {
+    let mut shards = Vec::new();
+    let mut start = None;
+    loop {
+        let resp = client
+            .describe_stream()
+            .stream_arn(stream_arn)
+            .set_exclusive_start_shard_id(start)
+            .send()
+            .await?;
+        let description = match resp.stream_description {
+            Some(description) => description,
+            None => break,
+        };
+        shards.extend(description.shards.unwrap_or_default());
+        start = description.last_evaluated_shard_id;
+        if start.is_none() {
+            break;
+        }
+    }
+    Ok(shards)
+}
+
+/// How often to look for new shards while others are still being read.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A shard being read, and where to read it next.
+struct Reader {
+    shard_id: String,
+    iterator: String,
+    /// The sequence number of the last record printed from the shard.
+    last: Option<String>,
+    /// Whether the shard was opened at its newest record, for lack of a checkpoint.
+    latest: bool,
+}
+
+async fn open_at(
+    client: &StreamsClient,
+    stream_arn: &str,
+    shard_id: &str,
+    kind: ShardIteratorType,
+    sequence_number: Option<&str>,
+) -> Result<Reader, StreamsError> {
+    let latest = kind == ShardIteratorType::Latest;
+    let resp = client
+        .get_shard_iterator()
+        .stream_arn(stream_arn)
+        .shard_id(shard_id)
+        .shard_iterator_type(kind)
+        .set_sequence_number(sequence_number.map(str::to_string))
+        .send()
+        .await?;
+    Ok(Reader {
+        shard_id: shard_id.to_string(),
+        iterator: resp.shard_iterator.unwrap_or_default(),
+        last: sequence_number.map(str::to_string),
+        latest,
+    })
+}
+
+async fn open(
+    client: &StreamsClient,
+    stream_arn: &str,
+    shard_id: &str,
+    checkpoint: &Checkpoint,
+    latest: bool,
+) -> Result<Reader, StreamsError> {
+    let (kind, sequence_number) = match checkpoint.start(shard_id) {
+        (ShardIteratorType::TrimHorizon, None) if latest => (ShardIteratorType::Latest, None),
+        start => start,
+    };
+    open_at(client, stream_arn, shard_id, kind, sequence_number).await
+}
+
+// Opens `reader`'s shard again where it left off, after its iterator expired.
+async fn reopen(
+    client: &StreamsClient,
+    stream_arn: &str,
+    reader: &Reader,
+) -> Result<Reader, StreamsError> {
+    let (kind, sequence_number) = match (&reader.last, reader.latest) {
+        (Some(last), _) => (ShardIteratorType::AfterSequenceNumber, Some(last.as_str())),
+        (None, true) => (ShardIteratorType::Latest, None),
+        (None, false) => (ShardIteratorType::TrimHorizon, None),
+    };
+    let mut reopened = open_at(client, stream_arn, &reader.shard_id, kind, sequence_number).await?;
+    reopened.latest = reader.latest;
+    Ok(reopened)
+}
+
+fn save(shared: &Shared, checkpoint: &Checkpoint, path: &Path) {
+    if let Err(e) = checkpoint.save(path) {
+        eprintln!("Got an error saving {}:", path.display());
+        eprintln!("{}", e);
+        shared.exit(1);
+    }
+}
+
+// Reads every shard in turn, in lineage order, printing each record.
+async fn tail(
+    shared: &Shared,
+    client: &StreamsClient,
+    stream_arn: &str,
+    path: &Path,
+    from_start: bool,
+    once: bool,
+) -> Result<(), StreamsError> {
+    let mut checkpoint = match Checkpoint::load(path, stream_arn) {
+        Ok(Some(checkpoint)) => checkpoint,
+        Ok(None) => Checkpoint::new(stream_arn),
+        Err(e) => {
+            eprintln!("Got an error reading {}:", path.display());
+            eprintln!("{}", e);
+            shared.exit(1);
+        }
+    };
+    // Without a checkpoint, the shards open now start at their newest record,
+    // and the closed ones are skipped. Their children start at the oldest.
+    let mut latest =
+        !from_start && checkpoint.positions.is_empty() && checkpoint.finished.is_empty();
+
+    let mut readers: Vec<Reader> = Vec::new();
+    let mut refresh = true;
+    let mut refreshed = Instant::now();
+    loop {
+        // New shards replace closed ones, and appear even when none of ours closed.
+        if refresh || refreshed.elapsed() >= REFRESH_INTERVAL {
+            let shards = list_shards(client, stream_arn).await?;
+            checkpoint.retain(&shards);
+            if latest {
+                for shard in shards.iter().filter(|shard| is_closed(shard)) {
+                    checkpoint.finish(shard.shard_id.as_deref().unwrap_or_default());
+                }
+            }
+            for shard in ready_shards(&shards, &checkpoint) {
+                let shard_id = shard.shard_id.as_deref().unwrap_or_default();
+                if !readers.iter().any(|r| r.shard_id == shard_id) {
+                    readers.push(open(client, stream_arn, shard_id, &checkpoint, latest).await?);
+                }
+            }
+            latest = false;
+            refresh = false;
+            refreshed = Instant::now();
+        }
+
+        let mut idle = true;
+        let mut i = 0;
+        while i < readers.len() {
+            let resp = match client
+                .get_records()
+                .shard_iterator(&readers[i].iterator)
+                .send()
+                .await
+            {
+                Ok(resp) => resp,
+                // Iterators expire after 15 minutes; start again after the last record read.
+                Err(SdkError::ServiceError { err, .. }) if err.is_expired_iterator_exception() => {
+                    readers[i] = reopen(client, stream_arn, &readers[i]).await?;
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            let records = resp.records.unwrap_or_default();
+            let read = !records.is_empty();
+            for record in records {
+                let sequence_number = record
+                    .dynamodb
+                    .as_ref()
+                    .and_then(|change| change.sequence_number.clone());
+                println!("{}", record_to_value(record));
+                if let Some(sequence_number) = sequence_number {
+                    checkpoint.read(&readers[i].shard_id, &sequence_number);
+                    readers[i].last = Some(sequence_number);
+                }
+            }
+
+            match resp.next_shard_iterator {
+                Some(iterator) => {
+                    readers[i].iterator = iterator;
+                    i += 1;
+                }
+                // The shard was closed and has been read to the end, so its
+                // children, if any, can be read now.
+                None => {
+                    checkpoint.finish(&readers.remove(i).shard_id);
+                    refresh = true;
+                }
+            }
+            if read || refresh {
+                idle = false;
+                save(shared, &checkpoint, path);
+            }
+        }
+
+        if idle {
+            if once {
+                return Ok(());
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            refresh = readers.is_empty();
+        }
+    }
+}
+
+/// Follows the changes to a DynamoDB table through its stream, printing
+/// each INSERT, MODIFY, and REMOVE record as a line of JSON.
+/// Enables the stream if the table does not have one.
+/// Reads the shards in order, each after the shard it was split from,
+/// and records how far it got in a checkpoint file, from which it resumes.
+/// # Arguments
+///
+/// * `-t TABLE` - The name of the table.
+/// * `[--checkpoint FILE]` - The checkpoint file. Defaults to TABLE.checkpoint.json.
+/// * `[--from-start]` - Without a checkpoint, start from the oldest record.
+/// * `[--view VIEW]` - What the stream records, if it has to be enabled.
+///    Defaults to NEW_AND_OLD_IMAGES.
+/// * `[--once]` - Stop once the stream has been read up to date.
+/// * `[-r REGION]` - The region in which the client is created.
+///    If not supplied, uses the value of the **AWS_REGION** environment variable.
+///    If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt {
+        table,
+        checkpoint,
+        from_start,
+        view,
+        once,
+        common,
+    } = Opt::from_args();
+
+    let checkpoint = checkpoint.unwrap_or_else(|| format!("{}.checkpoint.json", table).into());
+
+    let shared = common.load().await;
+
+    shared.banner(
+        "DynamoDB",
+        PKG_VERSION,
+        &[("Table", &table), ("Checkpoint", &checkpoint.display())],
+    );
+
+    let client = shared.dynamodb();
+    let streams = shared.dynamodbstreams();
+
+    let arn = stream_arn(&shared, &client, &table, &view).await?;
+
+    if let Err(e) = tail(&shared, &streams, &arn, &checkpoint, from_start, once).await {
+        eprintln!("Got an error reading the stream:");
+        eprintln!("{}", e);
+        shared.exit(1);
+    }
+
+    Ok(())
+}
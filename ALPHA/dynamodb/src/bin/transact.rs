@@ -0,0 +1,243 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::{CommonOpt, Shared};
+use aws_sdk_dynamodb::{Error, PKG_VERSION};
+use dynamodb_code_examples::expression::{attr, value, Update};
+use dynamodb_code_examples::json::{item_to_value, parse_item, Item};
+use dynamodb_code_examples::transaction;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::fmt;
+use std::path::PathBuf;
+use std::process;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The JSON file listing the operations.
+    #[structopt(short, long)]
+    file: PathBuf,
+
+    /// The idempotency token for the writes. Running again with the same
+    /// token within ten minutes does not repeat them.
+    #[structopt(long)]
+    token: Option<String>,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+/// One operation in the file. Keys and items are plain JSON objects, and
+/// `version` is the version of the item when it was last read.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+enum Operation {
+    /// Puts an item. Without a version, there must be no item with its key.
+    Put {
+        table: String,
+        item: Value,
+        version: Option<u64>,
+    },
+    /// Sets and removes attributes of an item.
+    Update {
+        table: String,
+        key: Value,
+        #[serde(default)]
+        set: Map<String, Value>,
+        #[serde(default)]
+        remove: Vec<String>,
+        version: Option<u64>,
+    },
+    /// Deletes an item.
+    Delete {
+        table: String,
+        key: Value,
+        version: Option<u64>,
+    },
+    /// Cancels the transaction unless the item exists at the given version.
+    Check {
+        table: String,
+        key: Value,
+        version: Option<u64>,
+    },
+    /// Reads an item. A file of gets is read in one transaction,
+    /// and cannot also have writes.
+    Get { table: String, key: Value },
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, table, key) = match self {
+            Operation::Put { table, item, .. } => ("put", table, item),
+            Operation::Update { table, key, .. } => ("update", table, key),
+            Operation::Delete { table, key, .. } => ("delete", table, key),
+            Operation::Check { table, key, .. } => ("check", table, key),
+            Operation::Get { table, key } => ("get", table, key),
+        };
+        write!(f, "{} {} {}", name, table, key)
+    }
+}
+
+fn item(shared: &Shared, value: &Value) -> Item {
+    match parse_item(value.clone()) {
+        Ok(item) => item,
+        Err(e) => {
+            println!("Got an error reading the operations:");
+            println!("{}", e);
+            shared.exit(1);
+        }
+    }
+}
+
+fn read_operations(file: &PathBuf) -> Result<Vec<Operation>, Box<dyn std::error::Error>> {
+    let json = std::fs::read(file)?;
+    let operations: Vec<Operation> = serde_json::from_slice(&json)?;
+    if operations.is_empty() {
+        return Err("the file lists no operations".into());
+    }
+    Ok(operations)
+}
+
+/// Writes or reads several DynamoDB items in one transaction: all of the
+/// writes are made or none are, and all of the reads see the same moment.
+/// Writes keep a version number in each item's `version` attribute, and fail
+/// if the item's version is no longer the one given, so that concurrent
+/// writers do not overwrite each other.
+///
+/// The file holds a JSON array of operations, such as:
+///
+/// ```json
+/// [
+///   {"put": {"table": "users", "item": {"username": "jdoe", "age": 33}}},
+///   {"update": {"table": "users", "key": {"username": "asmith"}, "set": {"age": 45}, "version": 2}},
+///   {"delete": {"table": "users", "key": {"username": "bwhite"}, "version": 5}},
+///   {"check": {"table": "accounts", "key": {"id": "a1"}, "version": 7}}
+/// ]
+/// ```
+///
+/// or only `{"get": {"table": ..., "key": ...}}` operations. The file must list
+/// at least one operation.
+/// # Arguments
+///
+/// * `-f FILE` - The JSON file listing the operations.
+/// * `[--token TOKEN]` - The idempotency token for the writes.
+/// * `[-r REGION]` - The region in which the client is created.
+///    If not supplied, uses the value of the **AWS_REGION** environment variable.
+///    If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt {
+        file,
+        token,
+        common,
+    } = Opt::from_args();
+
+    let operations = match read_operations(&file) {
+        Ok(operations) => operations,
+        Err(e) => {
+            println!("Got an error reading {}:", file.display());
+            println!("{}", e);
+            process::exit(1);
+        }
+    };
+
+    let shared = common.load().await;
+
+    shared.banner(
+        "DynamoDB",
+        PKG_VERSION,
+        &[("File", &file.display()), ("Operations", &operations.len())],
+    );
+
+    let client = shared.dynamodb();
+
+    let gets = operations
+        .iter()
+        .filter(|op| matches!(op, Operation::Get { .. }))
+        .count();
+    if gets == operations.len() {
+        let items = operations
+            .iter()
+            .filter_map(|op| match op {
+                Operation::Get { table, key } => Some(transaction::get(table, item(&shared, key))),
+                _ => None,
+            })
+            .collect();
+        let items = transaction::read(&client, items).await?;
+        for (op, item) in operations.iter().zip(items) {
+            match item {
+                Some(item) => println!(
+                    "{}:\n{}",
+                    op,
+                    serde_json::to_string_pretty(&item_to_value(&item)).unwrap()
+                ),
+                None => println!("{}: not found", op),
+            }
+        }
+        return Ok(());
+    }
+    if gets > 0 {
+        println!("A transaction cannot both get and write items.");
+        shared.exit(1);
+    }
+
+    let writes = operations
+        .iter()
+        .map(|op| match op {
+            Operation::Put {
+                table,
+                item: i,
+                version,
+            } => transaction::put(table, item(&shared, i), *version),
+            Operation::Update {
+                table,
+                key,
+                set,
+                remove,
+                version,
+            } => {
+                let mut update = Update::new();
+                for (name, v) in set {
+                    update = update.set(attr(name.as_str()), value(v));
+                }
+                for name in remove {
+                    update = update.remove(attr(name.as_str()));
+                }
+                transaction::update(table, item(&shared, key), update, *version)
+            }
+            Operation::Delete {
+                table,
+                key,
+                version,
+            } => transaction::delete(table, item(&shared, key), *version),
+            Operation::Check {
+                table,
+                key,
+                version,
+            } => transaction::check(table, item(&shared, key), *version),
+            Operation::Get { .. } => unreachable!("gets were handled above"),
+        })
+        .collect();
+
+    match transaction::write(&client, writes, token).await {
+        Ok(()) => println!("Wrote {} operations in one transaction.", operations.len()),
+        Err(e) => {
+            let reasons = transaction::cancellation_reasons(&e);
+            if !reasons.is_empty() {
+                println!("The transaction was canceled, and nothing was written:");
+                for (i, (op, reason)) in operations.iter().zip(reasons).enumerate() {
+                    if let Some(reason) = reason {
+                        println!("  {} {}: {}", i + 1, op, reason);
+                    }
+                }
+            }
+            return Err(e.into());
+        }
+    }
+
+    Ok(())
+}
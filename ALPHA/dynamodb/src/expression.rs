@@ -0,0 +1,778 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Building DynamoDB expressions without writing placeholders by hand.
+//!
+//! [`Expressions`] collects a key condition, filter, projection, condition,
+//! and update, gives every attribute name a `#n` placeholder and every value
+//! a `:v` placeholder, and sets them all on a request:
+//!
+//! ```no_run
+//! use dynamodb_code_examples::expression::{attr, key, value, Expressions};
+//!
+//! # async fn run(client: aws_sdk_dynamodb::Client) -> Result<(), aws_sdk_dynamodb::Error> {
+//! // KeyConditionExpression: #n0 = :v0 AND begins_with(#n1, :v1)
+//! // FilterExpression: #n2.#n3 > :v2
+//! let resp = Expressions::new()
+//!     .key_condition(key("year").eq(value(&2013)).and(key("title").begins_with(value("R"))))
+//!     .filter(attr("info").field("rating").gt(value(&7)))
+//!     .projection([attr("title"), attr("info").field("rating")])
+//!     .query(client.query().table_name("movies"))
+//!     .send()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::mem;
+
+use aws_sdk_dynamodb::client::fluent_builders::{
+    DeleteItem, GetItem, PutItem, Query, Scan, UpdateItem,
+};
+use aws_sdk_dynamodb::model::AttributeValue;
+use serde::Serialize;
+
+use crate::item::to_attribute_value;
+
+/// A path to an attribute, or to an element of a map or list attribute.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Path(Vec<Segment>);
+
+#[derive(Clone, Debug, PartialEq)]
+enum Segment {
+    Name(String),
+    Index(usize),
+}
+
+/// The top-level attribute `name`.
+pub fn attr(name: impl Into<String>) -> Path {
+    Path(vec![Segment::Name(name.into())])
+}
+
+/// Either side of a comparison: an attribute, a value, or the size of an attribute.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operand {
+    /// The value at a path.
+    Path(Path),
+    /// A literal value.
+    Value(AttributeValue),
+    /// The size of the value at a path.
+    Size(Path),
+}
+
+impl From<Path> for Operand {
+    fn from(path: Path) -> Self {
+        Operand::Path(path)
+    }
+}
+
+impl From<AttributeValue> for Operand {
+    fn from(value: AttributeValue) -> Self {
+        Operand::Value(value)
+    }
+}
+
+/// A literal value, converted with [`to_attribute_value`](crate::item::to_attribute_value).
+///
+/// # Panics
+///
+/// If DynamoDB cannot store `value`, such as a floating-point NaN.
+pub fn value<T: Serialize + ?Sized>(value: &T) -> Operand {
+    Operand::Value(to_attribute_value(value).expect("value cannot be stored in DynamoDB"))
+}
+
+impl Path {
+    /// The element `name` of this map attribute.
+    pub fn field(mut self, name: impl Into<String>) -> Path {
+        self.0.push(Segment::Name(name.into()));
+        self
+    }
+
+    /// The element at `index` of this list attribute.
+    pub fn index(mut self, index: usize) -> Path {
+        self.0.push(Segment::Index(index));
+        self
+    }
+
+    /// The size of the attribute: its length for strings, binary, and lists,
+    /// and its number of elements for maps and sets.
+    pub fn size(self) -> Operand {
+        Operand::Size(self)
+    }
+
+    /// Whether the attribute equals `other`.
+    pub fn eq(self, other: impl Into<Operand>) -> Condition {
+        self.compare("=", other)
+    }
+
+    /// Whether the attribute differs from `other`.
+    pub fn ne(self, other: impl Into<Operand>) -> Condition {
+        self.compare("<>", other)
+    }
+
+    /// Whether the attribute is less than `other`.
+    pub fn lt(self, other: impl Into<Operand>) -> Condition {
+        self.compare("<", other)
+    }
+
+    /// Whether the attribute is at most `other`.
+    pub fn le(self, other: impl Into<Operand>) -> Condition {
+        self.compare("<=", other)
+    }
+
+    /// Whether the attribute is greater than `other`.
+    pub fn gt(self, other: impl Into<Operand>) -> Condition {
+        self.compare(">", other)
+    }
+
+    /// Whether the attribute is at least `other`.
+    pub fn ge(self, other: impl Into<Operand>) -> Condition {
+        self.compare(">=", other)
+    }
+
+    fn compare(self, op: &'static str, other: impl Into<Operand>) -> Condition {
+        Condition::Compare(Operand::Path(self), op, other.into())
+    }
+
+    /// Whether the attribute is between `low` and `high`, inclusive.
+    pub fn between(self, low: impl Into<Operand>, high: impl Into<Operand>) -> Condition {
+        Condition::Between(Operand::Path(self), low.into(), high.into())
+    }
+
+    /// Whether the attribute equals one of `values`.
+    pub fn is_in(self, values: impl IntoIterator<Item = Operand>) -> Condition {
+        Condition::In(Operand::Path(self), values.into_iter().collect())
+    }
+
+    /// Whether the string attribute starts with `prefix`.
+    pub fn begins_with(self, prefix: impl Into<Operand>) -> Condition {
+        Condition::Function("begins_with", vec![Operand::Path(self), prefix.into()])
+    }
+
+    /// Whether the string attribute contains a substring, or the set or list attribute contains an element.
+    pub fn contains(self, operand: impl Into<Operand>) -> Condition {
+        Condition::Function("contains", vec![Operand::Path(self), operand.into()])
+    }
+
+    /// Whether the attribute is of a type, such as `S`, `N`, or `M`.
+    pub fn is_type(self, type_name: &str) -> Condition {
+        Condition::Function(
+            "attribute_type",
+            vec![
+                Operand::Path(self),
+                Operand::Value(AttributeValue::S(type_name.to_string())),
+            ],
+        )
+    }
+
+    /// Whether the item has the attribute.
+    pub fn exists(self) -> Condition {
+        Condition::Function("attribute_exists", vec![Operand::Path(self)])
+    }
+
+    /// Whether the item lacks the attribute.
+    pub fn not_exists(self) -> Condition {
+        Condition::Function("attribute_not_exists", vec![Operand::Path(self)])
+    }
+}
+
+/// A condition, for a `FilterExpression` or `ConditionExpression`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Condition {
+    #[doc(hidden)]
+    Compare(Operand, &'static str, Operand),
+    #[doc(hidden)]
+    Between(Operand, Operand, Operand),
+    #[doc(hidden)]
+    In(Operand, Vec<Operand>),
+    #[doc(hidden)]
+    Function(&'static str, Vec<Operand>),
+    #[doc(hidden)]
+    And(Box<Condition>, Box<Condition>),
+    #[doc(hidden)]
+    Or(Box<Condition>, Box<Condition>),
+    #[doc(hidden)]
+    Not(Box<Condition>),
+}
+
+impl Condition {
+    /// Whether both conditions hold.
+    pub fn and(self, other: Condition) -> Condition {
+        Condition::And(Box::new(self), Box::new(other))
+    }
+
+    /// Whether either condition holds.
+    pub fn or(self, other: Condition) -> Condition {
+        Condition::Or(Box::new(self), Box::new(other))
+    }
+}
+
+impl std::ops::Not for Condition {
+    type Output = Condition;
+
+    fn not(self) -> Condition {
+        Condition::Not(Box::new(self))
+    }
+}
+
+/// A key attribute, for a `KeyConditionExpression`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Key(String);
+
+/// The key attribute `name`.
+pub fn key(name: impl Into<String>) -> Key {
+    Key(name.into())
+}
+
+/// A condition on the partition key: it must equal a value.
+///
+/// [`and`](KeyCondition::and) adds a condition on the sort key.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyCondition {
+    partition: (Key, Operand),
+    sort: Option<SortKeyCondition>,
+}
+
+/// A condition on the sort key.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SortKeyCondition(Condition);
+
+impl Key {
+    /// Whether the key equals `value`. Works for the partition key and the sort key.
+    pub fn eq(self, value: Operand) -> KeyCondition {
+        KeyCondition {
+            partition: (self, value),
+            sort: None,
+        }
+    }
+
+    /// Whether the sort key is less than `value`.
+    pub fn lt(self, value: Operand) -> SortKeyCondition {
+        SortKeyCondition(attr(self.0).lt(value))
+    }
+
+    /// Whether the sort key is at most `value`.
+    pub fn le(self, value: Operand) -> SortKeyCondition {
+        SortKeyCondition(attr(self.0).le(value))
+    }
+
+    /// Whether the sort key is greater than `value`.
+    pub fn gt(self, value: Operand) -> SortKeyCondition {
+        SortKeyCondition(attr(self.0).gt(value))
+    }
+
+    /// Whether the sort key is at least `value`.
+    pub fn ge(self, value: Operand) -> SortKeyCondition {
+        SortKeyCondition(attr(self.0).ge(value))
+    }
+
+    /// Whether the key is between `low` and `high`, inclusive.
+    pub fn between(self, low: Operand, high: Operand) -> SortKeyCondition {
+        SortKeyCondition(attr(self.0).between(low, high))
+    }
+
+    /// Whether the string key starts with `prefix`.
+    pub fn begins_with(self, prefix: Operand) -> SortKeyCondition {
+        SortKeyCondition(attr(self.0).begins_with(prefix))
+    }
+}
+
+impl KeyCondition {
+    /// Adds a condition on the sort key.
+    pub fn and(mut self, sort: impl Into<SortKeyCondition>) -> KeyCondition {
+        self.sort = Some(sort.into());
+        self
+    }
+}
+
+impl From<KeyCondition> for SortKeyCondition {
+    /// The sort key condition `key = value`.
+    fn from(condition: KeyCondition) -> Self {
+        let (Key(name), value) = condition.partition;
+        SortKeyCondition(attr(name).eq(value))
+    }
+}
+
+/// The changes of an `UpdateExpression`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Update {
+    set: Vec<(Path, SetValue)>,
+    remove: Vec<Path>,
+    add: Vec<(Path, Operand)>,
+    delete: Vec<(Path, Operand)>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum SetValue {
+    Operand(Operand),
+    IfNotExists(Path, Operand),
+    Plus(Path, Operand),
+    Minus(Path, Operand),
+    ListAppend(Path, Operand),
+}
+
+impl Update {
+    /// Creates an update that changes nothing.
+    pub fn new() -> Self {
+        Update::default()
+    }
+
+    /// Sets the attribute to `value`.
+    pub fn set(mut self, path: Path, value: impl Into<Operand>) -> Self {
+        self.set.push((path, SetValue::Operand(value.into())));
+        self
+    }
+
+    /// Sets the attribute to `value` if the item does not have it.
+    pub fn set_if_not_exists(mut self, path: Path, value: impl Into<Operand>) -> Self {
+        self.set
+            .push((path.clone(), SetValue::IfNotExists(path, value.into())));
+        self
+    }
+
+    /// Adds `amount` to the number attribute.
+    pub fn increment(mut self, path: Path, amount: impl Into<Operand>) -> Self {
+        self.set
+            .push((path.clone(), SetValue::Plus(path, amount.into())));
+        self
+    }
+
+    /// Subtracts `amount` from the number attribute.
+    pub fn decrement(mut self, path: Path, amount: impl Into<Operand>) -> Self {
+        self.set
+            .push((path.clone(), SetValue::Minus(path, amount.into())));
+        self
+    }
+
+    /// Appends the elements of the list `values` to the list attribute.
+    pub fn append(mut self, path: Path, values: impl Into<Operand>) -> Self {
+        self.set
+            .push((path.clone(), SetValue::ListAppend(path, values.into())));
+        self
+    }
+
+    /// Removes the attribute.
+    pub fn remove(mut self, path: Path) -> Self {
+        self.remove.push(path);
+        self
+    }
+
+    /// Adds `value` to the number attribute, or its elements to the set attribute,
+    /// creating the attribute if needed.
+    pub fn add(mut self, path: Path, value: impl Into<Operand>) -> Self {
+        self.add.push((path, value.into()));
+        self
+    }
+
+    /// Removes the elements of the set `value` from the set attribute.
+    pub fn delete(mut self, path: Path, value: impl Into<Operand>) -> Self {
+        self.delete.push((path, value.into()));
+        self
+    }
+}
+
+/// Expressions for one request, and the placeholders they use.
+///
+/// Setting an expression again replaces it, along with the placeholders it used.
+#[derive(Clone, Debug, Default)]
+pub struct Expressions {
+    // The expressions as set, in order, so that replacing one renders them
+    // all again without the placeholders only it used.
+    parts: Vec<Part>,
+    key_condition: Option<String>,
+    filter: Option<String>,
+    projection: Option<String>,
+    condition: Option<String>,
+    update: Option<String>,
+    placeholders: Placeholders,
+}
+
+#[derive(Clone, Debug)]
+enum Part {
+    KeyCondition(KeyCondition),
+    Filter(Condition),
+    Projection(Vec<Path>),
+    Condition(Condition),
+    Update(Update),
+}
+
+#[derive(Clone, Debug, Default)]
+struct Placeholders {
+    names: Vec<String>,
+    values: Vec<AttributeValue>,
+}
+
+impl Placeholders {
+    fn name(&mut self, name: &str) -> String {
+        let index = match self.names.iter().position(|n| n == name) {
+            Some(index) => index,
+            None => {
+                self.names.push(name.to_string());
+                self.names.len() - 1
+            }
+        };
+        format!("#n{}", index)
+    }
+
+    fn value(&mut self, value: &AttributeValue) -> String {
+        self.values.push(value.clone());
+        format!(":v{}", self.values.len() - 1)
+    }
+
+    fn path(&mut self, path: &Path) -> String {
+        let mut out = String::new();
+        for segment in &path.0 {
+            match segment {
+                Segment::Name(name) => {
+                    if !out.is_empty() {
+                        out.push('.');
+                    }
+                    out.push_str(&self.name(name));
+                }
+                Segment::Index(index) => write!(out, "[{}]", index).unwrap(),
+            }
+        }
+        out
+    }
+
+    fn operand(&mut self, operand: &Operand) -> String {
+        match operand {
+            Operand::Path(path) => self.path(path),
+            Operand::Value(value) => self.value(value),
+            Operand::Size(path) => format!("size({})", self.path(path)),
+        }
+    }
+
+    fn condition(&mut self, condition: &Condition) -> String {
+        match condition {
+            Condition::Compare(a, op, b) => {
+                format!("{} {} {}", self.operand(a), op, self.operand(b))
+            }
+            Condition::Between(a, low, high) => format!(
+                "{} BETWEEN {} AND {}",
+                self.operand(a),
+                self.operand(low),
+                self.operand(high)
+            ),
+            Condition::In(a, values) => {
+                let a = self.operand(a);
+                let values: Vec<String> = values.iter().map(|v| self.operand(v)).collect();
+                format!("{} IN ({})", a, values.join(", "))
+            }
+            Condition::Function(function, args) => {
+                let args: Vec<String> = args.iter().map(|a| self.operand(a)).collect();
+                format!("{}({})", function, args.join(", "))
+            }
+            Condition::And(a, b) => format!("{} AND {}", self.nested(a), self.nested(b)),
+            Condition::Or(a, b) => format!("{} OR {}", self.nested(a), self.nested(b)),
+            Condition::Not(a) => format!("NOT {}", self.nested(a)),
+        }
+    }
+
+    // Parenthesizes compound conditions, so that AND and OR group as built.
+    fn nested(&mut self, condition: &Condition) -> String {
+        match condition {
+            Condition::And(..) | Condition::Or(..) | Condition::Not(..) => {
+                format!("({})", self.condition(condition))
+            }
+            _ => self.condition(condition),
+        }
+    }
+
+    fn update(&mut self, update: &Update) -> String {
+        let mut clauses = Vec::new();
+        if !update.set.is_empty() {
+            let actions: Vec<String> = update
+                .set
+                .iter()
+                .map(|(path, value)| {
+                    let path = self.path(path);
+                    let value = match value {
+                        SetValue::Operand(v) => self.operand(v),
+                        SetValue::IfNotExists(p, v) => {
+                            format!("if_not_exists({}, {})", self.path(p), self.operand(v))
+                        }
+                        SetValue::Plus(p, v) => format!("{} + {}", self.path(p), self.operand(v)),
+                        SetValue::Minus(p, v) => format!("{} - {}", self.path(p), self.operand(v)),
+                        SetValue::ListAppend(p, v) => {
+                            format!("list_append({}, {})", self.path(p), self.operand(v))
+                        }
+                    };
+                    format!("{} = {}", path, value)
+                })
+                .collect();
+            clauses.push(format!("SET {}", actions.join(", ")));
+        }
+        if !update.remove.is_empty() {
+            let paths: Vec<String> = update.remove.iter().map(|p| self.path(p)).collect();
+            clauses.push(format!("REMOVE {}", paths.join(", ")));
+        }
+        for (keyword, actions) in &[("ADD", &update.add), ("DELETE", &update.delete)] {
+            if !actions.is_empty() {
+                let actions: Vec<String> = actions
+                    .iter()
+                    .map(|(p, v)| format!("{} {}", self.path(p), self.operand(v)))
+                    .collect();
+                clauses.push(format!("{} {}", keyword, actions.join(", ")));
+            }
+        }
+        clauses.join(" ")
+    }
+}
+
+impl Expressions {
+    /// Creates an empty set of expressions.
+    pub fn new() -> Self {
+        Expressions::default()
+    }
+
+    /// Sets the `KeyConditionExpression` of a query.
+    pub fn key_condition(self, condition: impl Into<KeyCondition>) -> Self {
+        self.set(Part::KeyCondition(condition.into()))
+    }
+
+    /// Sets the `FilterExpression` of a query or scan.
+    pub fn filter(self, condition: Condition) -> Self {
+        self.set(Part::Filter(condition))
+    }
+
+    /// Sets the `ConditionExpression` of a put, update, or delete.
+    pub fn condition(self, condition: Condition) -> Self {
+        self.set(Part::Condition(condition))
+    }
+
+    /// Sets the `ProjectionExpression`: the attributes to return.
+    pub fn projection(self, paths: impl IntoIterator<Item = Path>) -> Self {
+        self.set(Part::Projection(paths.into_iter().collect()))
+    }
+
+    /// Sets the `UpdateExpression` of an update.
+    pub fn update(self, update: Update) -> Self {
+        self.set(Part::Update(update))
+    }
+
+    // Adds `part`, or replaces the part of the same kind, and renders them all.
+    fn set(mut self, part: Part) -> Self {
+        let mut parts = mem::take(&mut self.parts);
+        match parts
+            .iter()
+            .position(|p| mem::discriminant(p) == mem::discriminant(&part))
+        {
+            Some(index) => parts[index] = part,
+            None => parts.push(part),
+        }
+
+        let mut exprs = Expressions::new();
+        for part in &parts {
+            exprs.render(part);
+        }
+        exprs.parts = parts;
+        exprs
+    }
+
+    fn render(&mut self, part: &Part) {
+        let placeholders = &mut self.placeholders;
+        match part {
+            Part::KeyCondition(KeyCondition { partition, sort }) => {
+                let (Key(name), value) = partition;
+                let mut expression = placeholders.condition(&attr(name.as_str()).eq(value.clone()));
+                if let Some(SortKeyCondition(sort)) = sort {
+                    expression = format!("{} AND {}", expression, placeholders.condition(sort));
+                }
+                self.key_condition = Some(expression);
+            }
+            Part::Filter(condition) => self.filter = Some(placeholders.condition(condition)),
+            Part::Projection(paths) => {
+                let paths: Vec<String> = paths.iter().map(|p| placeholders.path(p)).collect();
+                self.projection = Some(paths.join(", "));
+            }
+            Part::Condition(condition) => self.condition = Some(placeholders.condition(condition)),
+            Part::Update(update) => self.update = Some(placeholders.update(update)),
+        }
+    }
+
+    /// The `KeyConditionExpression`, if one was set.
+    pub fn key_condition_expression(&self) -> Option<&str> {
+        self.key_condition.as_deref()
+    }
+
+    /// The `FilterExpression`, if one was set.
+    pub fn filter_expression(&self) -> Option<&str> {
+        self.filter.as_deref()
+    }
+
+    /// The `ProjectionExpression`, if one was set.
+    pub fn projection_expression(&self) -> Option<&str> {
+        self.projection.as_deref()
+    }
+
+    /// The `ConditionExpression`, if one was set.
+    pub fn condition_expression(&self) -> Option<&str> {
+        self.condition.as_deref()
+    }
+
+    /// The `UpdateExpression`, if one was set.
+    pub fn update_expression(&self) -> Option<&str> {
+        self.update.as_deref()
+    }
+
+    /// The `ExpressionAttributeNames`, or `None` if no expression uses a name,
+    /// since DynamoDB rejects an empty map.
+    pub fn names(&self) -> Option<HashMap<String, String>> {
+        let names = &self.placeholders.names;
+        (!names.is_empty()).then(|| {
+            names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| (format!("#n{}", i), name.clone()))
+                .collect()
+        })
+    }
+
+    /// The `ExpressionAttributeValues`, or `None` if no expression uses a value.
+    pub fn values(&self) -> Option<HashMap<String, AttributeValue>> {
+        let values = &self.placeholders.values;
+        (!values.is_empty()).then(|| {
+            values
+                .iter()
+                .enumerate()
+                .map(|(i, value)| (format!(":v{}", i), value.clone()))
+                .collect()
+        })
+    }
+
+    /// Sets the key condition, filter, projection, and placeholders of a query.
+    pub fn query(self, query: Query) -> Query {
+        query
+            .set_key_condition_expression(self.key_condition.clone())
+            .set_filter_expression(self.filter.clone())
+            .set_projection_expression(self.projection.clone())
+            .set_expression_attribute_names(self.names())
+            .set_expression_attribute_values(self.values())
+    }
+
+    /// Sets the filter, projection, and placeholders of a scan.
+    pub fn scan(self, scan: Scan) -> Scan {
+        scan.set_filter_expression(self.filter.clone())
+            .set_projection_expression(self.projection.clone())
+            .set_expression_attribute_names(self.names())
+            .set_expression_attribute_values(self.values())
+    }
+
+    /// Sets the projection and placeholders of a get.
+    pub fn get_item(self, get: GetItem) -> GetItem {
+        get.set_projection_expression(self.projection.clone())
+            .set_expression_attribute_names(self.names())
+    }
+
+    /// Sets the condition and placeholders of a put.
+    pub fn put_item(self, put: PutItem) -> PutItem {
+        put.set_condition_expression(self.condition.clone())
+            .set_expression_attribute_names(self.names())
+            .set_expression_attribute_values(self.values())
+    }
+
+    /// Sets the update, condition, and placeholders of an update.
+    pub fn update_item(self, update: UpdateItem) -> UpdateItem {
+        update
+            .set_update_expression(self.update.clone())
+            .set_condition_expression(self.condition.clone())
+            .set_expression_attribute_names(self.names())
+            .set_expression_attribute_values(self.values())
+    }
+
+    /// Sets the condition and placeholders of a delete.
+    pub fn delete_item(self, delete: DeleteItem) -> DeleteItem {
+        delete
+            .set_condition_expression(self.condition.clone())
+            .set_expression_attribute_names(self.names())
+            .set_expression_attribute_values(self.values())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(n: &str) -> AttributeValue {
+        AttributeValue::N(n.to_string())
+    }
+
+    #[test]
+    fn key_condition_and_filter_share_placeholders() {
+        let exprs = Expressions::new()
+            .key_condition(
+                key("year")
+                    .eq(value(&2013))
+                    .and(key("title").between(value("A"), value("M"))),
+            )
+            .filter(
+                attr("info")
+                    .field("rating")
+                    .ge(value(&7))
+                    .and(attr("year").ne(value(&2014)).or(!attr("title").exists())),
+            )
+            .projection(vec![attr("title"), attr("info").field("actors").index(0)]);
+
+        assert_eq!(
+            exprs.key_condition_expression(),
+            Some("#n0 = :v0 AND #n1 BETWEEN :v1 AND :v2")
+        );
+        assert_eq!(
+            exprs.filter_expression(),
+            Some("#n2.#n3 >= :v3 AND (#n0 <> :v4 OR (NOT attribute_exists(#n1)))")
+        );
+        assert_eq!(exprs.projection_expression(), Some("#n1, #n2.#n4[0]"));
+        assert_eq!(exprs.names().unwrap()["#n4"], "actors");
+        assert_eq!(exprs.values().unwrap()[":v3"], n("7"));
+    }
+
+    #[test]
+    fn update_groups_actions_by_clause() {
+        let exprs = Expressions::new()
+            .update(
+                Update::new()
+                    .set(attr("age"), value(&44))
+                    .increment(attr("logins"), value(&1))
+                    .remove(attr("nickname"))
+                    .add(attr("tags"), AttributeValue::Ss(vec!["new".into()])),
+            )
+            .condition(attr("username").exists());
+
+        assert_eq!(
+            exprs.update_expression(),
+            Some("SET #n0 = :v0, #n1 = #n1 + :v1 REMOVE #n2 ADD #n3 :v2")
+        );
+        assert_eq!(exprs.condition_expression(), Some("attribute_exists(#n4)"));
+    }
+
+    #[test]
+    fn replacing_a_filter_drops_its_placeholders() {
+        let exprs = Expressions::new()
+            .key_condition(key("year").eq(value(&2013)))
+            .filter(
+                attr("rating")
+                    .ge(value(&7))
+                    .and(attr("genre").eq(value("drama"))),
+            )
+            .filter(attr("rating").ge(value(&8)));
+
+        assert_eq!(exprs.key_condition_expression(), Some("#n0 = :v0"));
+        assert_eq!(exprs.filter_expression(), Some("#n1 >= :v1"));
+        assert_eq!(exprs.names().unwrap().len(), 2);
+        let values = exprs.values().unwrap();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[":v1"], n("8"));
+    }
+
+    #[test]
+    fn leaves_out_empty_placeholder_maps() {
+        let exprs = Expressions::new();
+        assert_eq!(exprs.names(), None);
+        assert_eq!(exprs.values(), None);
+    }
+}
@@ -0,0 +1,710 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Putting and getting Rust types as DynamoDB items with serde.
+//!
+//! [`to_item`] serializes a struct or map into an item, and [`from_item`]
+//! deserializes an item back:
+//!
+//! * Numbers are stored as `N`, strings and unit enum variants as `S`, and
+//!   `bool` as `BOOL`.
+//! * `None`, and any other null, is left out of items and maps.
+//!   A missing attribute deserializes as `None`.
+//! * Nested structs and maps are stored as `M`, and sequences as `L`.
+//!   String, number, and binary sets are read as sequences.
+//! * Byte buffers, such as those serialized with `serde_bytes`, are stored as `B`.
+//!
+//! ```no_run
+//! use aws_sdk_dynamodb::model::AttributeValue;
+//! use dynamodb_code_examples::item::{from_item, to_item};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct User {
+//!     username: String,
+//!     age: u32,
+//!     nickname: Option<String>,
+//! }
+//!
+//! # async fn run(client: aws_sdk_dynamodb::Client) -> Result<(), Box<dyn std::error::Error>> {
+//! let user = User { username: "jdoe".into(), age: 33, nickname: None };
+//! client.put_item().table_name("users").set_item(Some(to_item(&user)?)).send().await?;
+//!
+//! let resp = client
+//!     .get_item()
+//!     .table_name("users")
+//!     .key("username", AttributeValue::S("jdoe".into()))
+//!     .send()
+//!     .await?;
+//! let user: User = from_item(resp.item.unwrap_or_default())?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+use aws_sdk_dynamodb::model::AttributeValue;
+use aws_sdk_dynamodb::Blob;
+use serde::de::{
+    self, value::MapDeserializer, value::SeqDeserializer, DeserializeOwned, IntoDeserializer,
+    Visitor,
+};
+use serde::ser::{self, Serialize};
+
+use crate::json::Item;
+
+/// Why a value could not be converted to or from an item.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+/// Serializes a struct or map into an item.
+pub fn to_item<T: Serialize + ?Sized>(value: &T) -> Result<Item, Error> {
+    match to_attribute_value(value)? {
+        AttributeValue::M(item) => Ok(item),
+        other => Err(Error(format!(
+            "an item must serialize as a map, got {:?}",
+            other
+        ))),
+    }
+}
+
+/// Serializes a value into an attribute value.
+pub fn to_attribute_value<T: Serialize + ?Sized>(value: &T) -> Result<AttributeValue, Error> {
+    value.serialize(Serializer)
+}
+
+/// Deserializes an item into a struct or map.
+pub fn from_item<T: DeserializeOwned>(item: Item) -> Result<T, Error> {
+    from_attribute_value(AttributeValue::M(item))
+}
+
+/// Deserializes an attribute value.
+pub fn from_attribute_value<T: DeserializeOwned>(value: AttributeValue) -> Result<T, Error> {
+    T::deserialize(Deserializer(value))
+}
+
+// Nulls are left out of items and maps, so that `None` fields are not stored.
+fn is_null(value: &AttributeValue) -> bool {
+    matches!(value, AttributeValue::Null(_))
+}
+
+fn number(n: impl fmt::Display) -> Result<AttributeValue, Error> {
+    Ok(AttributeValue::N(n.to_string()))
+}
+
+// Formats `n` at its own precision, as an f32 widened to f64 gains digits.
+fn float<F: Into<f64> + fmt::Display + Copy>(n: F) -> Result<AttributeValue, Error> {
+    if n.into().is_finite() {
+        number(n)
+    } else {
+        Err(Error(format!("DynamoDB cannot store the number {}", n)))
+    }
+}
+
+fn tagged(variant: &str, value: AttributeValue) -> AttributeValue {
+    let mut map = HashMap::new();
+    map.insert(variant.to_string(), value);
+    AttributeValue::M(map)
+}
+
+struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = AttributeValue;
+    type Error = Error;
+
+    type SerializeSeq = SerializeList;
+    type SerializeTuple = SerializeList;
+    type SerializeTupleStruct = SerializeList;
+    type SerializeTupleVariant = SerializeList;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeMap;
+    type SerializeStructVariant = SerializeMap;
+
+    fn serialize_bool(self, v: bool) -> Result<AttributeValue, Error> {
+        Ok(AttributeValue::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<AttributeValue, Error> {
+        number(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<AttributeValue, Error> {
+        number(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<AttributeValue, Error> {
+        number(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<AttributeValue, Error> {
+        number(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<AttributeValue, Error> {
+        number(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<AttributeValue, Error> {
+        number(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<AttributeValue, Error> {
+        number(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<AttributeValue, Error> {
+        number(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<AttributeValue, Error> {
+        float(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<AttributeValue, Error> {
+        float(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<AttributeValue, Error> {
+        Ok(AttributeValue::S(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<AttributeValue, Error> {
+        Ok(AttributeValue::S(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<AttributeValue, Error> {
+        Ok(AttributeValue::B(Blob::new(v)))
+    }
+
+    fn serialize_none(self) -> Result<AttributeValue, Error> {
+        Ok(AttributeValue::Null(true))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<AttributeValue, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<AttributeValue, Error> {
+        Ok(AttributeValue::Null(true))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<AttributeValue, Error> {
+        Ok(AttributeValue::Null(true))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<AttributeValue, Error> {
+        Ok(AttributeValue::S(variant.to_string()))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<AttributeValue, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<AttributeValue, Error> {
+        Ok(tagged(variant, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeList, Error> {
+        Ok(SerializeList {
+            variant: None,
+            items: Vec::with_capacity(len.unwrap_or_default()),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeList, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeList, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeList, Error> {
+        Ok(SerializeList {
+            variant: Some(variant),
+            items: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeMap, Error> {
+        Ok(SerializeMap {
+            variant: None,
+            map: HashMap::new(),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeMap, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SerializeMap, Error> {
+        Ok(SerializeMap {
+            variant: Some(variant),
+            map: HashMap::new(),
+            key: None,
+        })
+    }
+}
+
+struct SerializeList {
+    variant: Option<&'static str>,
+    items: Vec<AttributeValue>,
+}
+
+impl SerializeList {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.items.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<AttributeValue, Error> {
+        let list = AttributeValue::L(self.items);
+        Ok(match self.variant {
+            Some(variant) => tagged(variant, list),
+            None => list,
+        })
+    }
+}
+
+impl ser::SerializeSeq for SerializeList {
+    type Ok = AttributeValue;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<AttributeValue, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SerializeList {
+    type Ok = AttributeValue;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<AttributeValue, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeList {
+    type Ok = AttributeValue;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<AttributeValue, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeList {
+    type Ok = AttributeValue;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<AttributeValue, Error> {
+        self.finish()
+    }
+}
+
+struct SerializeMap {
+    variant: Option<&'static str>,
+    map: HashMap<String, AttributeValue>,
+    key: Option<String>,
+}
+
+impl SerializeMap {
+    fn insert<T: Serialize + ?Sized>(&mut self, key: String, value: &T) -> Result<(), Error> {
+        let value = value.serialize(Serializer)?;
+        if !is_null(&value) {
+            self.map.insert(key, value);
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<AttributeValue, Error> {
+        let map = AttributeValue::M(self.map);
+        Ok(match self.variant {
+            Some(variant) => tagged(variant, map),
+            None => map,
+        })
+    }
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = AttributeValue;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(match key.serialize(Serializer)? {
+            AttributeValue::S(s) | AttributeValue::N(s) => s,
+            other => return Err(Error(format!("map keys must be strings, got {:?}", other))),
+        });
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error("map value without a key".to_string()))?;
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<AttributeValue, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for SerializeMap {
+    type Ok = AttributeValue;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.insert(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<AttributeValue, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeMap {
+    type Ok = AttributeValue;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.insert(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<AttributeValue, Error> {
+        self.finish()
+    }
+}
+
+struct Deserializer(AttributeValue);
+
+impl<'de> IntoDeserializer<'de, Error> for Deserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+fn list<'de, V: Visitor<'de>>(
+    items: impl IntoIterator<Item = AttributeValue>,
+    visitor: V,
+) -> Result<V::Value, Error> {
+    let mut seq = SeqDeserializer::new(items.into_iter().map(Deserializer));
+    let value = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+    Ok(value)
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            AttributeValue::S(s) => visitor.visit_string(s),
+            AttributeValue::N(n) => {
+                if let Ok(i) = n.parse::<i64>() {
+                    visitor.visit_i64(i)
+                } else if let Ok(u) = n.parse::<u64>() {
+                    visitor.visit_u64(u)
+                } else if let Ok(f) = n.parse::<f64>() {
+                    visitor.visit_f64(f)
+                } else {
+                    Err(Error(format!("invalid number {}", n)))
+                }
+            }
+            AttributeValue::B(b) => visitor.visit_byte_buf(b.into_inner()),
+            AttributeValue::Bool(b) => visitor.visit_bool(b),
+            AttributeValue::Null(_) => visitor.visit_unit(),
+            AttributeValue::L(l) => list(l, visitor),
+            AttributeValue::Ss(ss) => list(ss.into_iter().map(AttributeValue::S), visitor),
+            AttributeValue::Ns(ns) => list(ns.into_iter().map(AttributeValue::N), visitor),
+            AttributeValue::Bs(bs) => list(bs.into_iter().map(AttributeValue::B), visitor),
+            AttributeValue::M(m) => {
+                let mut map =
+                    MapDeserializer::new(m.into_iter().map(|(k, v)| (k, Deserializer(v))));
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+            other => Err(Error(format!("unsupported attribute value {:?}", other))),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            AttributeValue::Null(_) => visitor.visit_none(),
+            value => visitor.visit_some(Deserializer(value)),
+        }
+    }
+
+    // A number read into a string field keeps its digits.
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            AttributeValue::N(n) => visitor.visit_string(n),
+            value => Deserializer(value).deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.0 {
+            AttributeValue::S(variant) => visitor.visit_enum(variant.into_deserializer()),
+            AttributeValue::M(m) if m.len() == 1 => {
+                let (variant, value) = m.into_iter().next().unwrap();
+                visitor.visit_enum(Enum { variant, value })
+            }
+            other => Err(Error(format!(
+                "an enum must be a string or a map with one key, got {:?}",
+                other
+            ))),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct Enum {
+    variant: String,
+    value: AttributeValue,
+}
+
+impl<'de> de::EnumAccess<'de> for Enum {
+    type Error = Error;
+    type Variant = Deserializer;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Deserializer), Error> {
+        let variant = seed.deserialize(self.variant.into_deserializer())?;
+        Ok((variant, Deserializer(self.value)))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Deserializer {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        de::Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Plan {
+        Free,
+        Paid { seats: u16 },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Address {
+        city: String,
+        zip: Option<String>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct User {
+        username: String,
+        age: u32,
+        confidence: f64,
+        nickname: Option<String>,
+        address: Address,
+        tags: Vec<String>,
+        plan: Plan,
+    }
+
+    fn jdoe() -> User {
+        User {
+            username: "jdoe".into(),
+            age: 33,
+            confidence: 99.5,
+            nickname: None,
+            address: Address {
+                city: "Seattle".into(),
+                zip: Some("98101".into()),
+            },
+            tags: vec!["a".into(), "b".into()],
+            plan: Plan::Paid { seats: 3 },
+        }
+    }
+
+    #[test]
+    fn stores_numbers_as_n_and_omits_none() {
+        let item = to_item(&jdoe()).unwrap();
+
+        assert_eq!(item["age"], AttributeValue::N("33".into()));
+        assert_eq!(item["confidence"], AttributeValue::N("99.5".into()));
+        assert!(!item.contains_key("nickname"));
+        match &item["address"] {
+            AttributeValue::M(address) => {
+                assert_eq!(address["city"], AttributeValue::S("Seattle".into()))
+            }
+            other => panic!("address is {:?}", other),
+        }
+    }
+
+    #[test]
+    fn round_trips_structs() {
+        let user: User = from_item(to_item(&jdoe()).unwrap()).unwrap();
+        assert_eq!(user, jdoe());
+    }
+
+    #[test]
+    fn round_trips_f32_without_extra_digits() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Label {
+            confidence: f32,
+        }
+
+        let label = Label { confidence: 99.87 };
+        let item = to_item(&label).unwrap();
+        assert_eq!(item["confidence"], AttributeValue::N("99.87".into()));
+        assert_eq!(from_item::<Label>(item).unwrap(), label);
+        assert!(to_item(&Label {
+            confidence: f32::NAN
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn reads_sets_and_unit_variants() {
+        let mut item = to_item(&jdoe()).unwrap();
+        item.insert(
+            "tags".into(),
+            AttributeValue::Ss(vec!["x".into(), "y".into()]),
+        );
+        item.insert("plan".into(), AttributeValue::S("Free".into()));
+
+        let user: User = from_item(item).unwrap();
+        assert_eq!(user.tags, ["x", "y"]);
+        assert_eq!(user.plan, Plan::Free);
+    }
+
+    #[test]
+    fn reports_type_mismatches() {
+        let mut item = to_item(&jdoe()).unwrap();
+        item.insert("age".into(), AttributeValue::S("thirty".into()));
+        assert!(from_item::<User>(item).is_err());
+    }
+}
@@ -0,0 +1,223 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Converting between JSON and DynamoDB items.
+//!
+//! There are two JSON forms of an item:
+//!
+//! * Plain JSON, such as `{"year": 2013, "title": "Rush"}`, which
+//!   [`value_to_item`] reads. Numbers become `N`, arrays `L`, and objects `M`.
+//!   Writing an item back as plain JSON with [`attribute_to_value`] loses
+//!   the difference between a set and a list, and between binary and strings.
+//! * DynamoDB JSON, such as `{"year": {"N": "2013"}, "title": {"S": "Rush"}}`,
+//!   which tags every value with its type, as DynamoDB's own exports do.
+//!   [`item_to_typed`] and [`typed_to_item`] convert items to and from it
+//!   without losing anything.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use aws_sdk_dynamodb::model::AttributeValue;
+use aws_sdk_dynamodb::Blob;
+use aws_smithy_types::base64;
+use serde_json::{Map, Value};
+
+/// A DynamoDB item: attribute names and their values.
+pub type Item = HashMap<String, AttributeValue>;
+
+/// A JSON value that does not describe a DynamoDB item.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidItem(String);
+
+impl fmt::Display for InvalidItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid item: {}", self.0)
+    }
+}
+
+impl Error for InvalidItem {}
+
+/// Converts plain JSON into an attribute value.
+pub fn value_to_item(value: Value) -> AttributeValue {
+    match value {
+        Value::Null => AttributeValue::Null(true),
+        Value::Bool(b) => AttributeValue::Bool(b),
+        Value::Number(n) => AttributeValue::N(n.to_string()),
+        Value::String(s) => AttributeValue::S(s),
+        Value::Array(a) => AttributeValue::L(a.into_iter().map(value_to_item).collect()),
+        Value::Object(o) => {
+            AttributeValue::M(o.into_iter().map(|(k, v)| (k, value_to_item(v))).collect())
+        }
+    }
+}
+
+/// Converts a plain JSON object into an item.
+pub fn parse_item(value: Value) -> Result<Item, InvalidItem> {
+    match value_to_item(value) {
+        AttributeValue::M(map) => Ok(map),
+        other => Err(InvalidItem(format!(
+            "can only insert top level objects, got {:?}",
+            other
+        ))),
+    }
+}
+
+/// Converts an attribute value into plain JSON.
+///
+/// Numbers stay strings, so that none lose precision, and binary values are base64.
+pub fn attribute_to_value(value: &AttributeValue) -> Value {
+    match value {
+        AttributeValue::S(s) | AttributeValue::N(s) => s.as_str().into(),
+        AttributeValue::B(b) => base64::encode(b.as_ref()).into(),
+        AttributeValue::Ss(ss) | AttributeValue::Ns(ss) => ss.clone().into(),
+        AttributeValue::Bs(bs) => bs.iter().map(|b| base64::encode(b.as_ref())).collect(),
+        AttributeValue::Bool(b) => (*b).into(),
+        AttributeValue::L(l) => l.iter().map(attribute_to_value).collect(),
+        AttributeValue::M(m) => m
+            .iter()
+            .map(|(k, v)| (k.clone(), attribute_to_value(v)))
+            .collect(),
+        _ => Value::Null,
+    }
+}
+
+/// Converts an item into a plain JSON object.
+pub fn item_to_value(item: &Item) -> Value {
+    item.iter()
+        .map(|(k, v)| (k.clone(), attribute_to_value(v)))
+        .collect()
+}
+
+/// Converts an attribute value into DynamoDB JSON.
+pub fn attribute_to_typed(value: &AttributeValue) -> Value {
+    let (tag, value) = match value {
+        AttributeValue::S(s) => ("S", s.as_str().into()),
+        AttributeValue::N(n) => ("N", n.as_str().into()),
+        AttributeValue::B(b) => ("B", base64::encode(b.as_ref()).into()),
+        AttributeValue::Ss(ss) => ("SS", ss.clone().into()),
+        AttributeValue::Ns(ns) => ("NS", ns.clone().into()),
+        AttributeValue::Bs(bs) => (
+            "BS",
+            bs.iter().map(|b| base64::encode(b.as_ref())).collect(),
+        ),
+        AttributeValue::Bool(b) => ("BOOL", (*b).into()),
+        AttributeValue::L(l) => ("L", l.iter().map(attribute_to_typed).collect()),
+        AttributeValue::M(m) => ("M", item_to_typed(m)),
+        _ => ("NULL", true.into()),
+    };
+    let mut tagged = Map::new();
+    tagged.insert(tag.to_string(), value);
+    Value::Object(tagged)
+}
+
+/// Converts an item into a DynamoDB JSON object.
+pub fn item_to_typed(item: &Item) -> Value {
+    item.iter()
+        .map(|(k, v)| (k.clone(), attribute_to_typed(v)))
+        .collect()
+}
+
+/// Converts DynamoDB JSON, such as `{"N": "42"}`, into an attribute value.
+pub fn typed_to_attribute(value: Value) -> Result<AttributeValue, InvalidItem> {
+    let (tag, value) = match value {
+        Value::Object(o) if o.len() == 1 => o.into_iter().next().unwrap(),
+        other => {
+            return Err(InvalidItem(format!(
+                "expected an object with one type key, got {}",
+                other
+            )))
+        }
+    };
+    let invalid = |value: &Value| InvalidItem(format!("invalid {} value {}", tag, value));
+
+    Ok(match (tag.as_str(), value) {
+        ("S", Value::String(s)) => AttributeValue::S(s),
+        ("N", Value::String(n)) => AttributeValue::N(n),
+        ("B", Value::String(b)) => AttributeValue::B(decode(&b).ok_or_else(|| invalid(&b.into()))?),
+        ("BOOL", Value::Bool(b)) => AttributeValue::Bool(b),
+        ("NULL", Value::Bool(b)) => AttributeValue::Null(b),
+        ("SS", value @ Value::Array(_)) => {
+            AttributeValue::Ss(strings(&value).ok_or_else(|| invalid(&value))?)
+        }
+        ("NS", value @ Value::Array(_)) => {
+            AttributeValue::Ns(strings(&value).ok_or_else(|| invalid(&value))?)
+        }
+        ("BS", value @ Value::Array(_)) => AttributeValue::Bs(
+            strings(&value)
+                .and_then(|bs| bs.iter().map(|b| decode(b)).collect())
+                .ok_or_else(|| invalid(&value))?,
+        ),
+        ("L", Value::Array(l)) => AttributeValue::L(
+            l.into_iter()
+                .map(typed_to_attribute)
+                .collect::<Result<_, _>>()?,
+        ),
+        ("M", value) => AttributeValue::M(typed_to_item(value)?),
+        (_, value) => return Err(invalid(&value)),
+    })
+}
+
+/// Converts a DynamoDB JSON object into an item.
+pub fn typed_to_item(value: Value) -> Result<Item, InvalidItem> {
+    match value {
+        Value::Object(o) => o
+            .into_iter()
+            .map(|(k, v)| Ok((k, typed_to_attribute(v)?)))
+            .collect(),
+        other => Err(InvalidItem(format!("expected an object, got {}", other))),
+    }
+}
+
+fn strings(value: &Value) -> Option<Vec<String>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|s| s.as_str().map(str::to_string))
+        .collect()
+}
+
+fn decode(b: &str) -> Option<Blob> {
+    base64::decode(b).ok().map(Blob::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn typed_json_round_trips_every_type() {
+        let typed = json!({
+            "title": {"S": "Rush"},
+            "year": {"N": "2013"},
+            "poster": {"B": "AAEC"},
+            "genres": {"SS": ["Action", "Drama"]},
+            "ratings": {"NS": ["7.2", "8.1"]},
+            "thumbnails": {"BS": ["AQ==", "Ag=="]},
+            "released": {"BOOL": true},
+            "sequel": {"NULL": true},
+            "info": {"M": {
+                "actors": {"L": [{"S": "Daniel Bruhl"}, {"N": "1"}]}
+            }}
+        });
+
+        let item = typed_to_item(typed.clone()).unwrap();
+
+        assert_eq!(item["poster"], AttributeValue::B(Blob::new(vec![0, 1, 2])));
+        assert_eq!(
+            item["genres"],
+            AttributeValue::Ss(vec!["Action".into(), "Drama".into()])
+        );
+        assert_eq!(item_to_typed(&item), typed);
+    }
+
+    #[test]
+    fn rejects_untyped_values() {
+        assert!(typed_to_item(json!({"title": "Rush"})).is_err());
+        assert!(typed_to_item(json!({"year": {"N": 2013}})).is_err());
+        assert!(typed_to_item(json!({"poster": {"B": "not base64!"}})).is_err());
+    }
+}
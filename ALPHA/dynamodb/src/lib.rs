@@ -0,0 +1,14 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Helpers shared by the DynamoDB code examples.
+
+pub mod expression;
+pub mod item;
+pub mod json;
+pub mod scan;
+pub mod schema;
+pub mod stream;
+pub mod transaction;
@@ -0,0 +1,187 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Scanning a whole table in parallel segments, optionally filtered,
+//! projected, and held to a read capacity budget.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use aws_sdk_dynamodb::error::ScanError;
+use aws_sdk_dynamodb::model::{AttributeValue, ReturnConsumedCapacity};
+use aws_sdk_dynamodb::{Client, SdkError};
+use futures_util::stream::{self, BoxStream, StreamExt};
+use serde_json::Value;
+use structopt::StructOpt;
+use tokio::time::Instant;
+
+use crate::json::{value_to_item, Item};
+
+/// Command-line options for a scan.
+#[derive(Clone, Debug, Default, StructOpt)]
+pub struct ScanOpt {
+    /// The number of segments to scan in parallel.
+    #[structopt(long, default_value = "1")]
+    pub segments: i32,
+
+    /// The most read capacity units to consume per second, across all segments.
+    /// Unlimited if not supplied.
+    #[structopt(long)]
+    pub max_rcu: Option<f64>,
+
+    /// A filter expression, such as "age > :min". Use --name and --value
+    /// to define its placeholders.
+    #[structopt(long)]
+    pub filter: Option<String>,
+
+    /// An attribute name placeholder for the filter, as #NAME=ATTRIBUTE.
+    #[structopt(long = "name", number_of_values = 1, parse(try_from_str = parse_name))]
+    pub names: Vec<(String, String)>,
+
+    /// A value placeholder for the filter, as :NAME=VALUE. A VALUE that is
+    /// JSON, such as 40 or true, is read as JSON; anything else is a string.
+    #[structopt(long = "value", number_of_values = 1, parse(try_from_str = parse_value))]
+    pub values: Vec<(String, AttributeValue)>,
+
+    /// The attributes to return, separated by commas. Returns every attribute if not supplied.
+    #[structopt(long, use_delimiter = true)]
+    pub projection: Vec<String>,
+}
+
+fn parse_name(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((name, attribute)) if name.starts_with('#') => {
+            Ok((name.to_string(), attribute.to_string()))
+        }
+        _ => Err(format!("expected #NAME=ATTRIBUTE, got {}", s)),
+    }
+}
+
+fn parse_value(s: &str) -> Result<(String, AttributeValue), String> {
+    match s.split_once('=') {
+        Some((name, value)) if name.starts_with(':') => {
+            let value = serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.into()));
+            Ok((name.to_string(), value_to_item(value)))
+        }
+        _ => Err(format!("expected :NAME=VALUE, got {}", s)),
+    }
+}
+
+/// Spreads requests out so that, on average, they consume no more than a
+/// given number of capacity units a second.
+///
+/// Clones share the budget, so one limit can pace several segments.
+#[derive(Clone, Debug)]
+pub struct CapacityLimit {
+    per_second: f64,
+    next: Arc<Mutex<Instant>>,
+}
+
+impl CapacityLimit {
+    /// Creates a limit of `per_second` capacity units a second.
+    pub fn new(per_second: f64) -> Self {
+        CapacityLimit {
+            per_second,
+            next: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    /// Waits until the capacity already consumed has been paid off.
+    pub async fn ready(&self) {
+        let next = *self.next.lock().expect("limit lock is never poisoned");
+        tokio::time::sleep_until(next).await;
+    }
+
+    /// Records that a request consumed `units` capacity units.
+    pub fn consumed(&self, units: f64) {
+        let mut next = self.next.lock().expect("limit lock is never poisoned");
+        *next = (*next).max(Instant::now()) + Duration::from_secs_f64(units / self.per_second);
+    }
+}
+
+/// Scans every segment of `table` at once, yielding items as they arrive
+/// from any segment. `pages` sets the page size, and the most items to return.
+pub fn parallel_scan(
+    client: &Client,
+    table: &str,
+    scan: &ScanOpt,
+    pages: &PageOpt,
+) -> BoxStream<'static, Result<Item, SdkError<ScanError>>> {
+    let segments = scan.segments.max(1);
+    let limit = scan.max_rcu.map(CapacityLimit::new);
+
+    let mut names: HashMap<String, String> = scan.names.iter().cloned().collect();
+    // The projected attributes get placeholders, so reserved words such as
+    // "name" and "year" can be projected.
+    let projection = if scan.projection.is_empty() {
+        None
+    } else {
+        let placeholders: Vec<String> = scan
+            .projection
+            .iter()
+            .enumerate()
+            .map(|(i, attribute)| {
+                let placeholder = format!("#p{}", i);
+                names.insert(placeholder.clone(), attribute.clone());
+                placeholder
+            })
+            .collect();
+        Some(placeholders.join(", "))
+    };
+    let names = Some(names).filter(|names| !names.is_empty());
+    let values: HashMap<String, AttributeValue> = scan.values.iter().cloned().collect();
+    let values = Some(values).filter(|values| !values.is_empty());
+
+    let scans = (0..segments).map(|segment| {
+        let client = client.clone();
+        let table = table.to_string();
+        let filter = scan.filter.clone();
+        let projection = projection.clone();
+        let names = names.clone();
+        let values = values.clone();
+        let limit = limit.clone();
+        paginate(pages, move |start, page_size| {
+            let request = client
+                .scan()
+                .table_name(&table)
+                .set_filter_expression(filter.clone())
+                .set_projection_expression(projection.clone())
+                .set_expression_attribute_names(names.clone())
+                .set_expression_attribute_values(values.clone())
+                .set_return_consumed_capacity(limit.as_ref().map(|_| ReturnConsumedCapacity::Total))
+                .segment(segment)
+                .total_segments(segments)
+                .set_exclusive_start_key(start)
+                .set_limit(page_size);
+            let limit = limit.clone();
+            async move {
+                if let Some(limit) = &limit {
+                    limit.ready().await;
+                }
+                let resp = request.send().await?;
+                if let Some(limit) = &limit {
+                    limit.consumed(
+                        resp.consumed_capacity
+                            .as_ref()
+                            .and_then(|c| c.capacity_units)
+                            .unwrap_or_default(),
+                    );
+                }
+                Ok(Page::new(
+                    resp.items.unwrap_or_default(),
+                    resp.last_evaluated_key,
+                ))
+            }
+        })
+    });
+
+    let items = stream::select_all(scans);
+    match pages.max_items {
+        Some(max) => items.take(max).boxed(),
+        None => items.boxed(),
+    }
+}
@@ -0,0 +1,806 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Describing a table in a YAML or JSON file, and working out what has to
+//! change to make a table match it.
+//!
+//! A spec looks like this; everything but `name` and `hash_key` is optional:
+//!
+//! ```yaml
+//! name: movies
+//! hash_key: { name: year, type: N }
+//! range_key: { name: title, type: S }
+//! billing: { provisioned: { read: 10, write: 5 } }  # or pay_per_request, the default
+//! global_secondary_indexes:
+//!   - name: by-title
+//!     hash_key: { name: title, type: S }
+//!     projection: { include: [rating] }            # or all, the default, or keys_only
+//!     throughput: { read: 5, write: 5 }            # defaults to the table's
+//! local_secondary_indexes:
+//!   - name: by-rating
+//!     range_key: { name: rating, type: N }
+//! ttl: expires_at
+//! stream: new_and_old_images
+//! ```
+//!
+//! [`plan`] compares a spec with what `DescribeTable` and
+//! `DescribeTimeToLive` return, and lists the [`Change`]s to make.
+
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+use std::fmt;
+
+use aws_sdk_dynamodb::model::{
+    AttributeDefinition, BillingMode, GlobalSecondaryIndex, KeySchemaElement, KeyType,
+    LocalSecondaryIndex, Projection as SdkProjection, ProjectionType, ProvisionedThroughput,
+    ScalarAttributeType, StreamSpecification, StreamViewType, TableDescription,
+    TimeToLiveDescription, TimeToLiveStatus,
+};
+use serde::Deserialize;
+
+/// A spec that cannot be read, or that the table cannot be changed to match.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpecError(String);
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid table spec: {}", self.0)
+    }
+}
+
+impl Error for SpecError {}
+
+/// The type of a key attribute.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AttributeType {
+    S,
+    N,
+    B,
+}
+
+impl AttributeType {
+    fn to_sdk(self) -> ScalarAttributeType {
+        match self {
+            AttributeType::S => ScalarAttributeType::S,
+            AttributeType::N => ScalarAttributeType::N,
+            AttributeType::B => ScalarAttributeType::B,
+        }
+    }
+
+    fn from_sdk(t: &ScalarAttributeType) -> Option<Self> {
+        match t {
+            ScalarAttributeType::S => Some(AttributeType::S),
+            ScalarAttributeType::N => Some(AttributeType::N),
+            ScalarAttributeType::B => Some(AttributeType::B),
+            _ => None,
+        }
+    }
+}
+
+/// A key attribute of a table or index.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct KeySpec {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub attribute_type: AttributeType,
+}
+
+impl fmt::Display for KeySpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:?}", self.name, self.attribute_type)
+    }
+}
+
+/// Read and write capacity units.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+pub struct Throughput {
+    pub read: i64,
+    pub write: i64,
+}
+
+impl Throughput {
+    fn to_sdk(self) -> ProvisionedThroughput {
+        ProvisionedThroughput::builder()
+            .read_capacity_units(self.read)
+            .write_capacity_units(self.write)
+            .build()
+    }
+}
+
+/// How the table is paid for.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Billing {
+    #[default]
+    PayPerRequest,
+    Provisioned(Throughput),
+}
+
+impl fmt::Display for Billing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Billing::PayPerRequest => f.write_str("pay per request"),
+            Billing::Provisioned(t) => {
+                write!(f, "provisioned (read {}, write {})", t.read, t.write)
+            }
+        }
+    }
+}
+
+/// Which attributes an index copies from the table.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Projection {
+    #[default]
+    All,
+    KeysOnly,
+    Include(BTreeSet<String>),
+}
+
+impl Projection {
+    fn to_sdk(&self) -> SdkProjection {
+        let builder = SdkProjection::builder();
+        match self {
+            Projection::All => builder.projection_type(ProjectionType::All),
+            Projection::KeysOnly => builder.projection_type(ProjectionType::KeysOnly),
+            Projection::Include(names) => builder
+                .projection_type(ProjectionType::Include)
+                .set_non_key_attributes(Some(names.iter().cloned().collect())),
+        }
+        .build()
+    }
+
+    fn from_sdk(projection: Option<&SdkProjection>) -> Option<Self> {
+        let projection = projection?;
+        match projection.projection_type.as_ref()? {
+            ProjectionType::All => Some(Projection::All),
+            ProjectionType::KeysOnly => Some(Projection::KeysOnly),
+            ProjectionType::Include => Some(Projection::Include(
+                projection
+                    .non_key_attributes
+                    .iter()
+                    .flatten()
+                    .cloned()
+                    .collect(),
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// A global secondary index.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub struct GlobalIndexSpec {
+    pub name: String,
+    pub hash_key: KeySpec,
+    pub range_key: Option<KeySpec>,
+    #[serde(default)]
+    pub projection: Projection,
+    /// The index's throughput on a provisioned table. Defaults to the table's.
+    pub throughput: Option<Throughput>,
+}
+
+impl fmt::Display for GlobalIndexSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}", self.name, self.hash_key)?;
+        if let Some(range_key) = &self.range_key {
+            write!(f, ", {}", range_key)?;
+        }
+        f.write_str(")")
+    }
+}
+
+/// A local secondary index, which shares the table's hash key.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub struct LocalIndexSpec {
+    pub name: String,
+    pub range_key: KeySpec,
+    #[serde(default)]
+    pub projection: Projection,
+}
+
+/// What is recorded in the table's stream.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamView {
+    KeysOnly,
+    NewImage,
+    OldImage,
+    NewAndOldImages,
+}
+
+impl StreamView {
+    fn to_sdk(self) -> StreamViewType {
+        match self {
+            StreamView::KeysOnly => StreamViewType::KeysOnly,
+            StreamView::NewImage => StreamViewType::NewImage,
+            StreamView::OldImage => StreamViewType::OldImage,
+            StreamView::NewAndOldImages => StreamViewType::NewAndOldImages,
+        }
+    }
+
+    fn from_sdk(view: &StreamViewType) -> Option<Self> {
+        match view {
+            StreamViewType::KeysOnly => Some(StreamView::KeysOnly),
+            StreamViewType::NewImage => Some(StreamView::NewImage),
+            StreamViewType::OldImage => Some(StreamView::OldImage),
+            StreamViewType::NewAndOldImages => Some(StreamView::NewAndOldImages),
+            _ => None,
+        }
+    }
+}
+
+/// A table as described in a spec file.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TableSpec {
+    pub name: String,
+    pub hash_key: KeySpec,
+    pub range_key: Option<KeySpec>,
+    #[serde(default)]
+    pub billing: Billing,
+    #[serde(default)]
+    pub global_secondary_indexes: Vec<GlobalIndexSpec>,
+    #[serde(default)]
+    pub local_secondary_indexes: Vec<LocalIndexSpec>,
+    /// The attribute holding each item's expiry time.
+    pub ttl: Option<String>,
+    pub stream: Option<StreamView>,
+}
+
+fn key_schema(hash_key: &KeySpec, range_key: Option<&KeySpec>) -> Vec<KeySchemaElement> {
+    let element = |key: &KeySpec, key_type| {
+        KeySchemaElement::builder()
+            .attribute_name(&key.name)
+            .key_type(key_type)
+            .build()
+    };
+    std::iter::once(element(hash_key, KeyType::Hash))
+        .chain(range_key.map(|key| element(key, KeyType::Range)))
+        .collect()
+}
+
+fn definitions<'a>(keys: impl IntoIterator<Item = &'a KeySpec>) -> Vec<AttributeDefinition> {
+    let mut seen = BTreeSet::new();
+    keys.into_iter()
+        .filter(|key| seen.insert(&key.name))
+        .map(|key| {
+            AttributeDefinition::builder()
+                .attribute_name(&key.name)
+                .attribute_type(key.attribute_type.to_sdk())
+                .build()
+        })
+        .collect()
+}
+
+impl TableSpec {
+    /// Reads a spec from YAML, which includes JSON, and checks that it is
+    /// one DynamoDB would accept.
+    pub fn from_yaml(yaml: &str) -> Result<Self, SpecError> {
+        let spec: TableSpec = serde_yaml::from_str(yaml).map_err(|e| SpecError(e.to_string()))?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    fn validate(&self) -> Result<(), SpecError> {
+        let mut types = HashMap::new();
+        for key in self.keys() {
+            if *types.entry(&key.name).or_insert(key.attribute_type) != key.attribute_type {
+                return Err(SpecError(format!(
+                    "attribute {} is given more than one type",
+                    key.name
+                )));
+            }
+        }
+
+        let mut names = BTreeSet::new();
+        let index_names = self
+            .global_secondary_indexes
+            .iter()
+            .map(|index| &index.name)
+            .chain(self.local_secondary_indexes.iter().map(|index| &index.name));
+        for name in index_names {
+            if !names.insert(name) {
+                return Err(SpecError(format!("index {} is given more than once", name)));
+            }
+        }
+
+        if self.range_key.is_none() && !self.local_secondary_indexes.is_empty() {
+            return Err(SpecError(
+                "local secondary indexes need the table to have a range key".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Every key attribute of the table and its indexes.
+    fn keys(&self) -> impl Iterator<Item = &KeySpec> {
+        std::iter::once(&self.hash_key)
+            .chain(&self.range_key)
+            .chain(
+                self.global_secondary_indexes
+                    .iter()
+                    .flat_map(|index| std::iter::once(&index.hash_key).chain(&index.range_key)),
+            )
+            .chain(
+                self.local_secondary_indexes
+                    .iter()
+                    .map(|index| &index.range_key),
+            )
+    }
+
+    /// The table's key schema.
+    pub fn key_schema(&self) -> Vec<KeySchemaElement> {
+        key_schema(&self.hash_key, self.range_key.as_ref())
+    }
+
+    /// The definitions of every key attribute of the table and its indexes.
+    pub fn attribute_definitions(&self) -> Vec<AttributeDefinition> {
+        definitions(self.keys())
+    }
+
+    /// The definitions of the table's key attributes and those of `index`,
+    /// which `UpdateTable` needs to create the index.
+    pub fn index_attribute_definitions(&self, index: &GlobalIndexSpec) -> Vec<AttributeDefinition> {
+        definitions(
+            std::iter::once(&self.hash_key)
+                .chain(&self.range_key)
+                .chain(std::iter::once(&index.hash_key))
+                .chain(&index.range_key),
+        )
+    }
+
+    /// The throughput of an index, or `None` on a pay-per-request table.
+    pub fn index_throughput(&self, index: &GlobalIndexSpec) -> Option<Throughput> {
+        match self.billing {
+            Billing::PayPerRequest => None,
+            Billing::Provisioned(table) => Some(index.throughput.unwrap_or(table)),
+        }
+    }
+
+    /// A global secondary index, as `CreateTable` and `UpdateTable` take it.
+    pub fn global_index(&self, index: &GlobalIndexSpec) -> GlobalSecondaryIndex {
+        GlobalSecondaryIndex::builder()
+            .index_name(&index.name)
+            .set_key_schema(Some(key_schema(&index.hash_key, index.range_key.as_ref())))
+            .projection(index.projection.to_sdk())
+            .set_provisioned_throughput(self.index_throughput(index).map(Throughput::to_sdk))
+            .build()
+    }
+
+    /// The local secondary indexes, as `CreateTable` takes them.
+    pub fn local_indexes(&self) -> Option<Vec<LocalSecondaryIndex>> {
+        if self.local_secondary_indexes.is_empty() {
+            return None;
+        }
+        let indexes = self
+            .local_secondary_indexes
+            .iter()
+            .map(|index| {
+                LocalSecondaryIndex::builder()
+                    .index_name(&index.name)
+                    .set_key_schema(Some(key_schema(&self.hash_key, Some(&index.range_key))))
+                    .projection(index.projection.to_sdk())
+                    .build()
+            })
+            .collect();
+        Some(indexes)
+    }
+
+    /// The billing mode and table throughput, as `CreateTable` and `UpdateTable` take them.
+    pub fn billing_mode(&self) -> (BillingMode, Option<ProvisionedThroughput>) {
+        match self.billing {
+            Billing::PayPerRequest => (BillingMode::PayPerRequest, None),
+            Billing::Provisioned(t) => (BillingMode::Provisioned, Some(t.to_sdk())),
+        }
+    }
+}
+
+/// The stream settings that turn the stream on with `view`, or off.
+pub fn stream_specification(view: Option<StreamView>) -> StreamSpecification {
+    StreamSpecification::builder()
+        .stream_enabled(view.is_some())
+        .set_stream_view_type(view.map(StreamView::to_sdk))
+        .build()
+}
+
+/// One step in making a table match its spec.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Change {
+    /// Create the table, with its indexes and stream.
+    CreateTable,
+    /// Change the billing mode or the table's throughput.
+    SetBilling(Billing),
+    /// Delete a global secondary index.
+    DeleteIndex(String),
+    /// Create a global secondary index.
+    CreateIndex(GlobalIndexSpec),
+    /// Change the throughput of a global secondary index.
+    SetIndexThroughput(String, Throughput),
+    /// Turn the stream on with the given view, or off.
+    SetStream(Option<StreamView>),
+    /// Turn time to live on for the given attribute, or off.
+    SetTtl(Option<String>),
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::CreateTable => f.write_str("+ create table"),
+            Change::SetBilling(billing) => write!(f, "~ billing: {}", billing),
+            Change::DeleteIndex(name) => write!(f, "- index {}", name),
+            Change::CreateIndex(index) => write!(f, "+ index {}", index),
+            Change::SetIndexThroughput(name, t) => write!(
+                f,
+                "~ index {} throughput: read {}, write {}",
+                name, t.read, t.write
+            ),
+            Change::SetStream(Some(view)) => write!(f, "~ stream: {:?}", view),
+            Change::SetStream(None) => f.write_str("~ stream: off"),
+            Change::SetTtl(Some(attribute)) => write!(f, "~ ttl: {}", attribute),
+            Change::SetTtl(None) => f.write_str("~ ttl: off"),
+        }
+    }
+}
+
+/// The hash and range keys of a key schema, with their types looked up in `definitions`.
+fn described_keys(
+    schema: Option<&[KeySchemaElement]>,
+    definitions: &[AttributeDefinition],
+) -> (Option<KeySpec>, Option<KeySpec>) {
+    let key = |key_type: KeyType| {
+        let name = schema?
+            .iter()
+            .find(|element| element.key_type.as_ref() == Some(&key_type))?
+            .attribute_name
+            .clone()?;
+        let attribute_type = definitions
+            .iter()
+            .find(|d| d.attribute_name.as_deref() == Some(&name))?
+            .attribute_type
+            .as_ref()
+            .and_then(AttributeType::from_sdk)?;
+        Some(KeySpec {
+            name,
+            attribute_type,
+        })
+    };
+    (key(KeyType::Hash), key(KeyType::Range))
+}
+
+fn described_throughput(
+    t: Option<&aws_sdk_dynamodb::model::ProvisionedThroughputDescription>,
+) -> Option<Throughput> {
+    let t = t?;
+    Some(Throughput {
+        read: t.read_capacity_units?,
+        write: t.write_capacity_units?,
+    })
+}
+
+/// Lists the changes that make `table`, as `DescribeTable` returned it,
+/// match `spec`; `table` is `None` if it does not exist yet. `ttl` is what
+/// `DescribeTimeToLive` returned.
+///
+/// Fails if the table's keys or local secondary indexes differ, as those
+/// can only be set when a table is created, or if TTL is on for another
+/// attribute, as DynamoDB won't turn it back on for about an hour after it is turned off.
+pub fn plan(
+    spec: &TableSpec,
+    table: Option<&TableDescription>,
+    ttl: Option<&TimeToLiveDescription>,
+) -> Result<Vec<Change>, SpecError> {
+    let mut changes = Vec::new();
+
+    let table = match table {
+        Some(table) => table,
+        None => {
+            changes.push(Change::CreateTable);
+            if let Some(attribute) = &spec.ttl {
+                changes.push(Change::SetTtl(Some(attribute.clone())));
+            }
+            return Ok(changes);
+        }
+    };
+    let definitions = table.attribute_definitions.as_deref().unwrap_or_default();
+
+    let (hash_key, range_key) = described_keys(table.key_schema.as_deref(), definitions);
+    if hash_key.as_ref() != Some(&spec.hash_key) || range_key != spec.range_key {
+        return Err(SpecError(format!(
+            "the keys of table {} differ from the spec, and can only be set when the table is created",
+            spec.name
+        )));
+    }
+
+    let local_indexes: BTreeSet<_> = table
+        .local_secondary_indexes
+        .iter()
+        .flatten()
+        .map(|index| {
+            let (_, range_key) = described_keys(index.key_schema.as_deref(), definitions);
+            (
+                index.index_name.clone(),
+                range_key,
+                Projection::from_sdk(index.projection.as_ref()),
+            )
+        })
+        .collect();
+    let wanted: BTreeSet<_> = spec
+        .local_secondary_indexes
+        .iter()
+        .map(|index| {
+            (
+                Some(index.name.clone()),
+                Some(index.range_key.clone()),
+                Some(index.projection.clone()),
+            )
+        })
+        .collect();
+    if local_indexes != wanted {
+        return Err(SpecError(format!(
+            "the local secondary indexes of table {} differ from the spec, and can only be set when the table is created",
+            spec.name
+        )));
+    }
+
+    // Tables created before on-demand billing have no billing mode summary.
+    let billing = match table
+        .billing_mode_summary
+        .as_ref()
+        .and_then(|s| s.billing_mode.as_ref())
+    {
+        Some(BillingMode::PayPerRequest) => Some(Billing::PayPerRequest),
+        _ => described_throughput(table.provisioned_throughput.as_ref()).map(Billing::Provisioned),
+    };
+    let billing_changes = billing != Some(spec.billing);
+
+    let current: Vec<_> = table.global_secondary_indexes.iter().flatten().collect();
+    let mut creates = Vec::new();
+    for index in &current {
+        let name = index.index_name.clone().unwrap_or_default();
+        let (hash_key, range_key) = described_keys(index.key_schema.as_deref(), definitions);
+        match spec
+            .global_secondary_indexes
+            .iter()
+            .find(|i| i.name == name)
+        {
+            Some(wanted)
+                if hash_key.as_ref() == Some(&wanted.hash_key)
+                    && range_key == wanted.range_key
+                    && Projection::from_sdk(index.projection.as_ref()).as_ref()
+                        == Some(&wanted.projection) =>
+            {
+                // Switching to provisioned billing sets every index's throughput too.
+                match spec.index_throughput(wanted) {
+                    Some(throughput)
+                        if !billing_changes
+                            && described_throughput(index.provisioned_throughput.as_ref())
+                                != Some(throughput) =>
+                    {
+                        changes.push(Change::SetIndexThroughput(name, throughput))
+                    }
+                    _ => (),
+                }
+            }
+            // An index's keys and projection cannot change, so it is rebuilt.
+            Some(wanted) => {
+                changes.push(Change::DeleteIndex(name));
+                creates.push(Change::CreateIndex(wanted.clone()));
+            }
+            None => changes.push(Change::DeleteIndex(name)),
+        }
+    }
+    for index in &spec.global_secondary_indexes {
+        if !current
+            .iter()
+            .any(|i| i.index_name.as_deref() == Some(&index.name))
+        {
+            creates.push(Change::CreateIndex(index.clone()));
+        }
+    }
+
+    // Deleting indexes first makes switching to provisioned billing cheaper,
+    // and creating them last gives them the new billing mode.
+    changes.sort_by_key(|change| !matches!(change, Change::DeleteIndex(_)));
+    if billing_changes {
+        let at = changes
+            .iter()
+            .take_while(|change| matches!(change, Change::DeleteIndex(_)))
+            .count();
+        changes.insert(at, Change::SetBilling(spec.billing));
+    }
+    changes.extend(creates);
+
+    let stream = table
+        .stream_specification
+        .as_ref()
+        .filter(|s| s.stream_enabled == Some(true))
+        .and_then(|s| s.stream_view_type.as_ref())
+        .and_then(StreamView::from_sdk);
+    if stream != spec.stream {
+        // The view of an enabled stream cannot change; it is turned off first.
+        if stream.is_some() && spec.stream.is_some() {
+            changes.push(Change::SetStream(None));
+        }
+        changes.push(Change::SetStream(spec.stream));
+    }
+
+    let ttl = ttl
+        .filter(|ttl| {
+            matches!(
+                ttl.time_to_live_status,
+                Some(TimeToLiveStatus::Enabled) | Some(TimeToLiveStatus::Enabling)
+            )
+        })
+        .and_then(|ttl| ttl.attribute_name.clone());
+    if ttl != spec.ttl {
+        if let (Some(current), Some(wanted)) = (&ttl, &spec.ttl) {
+            return Err(SpecError(format!(
+                "TTL on table {} is on for {}, not {}; remove `ttl` from the spec and apply it to turn TTL off, then set `ttl: {}` and apply it again in an hour or so, once DynamoDB allows TTL back on",
+                spec.name, current, wanted, wanted
+            )));
+        }
+        changes.push(Change::SetTtl(spec.ttl.clone()));
+    }
+
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_dynamodb::model::{
+        BillingModeSummary, GlobalSecondaryIndexDescription, ProvisionedThroughputDescription,
+    };
+
+    const MOVIES: &str = "
+name: movies
+hash_key: { name: year, type: N }
+range_key: { name: title, type: S }
+billing: { provisioned: { read: 10, write: 5 } }
+global_secondary_indexes:
+  - name: by-title
+    hash_key: { name: title, type: S }
+    projection: { include: [rating] }
+ttl: expires_at
+stream: new_and_old_images
+";
+
+    fn key(name: &str, key_type: KeyType) -> KeySchemaElement {
+        KeySchemaElement::builder()
+            .attribute_name(name)
+            .key_type(key_type)
+            .build()
+    }
+
+    fn definition(name: &str, t: ScalarAttributeType) -> AttributeDefinition {
+        AttributeDefinition::builder()
+            .attribute_name(name)
+            .attribute_type(t)
+            .build()
+    }
+
+    fn throughput(read: i64, write: i64) -> ProvisionedThroughputDescription {
+        ProvisionedThroughputDescription::builder()
+            .read_capacity_units(read)
+            .write_capacity_units(write)
+            .build()
+    }
+
+    // The movies table as it was first created, with on-demand billing,
+    // an index on genre, and no stream.
+    fn described_movies() -> TableDescription {
+        TableDescription::builder()
+            .table_name("movies")
+            .key_schema(key("year", KeyType::Hash))
+            .key_schema(key("title", KeyType::Range))
+            .attribute_definitions(definition("year", ScalarAttributeType::N))
+            .attribute_definitions(definition("title", ScalarAttributeType::S))
+            .attribute_definitions(definition("genre", ScalarAttributeType::S))
+            .billing_mode_summary(
+                BillingModeSummary::builder()
+                    .billing_mode(BillingMode::PayPerRequest)
+                    .build(),
+            )
+            .provisioned_throughput(throughput(0, 0))
+            .global_secondary_indexes(
+                GlobalSecondaryIndexDescription::builder()
+                    .index_name("by-genre")
+                    .key_schema(key("genre", KeyType::Hash))
+                    .projection(Projection::All.to_sdk())
+                    .build(),
+            )
+            .build()
+    }
+
+    #[test]
+    fn new_table_is_created() {
+        let spec = TableSpec::from_yaml(MOVIES).unwrap();
+        assert_eq!(
+            spec.billing,
+            Billing::Provisioned(Throughput { read: 10, write: 5 })
+        );
+        assert_eq!(
+            spec.global_secondary_indexes[0].projection,
+            Projection::Include(std::iter::once("rating".to_string()).collect())
+        );
+        assert_eq!(spec.attribute_definitions().len(), 2);
+
+        assert_eq!(
+            plan(&spec, None, None).unwrap(),
+            vec![
+                Change::CreateTable,
+                Change::SetTtl(Some("expires_at".into()))
+            ]
+        );
+    }
+
+    #[test]
+    fn existing_table_is_updated() {
+        let spec = TableSpec::from_yaml(MOVIES).unwrap();
+        let changes = plan(&spec, Some(&described_movies()), None).unwrap();
+
+        assert_eq!(
+            changes,
+            vec![
+                Change::DeleteIndex("by-genre".into()),
+                Change::SetBilling(spec.billing),
+                Change::CreateIndex(spec.global_secondary_indexes[0].clone()),
+                Change::SetStream(Some(StreamView::NewAndOldImages)),
+                Change::SetTtl(Some("expires_at".into())),
+            ]
+        );
+        assert_eq!(changes[2].to_string(), "+ index by-title (title S)");
+
+        // Once applied, there is nothing left to do.
+        let index = GlobalSecondaryIndexDescription::builder()
+            .index_name("by-title")
+            .key_schema(key("title", KeyType::Hash))
+            .projection(spec.global_secondary_indexes[0].projection.to_sdk())
+            .provisioned_throughput(throughput(10, 5))
+            .build();
+        let table = TableDescription::builder()
+            .set_key_schema(Some(spec.key_schema()))
+            .set_attribute_definitions(Some(spec.attribute_definitions()))
+            .provisioned_throughput(throughput(10, 5))
+            .global_secondary_indexes(index)
+            .stream_specification(stream_specification(spec.stream))
+            .build();
+        let ttl = TimeToLiveDescription::builder()
+            .time_to_live_status(TimeToLiveStatus::Enabled)
+            .attribute_name("expires_at")
+            .build();
+        assert_eq!(plan(&spec, Some(&table), Some(&ttl)).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn ttl_attribute_cannot_change_in_one_apply() {
+        let spec = TableSpec::from_yaml(MOVIES).unwrap();
+        let ttl = TimeToLiveDescription::builder()
+            .time_to_live_status(TimeToLiveStatus::Enabled)
+            .attribute_name("deleted_at")
+            .build();
+        let e = plan(&spec, Some(&described_movies()), Some(&ttl)).unwrap_err();
+        assert!(e.to_string().contains("is on for deleted_at"), "{}", e);
+
+        let off = TableSpec::from_yaml(&MOVIES.replace("ttl: expires_at", "")).unwrap();
+        let changes = plan(&off, Some(&described_movies()), Some(&ttl)).unwrap();
+        assert_eq!(changes.last(), Some(&Change::SetTtl(None)));
+    }
+
+    #[test]
+    fn keys_cannot_change() {
+        let spec = TableSpec::from_yaml(
+            r#"{"name": "movies", "hash_key": {"name": "year", "type": "S"}}"#,
+        )
+        .unwrap();
+        assert!(plan(&spec, Some(&described_movies()), None).is_err());
+
+        assert!(TableSpec::from_yaml(
+            "{name: movies, hash_key: {name: year, type: N}, local_secondary_indexes: [{name: by-rating, range_key: {name: rating, type: N}}]}"
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,265 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Reading a table's stream shard by shard, and remembering how far it was read.
+//!
+//! A stream is split into shards, each of which holds the changes to part of
+//! the table for a few hours before it is closed and replaced by one or more
+//! child shards. Reading a child before its parent is finished would print
+//! changes out of order, so [`ready_shards`] only offers a shard once its
+//! parent has been read to the end, or has been trimmed from the stream.
+//!
+//! A [`Checkpoint`] records the last sequence number read from each shard,
+//! and which shards were read to the end, so that a reader can resume.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use aws_sdk_dynamodb::model::AttributeValue;
+use aws_sdk_dynamodbstreams::model::{
+    AttributeValue as StreamAttributeValue, Record, Shard, ShardIteratorType,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::json::{item_to_value, Item};
+
+/// How far a stream has been read.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct Checkpoint {
+    /// The stream this checkpoint belongs to.
+    pub stream_arn: String,
+    /// The last sequence number read from each shard that is still being read.
+    #[serde(default)]
+    pub positions: BTreeMap<String, String>,
+    /// The shards that have been read to the end.
+    #[serde(default)]
+    pub finished: BTreeSet<String>,
+}
+
+impl Checkpoint {
+    /// An empty checkpoint for the stream `stream_arn`.
+    pub fn new(stream_arn: &str) -> Self {
+        Checkpoint {
+            stream_arn: stream_arn.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Reads the checkpoint for `stream_arn` saved at `path`.
+    ///
+    /// Returns `None` if there is no file, or if it belongs to another
+    /// stream, such as the one before the table's stream was turned off and on.
+    pub fn load(path: &Path, stream_arn: &str) -> io::Result<Option<Self>> {
+        let json = match fs::read(path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let checkpoint: Checkpoint = serde_json::from_slice(&json)?;
+        Ok(Some(checkpoint).filter(|c| c.stream_arn == stream_arn))
+    }
+
+    /// Saves the checkpoint to `path`.
+    ///
+    /// Writes a new file and renames it over the old one, so that a reader
+    /// stopped partway through never leaves a damaged checkpoint behind.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        fs::write(&temp, serde_json::to_vec_pretty(self)?)?;
+        fs::rename(&temp, path)
+    }
+
+    /// Records that `sequence_number` is the last record read from `shard_id`.
+    pub fn read(&mut self, shard_id: &str, sequence_number: &str) {
+        self.positions
+            .insert(shard_id.to_string(), sequence_number.to_string());
+    }
+
+    /// Records that `shard_id` has been read to the end.
+    pub fn finish(&mut self, shard_id: &str) {
+        self.positions.remove(shard_id);
+        self.finished.insert(shard_id.to_string());
+    }
+
+    /// Whether `shard_id` has been read to the end.
+    pub fn is_finished(&self, shard_id: &str) -> bool {
+        self.finished.contains(shard_id)
+    }
+
+    /// Where to start reading `shard_id`: after the last record read from
+    /// it, or else at its oldest record.
+    pub fn start(&self, shard_id: &str) -> (ShardIteratorType, Option<&str>) {
+        match self.positions.get(shard_id) {
+            Some(sequence_number) => (
+                ShardIteratorType::AfterSequenceNumber,
+                Some(sequence_number.as_str()),
+            ),
+            None => (ShardIteratorType::TrimHorizon, None),
+        }
+    }
+
+    /// Forgets the shards that are no longer in the stream, which trims
+    /// shards 24 hours after they close, so that the file does not keep growing.
+    pub fn retain(&mut self, shards: &[Shard]) {
+        let listed: HashSet<&str> = shards
+            .iter()
+            .filter_map(|s| s.shard_id.as_deref())
+            .collect();
+        self.positions.retain(|id, _| listed.contains(id.as_str()));
+        self.finished.retain(|id| listed.contains(id.as_str()));
+    }
+}
+
+/// Whether `shard` has been closed, so that reading it will come to an end.
+pub fn is_closed(shard: &Shard) -> bool {
+    shard
+        .sequence_number_range
+        .as_ref()
+        .is_some_and(|range| range.ending_sequence_number.is_some())
+}
+
+/// The shards that can be read now: those not yet finished whose parent,
+/// if any, has been finished or is no longer in the stream.
+pub fn ready_shards<'a>(shards: &'a [Shard], checkpoint: &Checkpoint) -> Vec<&'a Shard> {
+    let listed: HashSet<&str> = shards
+        .iter()
+        .filter_map(|s| s.shard_id.as_deref())
+        .collect();
+    shards
+        .iter()
+        .filter(|shard| match &shard.shard_id {
+            Some(id) => !checkpoint.is_finished(id),
+            None => false,
+        })
+        .filter(|shard| match &shard.parent_shard_id {
+            Some(parent) => !listed.contains(parent.as_str()) || checkpoint.is_finished(parent),
+            None => true,
+        })
+        .collect()
+}
+
+/// Converts an attribute value from the streams client into one from the
+/// DynamoDB client, which has the same variants.
+/// A type this client does not know becomes `NULL`.
+pub fn to_attribute(value: StreamAttributeValue) -> AttributeValue {
+    match value {
+        StreamAttributeValue::B(b) => AttributeValue::B(b),
+        StreamAttributeValue::Bool(b) => AttributeValue::Bool(b),
+        StreamAttributeValue::Bs(bs) => AttributeValue::Bs(bs),
+        StreamAttributeValue::L(l) => AttributeValue::L(l.into_iter().map(to_attribute).collect()),
+        StreamAttributeValue::M(m) => {
+            AttributeValue::M(m.into_iter().map(|(k, v)| (k, to_attribute(v))).collect())
+        }
+        StreamAttributeValue::N(n) => AttributeValue::N(n),
+        StreamAttributeValue::Ns(ns) => AttributeValue::Ns(ns),
+        StreamAttributeValue::Null(b) => AttributeValue::Null(b),
+        StreamAttributeValue::S(s) => AttributeValue::S(s),
+        StreamAttributeValue::Ss(ss) => AttributeValue::Ss(ss),
+        _ => AttributeValue::Null(true),
+    }
+}
+
+/// Converts a stream record into plain JSON, such as
+/// `{"event": "MODIFY", "sequence_number": "200", "created": 1634169600, "keys": {...}, "old_image": {...}, "new_image": {...}}`.
+/// Images the stream does not record are left out.
+pub fn record_to_value(record: Record) -> Value {
+    let mut value = json!({
+        "event": record.event_name.as_ref().map(|name| name.as_str()),
+    });
+    let change = match record.dynamodb {
+        Some(change) => change,
+        None => return value,
+    };
+    let image = |image: Option<HashMap<String, StreamAttributeValue>>| {
+        image.map(|image| {
+            let item: Item = image
+                .into_iter()
+                .map(|(k, v)| (k, to_attribute(v)))
+                .collect();
+            item_to_value(&item)
+        })
+    };
+    let fields = value.as_object_mut().expect("value is an object");
+    fields.insert("sequence_number".into(), change.sequence_number.into());
+    fields.insert(
+        "created".into(),
+        change
+            .approximate_creation_date_time
+            .map(|time| time.secs())
+            .into(),
+    );
+    fields.insert("keys".into(), image(change.keys).into());
+    for (name, image) in [
+        ("old_image", image(change.old_image)),
+        ("new_image", image(change.new_image)),
+    ] {
+        if let Some(image) = image {
+            fields.insert(name.into(), image);
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_dynamodbstreams::model::SequenceNumberRange;
+
+    fn shard(id: &str, parent: Option<&str>, closed: bool) -> Shard {
+        let range = SequenceNumberRange::builder()
+            .starting_sequence_number("100")
+            .set_ending_sequence_number(if closed { Some("199".into()) } else { None })
+            .build();
+        Shard::builder()
+            .shard_id(id)
+            .set_parent_shard_id(parent.map(str::to_string))
+            .sequence_number_range(range)
+            .build()
+    }
+
+    fn ids(shards: Vec<&Shard>) -> Vec<&str> {
+        shards
+            .iter()
+            .filter_map(|s| s.shard_id.as_deref())
+            .collect()
+    }
+
+    #[test]
+    fn children_wait_for_their_parent() {
+        // "old" was trimmed already; "parent" split into "left" and "right".
+        let shards = vec![
+            shard("parent", Some("old"), true),
+            shard("left", Some("parent"), false),
+            shard("right", Some("parent"), false),
+        ];
+        let mut checkpoint = Checkpoint::new("arn");
+        assert!(is_closed(&shards[0]));
+        assert_eq!(ids(ready_shards(&shards, &checkpoint)), ["parent"]);
+
+        checkpoint.read("parent", "150");
+        assert_eq!(
+            checkpoint.start("parent"),
+            (ShardIteratorType::AfterSequenceNumber, Some("150"))
+        );
+        checkpoint.finish("parent");
+        assert_eq!(ids(ready_shards(&shards, &checkpoint)), ["left", "right"]);
+        assert_eq!(
+            checkpoint.start("left"),
+            (ShardIteratorType::TrimHorizon, None)
+        );
+
+        // Once the parent is trimmed, the checkpoint forgets it.
+        checkpoint.retain(&shards[1..]);
+        assert!(checkpoint.finished.is_empty());
+        assert_eq!(
+            ids(ready_shards(&shards[1..], &checkpoint)),
+            ["left", "right"]
+        );
+    }
+}
@@ -0,0 +1,264 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Writing items with optimistic locking, alone or several at once in a
+//! transaction.
+//!
+//! A versioned item keeps a number in its [`VERSION`] attribute, which every
+//! write increases by one. A writer says which version it last read, and the
+//! write fails with a conditional check failure if someone else has written
+//! the item since, instead of silently overwriting their change.
+//!
+//! The `versioned_*` functions return the [`Expressions`] for a single-item
+//! request; [`put`], [`update`], [`delete`], and [`check`] build the items of
+//! a `TransactWriteItems` request, which [`write`] sends:
+//!
+//! ```no_run
+//! use dynamodb_code_examples::expression::{attr, value, Update};
+//! use dynamodb_code_examples::json::Item;
+//! use dynamodb_code_examples::transaction;
+//!
+//! # async fn run(client: aws_sdk_dynamodb::Client, from: Item, to: Item) {
+//! // Move 10 credits from one account to another, if neither has changed
+//! // since version 3 and 7 were read.
+//! let result = transaction::write(
+//!     &client,
+//!     vec![
+//!         transaction::update(
+//!             "accounts",
+//!             from,
+//!             Update::new().decrement(attr("credits"), value(&10)),
+//!             Some(3),
+//!         ),
+//!         transaction::update(
+//!             "accounts",
+//!             to,
+//!             Update::new().increment(attr("credits"), value(&10)),
+//!             Some(7),
+//!         ),
+//!     ],
+//!     None,
+//! )
+//! .await;
+//!
+//! if let Err(e) = &result {
+//!     // One reason per item, such as Some("ConditionalCheckFailed").
+//!     println!("{:?}", transaction::cancellation_reasons(e));
+//! }
+//! # }
+//! ```
+
+use aws_sdk_dynamodb::error::{
+    TransactGetItemsError, TransactWriteItemsError, TransactWriteItemsErrorKind,
+};
+use aws_sdk_dynamodb::model::{
+    AttributeValue, ConditionCheck, Delete, Get, Put, TransactGetItem, TransactWriteItem,
+    Update as UpdateAction,
+};
+use aws_sdk_dynamodb::{Client, SdkError};
+
+use crate::expression::{attr, value, Expressions, Update};
+use crate::json::Item;
+
+/// The attribute that holds an item's version.
+pub const VERSION: &str = "version";
+
+/// Sets the version of `item` to follow `expected`, and returns the
+/// condition for putting it: that the stored item is still at version
+/// `expected`, or, if `expected` is `None`, that there is no stored item.
+pub fn versioned_put(item: &mut Item, expected: Option<u64>) -> Expressions {
+    let next = expected.unwrap_or_default() + 1;
+    item.insert(VERSION.to_string(), AttributeValue::N(next.to_string()));
+    let condition = match expected {
+        Some(version) => attr(VERSION).eq(value(&version)),
+        None => attr(VERSION).not_exists(),
+    };
+    Expressions::new().condition(condition)
+}
+
+/// Adds increasing the version to `update`, and returns the update with the
+/// condition that the stored item is still at version `expected`.
+/// With `expected` of `None`, the item is updated whatever its version.
+pub fn versioned_update(update: Update, expected: Option<u64>) -> Expressions {
+    let exprs = Expressions::new().update(update.add(attr(VERSION), value(&1)));
+    match expected {
+        Some(version) => exprs.condition(attr(VERSION).eq(value(&version))),
+        None => exprs,
+    }
+}
+
+/// Returns the condition for deleting an item: that it is still at version
+/// `expected`. With `expected` of `None`, the item is deleted whatever its version.
+pub fn versioned_delete(expected: Option<u64>) -> Expressions {
+    match expected {
+        Some(version) => Expressions::new().condition(attr(VERSION).eq(value(&version))),
+        None => Expressions::new(),
+    }
+}
+
+/// A transaction item that puts `item`, as [`versioned_put`] does.
+pub fn put(table: &str, mut item: Item, expected: Option<u64>) -> TransactWriteItem {
+    let exprs = versioned_put(&mut item, expected);
+    let put = Put::builder()
+        .table_name(table)
+        .set_item(Some(item))
+        .set_condition_expression(exprs.condition_expression().map(str::to_string))
+        .set_expression_attribute_names(exprs.names())
+        .set_expression_attribute_values(exprs.values())
+        .build();
+    TransactWriteItem::builder().put(put).build()
+}
+
+/// A transaction item that updates the item with `key`, as [`versioned_update`] does.
+pub fn update(table: &str, key: Item, update: Update, expected: Option<u64>) -> TransactWriteItem {
+    let exprs = versioned_update(update, expected);
+    let update = UpdateAction::builder()
+        .table_name(table)
+        .set_key(Some(key))
+        .set_update_expression(exprs.update_expression().map(str::to_string))
+        .set_condition_expression(exprs.condition_expression().map(str::to_string))
+        .set_expression_attribute_names(exprs.names())
+        .set_expression_attribute_values(exprs.values())
+        .build();
+    TransactWriteItem::builder().update(update).build()
+}
+
+/// A transaction item that deletes the item with `key`, as [`versioned_delete`] does.
+pub fn delete(table: &str, key: Item, expected: Option<u64>) -> TransactWriteItem {
+    let exprs = versioned_delete(expected);
+    let delete = Delete::builder()
+        .table_name(table)
+        .set_key(Some(key))
+        .set_condition_expression(exprs.condition_expression().map(str::to_string))
+        .set_expression_attribute_names(exprs.names())
+        .set_expression_attribute_values(exprs.values())
+        .build();
+    TransactWriteItem::builder().delete(delete).build()
+}
+
+/// A transaction item that changes nothing, but cancels the transaction
+/// unless the versioned item with `key` exists, at version `expected` if given.
+pub fn check(table: &str, key: Item, expected: Option<u64>) -> TransactWriteItem {
+    let exprs = Expressions::new().condition(match expected {
+        Some(version) => attr(VERSION).eq(value(&version)),
+        None => attr(VERSION).exists(),
+    });
+    let check = ConditionCheck::builder()
+        .table_name(table)
+        .set_key(Some(key))
+        .set_condition_expression(exprs.condition_expression().map(str::to_string))
+        .set_expression_attribute_names(exprs.names())
+        .set_expression_attribute_values(exprs.values())
+        .build();
+    TransactWriteItem::builder().condition_check(check).build()
+}
+
+/// A transaction item that reads the item with `key`.
+pub fn get(table: &str, key: Item) -> TransactGetItem {
+    let get = Get::builder().table_name(table).set_key(Some(key)).build();
+    TransactGetItem::builder().get(get).build()
+}
+
+/// Makes all of the writes in `items`, or none of them.
+///
+/// `token` makes the request idempotent: DynamoDB ignores a repeat of a
+/// request with the same token made within ten minutes. If it is `None`,
+/// the SDK generates one, which covers only its own retries.
+pub async fn write(
+    client: &Client,
+    items: Vec<TransactWriteItem>,
+    token: Option<String>,
+) -> Result<(), SdkError<TransactWriteItemsError>> {
+    client
+        .transact_write_items()
+        .set_transact_items(Some(items))
+        .set_client_request_token(token)
+        .send()
+        .await?;
+    Ok(())
+}
+
+/// Reads all of the items in `items` as of one moment, returning `None`
+/// for each one that does not exist.
+pub async fn read(
+    client: &Client,
+    items: Vec<TransactGetItem>,
+) -> Result<Vec<Option<Item>>, SdkError<TransactGetItemsError>> {
+    let resp = client
+        .transact_get_items()
+        .set_transact_items(Some(items))
+        .send()
+        .await?;
+    Ok(resp
+        .responses
+        .unwrap_or_default()
+        .into_iter()
+        .map(|r| r.item)
+        .collect())
+}
+
+/// Why DynamoDB canceled a transaction: one entry per item, such as
+/// `Some("ConditionalCheckFailed")`, or `None` for the items that were fine.
+/// Empty if `err` is not a canceled transaction.
+pub fn cancellation_reasons(err: &SdkError<TransactWriteItemsError>) -> Vec<Option<String>> {
+    match err {
+        SdkError::ServiceError { err, .. } => match &err.kind {
+            TransactWriteItemsErrorKind::TransactionCanceledException(e) => e
+                .cancellation_reasons
+                .iter()
+                .flatten()
+                .map(|reason| reason.code.clone().filter(|code| code != "None"))
+                .collect(),
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> Item {
+        [("username".to_string(), AttributeValue::S("jdoe".into()))]
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn writes_bump_and_check_the_version() {
+        let mut item = key();
+        let exprs = versioned_put(&mut item, None);
+        assert_eq!(item[VERSION], AttributeValue::N("1".into()));
+        assert_eq!(
+            exprs.condition_expression(),
+            Some("attribute_not_exists(#n0)")
+        );
+
+        let write = update(
+            "users",
+            key(),
+            Update::new().set(attr("age"), value(&44)),
+            Some(1),
+        );
+        let update = write.update.unwrap();
+        assert_eq!(
+            update.update_expression.as_deref(),
+            Some("SET #n0 = :v0 ADD #n1 :v1")
+        );
+        assert_eq!(update.condition_expression.as_deref(), Some("#n1 = :v2"));
+        assert_eq!(update.expression_attribute_names.unwrap()["#n1"], VERSION);
+        assert_eq!(
+            update.expression_attribute_values.unwrap()[":v2"],
+            AttributeValue::N("1".into())
+        );
+
+        // Without a version, a delete has no condition at all.
+        let delete = delete("users", key(), None).delete.unwrap();
+        assert_eq!(delete.condition_expression, None);
+        assert_eq!(delete.expression_attribute_names, None);
+    }
+}
@@ -0,0 +1,32 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::testing::Replay;
+
+#[test]
+fn dry_run_shows_plan_without_changing_table() {
+    let run = Replay::new(
+        env!("CARGO_BIN_EXE_apply-table"),
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/apply-table.json"
+        ),
+    )
+    .args([
+        "-f",
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/bin/movies.yaml"),
+        "--dry-run",
+    ])
+    .run();
+
+    run.assert_success()
+        .assert_requests_match(&["content-type", "x-amz-target"]);
+    assert!(
+        run.stdout
+            .contains("Changes to table movies:\n  ~ billing: provisioned (read 10, write 10)\n"),
+        "unexpected output:\n{}",
+        run.stdout
+    );
+}
@@ -0,0 +1,52 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::testing::Replay;
+
+#[test]
+fn crud_creates_uses_and_deletes_a_table() {
+    let run = Replay::new(
+        env!("CARGO_BIN_EXE_crud"),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/crud.json"),
+    )
+    .args(["-t", "users", "-k", "username", "--key-value", "jdoe"])
+    .run();
+
+    run.assert_success()
+        .assert_requests_match(&["content-type", "x-amz-target"]);
+
+    // Every write is conditional on the version the item was last seen at.
+    let put = run.requests[2].json();
+    assert_eq!(put["ConditionExpression"], "attribute_not_exists(#n0)");
+    assert_eq!(put["Item"]["version"]["N"], "1");
+
+    // The update changes only the age, and moves the item on from version 1.
+    let update = run.requests[3].json();
+    assert_eq!(update["UpdateExpression"], "SET #n0 = :v0 ADD #n1 :v1");
+    assert_eq!(update["ConditionExpression"], "#n1 = :v2");
+    assert_eq!(update["ExpressionAttributeValues"][":v0"]["N"], "44");
+    assert_eq!(update["ExpressionAttributeValues"][":v2"]["N"], "1");
+
+    let delete = run.requests[5].json();
+    assert_eq!(delete["ConditionExpression"], "#n0 = :v0");
+    assert_eq!(delete["ExpressionAttributeValues"][":v0"]["N"], "2");
+
+    for line in &[
+        "Created the table.",
+        "Table is now ready to use.",
+        "Modified table item.",
+        "Found a matching entry in the table:",
+        "It matches the original value.",
+        "Deleted item.",
+        "Deleted table.",
+    ] {
+        assert!(
+            run.stdout.contains(line),
+            "missing {:?} in\n{}",
+            line,
+            run.stdout
+        );
+    }
+}
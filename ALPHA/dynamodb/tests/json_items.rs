@@ -0,0 +1,82 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::testing::Replay;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::PathBuf;
+
+fn tmp_file(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name)
+}
+
+#[test]
+fn import_retries_unprocessed_items() {
+    let file = tmp_file("json-items-import.ndjson");
+    fs::write(
+        &file,
+        concat!(
+            "{\"username\": \"jdoe\", \"age\": 33}\n",
+            "{\"Item\": {\"username\": {\"S\": \"asmith\"}, \"groups\": {\"SS\": [\"admins\", \"users\"]}, \"avatar\": {\"B\": \"AAEC\"}}}\n",
+        ),
+    )
+    .unwrap();
+
+    let run = Replay::new(
+        env!("CARGO_BIN_EXE_json-items"),
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/json-items-import.json"
+        ),
+    )
+    .args(["import", "-t", "users", "-f"])
+    .args([&file])
+    .run();
+
+    // The second request resends only the item left unprocessed by the first.
+    run.assert_success()
+        .assert_requests_match(&["content-type", "x-amz-target"]);
+    assert!(
+        run.stdout.contains("Imported 2 items into users"),
+        "unexpected output:\n{}",
+        run.stdout
+    );
+}
+
+#[test]
+fn export_writes_dynamodb_json() {
+    let file = tmp_file("json-items-export.ndjson");
+
+    let run = Replay::new(
+        env!("CARGO_BIN_EXE_json-items"),
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/json-items-export.json"
+        ),
+    )
+    .args(["export", "-t", "users", "--segments", "1", "-f"])
+    .args([&file])
+    .run();
+
+    run.assert_success()
+        .assert_requests_match(&["content-type", "x-amz-target"]);
+
+    let lines: Vec<Value> = fs::read_to_string(&file)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(
+        lines,
+        [
+            json!({"Item": {"username": {"S": "jdoe"}, "age": {"N": "33"}}}),
+            json!({"Item": {
+                "username": {"S": "asmith"},
+                "groups": {"SS": ["admins", "users"]},
+                "avatar": {"B": "AAEC"}
+            }}),
+        ]
+    );
+}
@@ -0,0 +1,51 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::testing::{Replay, ReplayRun};
+
+fn list_items(output: &str) -> ReplayRun {
+    Replay::new(
+        env!("CARGO_BIN_EXE_list-items"),
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/list-items.json"
+        ),
+    )
+    .args([
+        "-t",
+        "users",
+        "--filter",
+        "age > :min",
+        "--value",
+        ":min=40",
+        "--projection",
+        "username,age",
+        "--max-rcu",
+        "100",
+        "--output",
+        output,
+    ])
+    .run()
+}
+
+#[test]
+fn list_items_filters_and_writes_csv() {
+    let run = list_items("csv");
+
+    run.assert_success()
+        .assert_requests_match(&["content-type", "x-amz-target"]);
+    assert_eq!(run.stdout, "username,age\njdoe,44\nasmith,52\n");
+}
+
+#[test]
+fn list_items_writes_ndjson() {
+    let run = list_items("ndjson");
+
+    run.assert_success();
+    assert_eq!(
+        run.stdout,
+        "{\"username\":\"jdoe\",\"age\":\"44\"}\n{\"username\":\"asmith\",\"age\":\"52\"}\n"
+    );
+}
@@ -0,0 +1,43 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::testing::Replay;
+
+const FIXTURE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/list-tables.json"
+);
+
+#[test]
+fn list_tables_follows_every_page() {
+    let run = Replay::new(env!("CARGO_BIN_EXE_list-tables"), FIXTURE)
+        .args(["--page-size", "2"])
+        .run();
+
+    run.assert_success()
+        .assert_requests_match(&["content-type", "x-amz-target"]);
+    assert!(
+        run.stdout
+            .contains("  movies\n  orders\n  users\nFound 3 tables"),
+        "unexpected output:\n{}",
+        run.stdout
+    );
+}
+
+#[test]
+fn list_tables_stops_at_max_items() {
+    let run = Replay::new(env!("CARGO_BIN_EXE_list-tables"), FIXTURE)
+        .args(["--page-size", "2", "--max-items", "1"])
+        .run();
+
+    run.assert_success();
+    // The second page is never requested.
+    assert_eq!(run.requests.len(), 1);
+    assert!(
+        run.stdout.contains("  movies\nFound 1 tables"),
+        "unexpected output:\n{}",
+        run.stdout
+    );
+}
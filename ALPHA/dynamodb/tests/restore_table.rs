@@ -0,0 +1,36 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::testing::Replay;
+
+#[test]
+fn restore_table_to_a_point_in_time() {
+    let run = Replay::new(
+        env!("CARGO_BIN_EXE_restore-table"),
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/restore-table.json"
+        ),
+    )
+    .args([
+        "-t",
+        "users-restored",
+        "--source",
+        "users",
+        "--time",
+        "2021-10-14T09:30:00Z",
+    ])
+    .run();
+
+    run.assert_success()
+        .assert_requests_match(&["content-type", "x-amz-target"]);
+    assert!(
+        run.stdout
+            .contains("Restoring table users as of 2021-10-14T09:30:00Z to table users-restored\n"),
+        "unexpected output:\n{}",
+        run.stdout
+    );
+    assert!(run.stdout.ends_with("Restored table users-restored\n"));
+}
@@ -0,0 +1,40 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::testing::Replay;
+use std::fs;
+use std::path::PathBuf;
+
+#[test]
+fn shell_runs_commands_and_partiql() {
+    let history = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("shell-history");
+    let _ = fs::remove_file(&history);
+
+    let run = Replay::new(
+        env!("CARGO_BIN_EXE_shell"),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/shell.json"),
+    )
+    .args(["--history".as_ref(), history.as_os_str()])
+    .stdin(concat!(
+        ".get users username=jdoe\n",
+        "SELECT * FROM \"users\" WHERE age > 40\n",
+        ".frobnicate\n",
+        ".quit\n",
+        "SELECT * FROM \"movies\"\n",
+    ))
+    .run();
+
+    run.assert_success()
+        .assert_requests_match(&["content-type", "x-amz-target"]);
+    assert!(
+        run.stdout.contains("\"username\": \"asmith\"") && run.stdout.contains("2 items"),
+        "unexpected output:\n{}",
+        run.stdout
+    );
+    assert!(run.stdout.contains("unknown command"), "{}", run.stdout);
+    // Nothing after .quit is run, but everything before it is remembered.
+    let history = fs::read_to_string(&history).unwrap();
+    assert!(history.contains(".get users username=jdoe"), "{}", history);
+}
@@ -0,0 +1,85 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::testing::Replay;
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+
+#[test]
+fn tail_stream_reads_children_after_their_parent() {
+    let checkpoint = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("users.checkpoint.json");
+    let _ = fs::remove_file(&checkpoint);
+
+    let run = Replay::new(
+        env!("CARGO_BIN_EXE_tail-stream"),
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/tail-stream.json"
+        ),
+    )
+    .args([
+        "-t".as_ref(),
+        "users".as_ref(),
+        "--from-start".as_ref(),
+        "--once".as_ref(),
+        "--checkpoint".as_ref(),
+        checkpoint.as_os_str(),
+    ])
+    .run();
+
+    run.assert_success()
+        .assert_requests_match(&["content-type", "x-amz-target"]);
+    let events: Vec<Value> = run
+        .stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(events.len(), 2, "unexpected output:\n{}", run.stdout);
+    assert_eq!(events[0]["event"], "INSERT");
+    assert_eq!(events[1]["event"], "MODIFY");
+    assert_eq!(events[1]["old_image"]["age"], "33");
+    assert_eq!(events[1]["new_image"]["age"], "34");
+
+    let saved: Value = serde_json::from_slice(&fs::read(&checkpoint).unwrap()).unwrap();
+    assert_eq!(
+        saved["positions"]["shardId-00000001634184000000-bbbbbbbb"],
+        "200"
+    );
+    assert_eq!(
+        saved["finished"][0],
+        "shardId-00000001634169600000-aaaaaaaa"
+    );
+}
+
+#[test]
+fn tail_stream_reopens_an_expired_iterator_where_it_was() {
+    let checkpoint =
+        PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("users-expired.checkpoint.json");
+    let _ = fs::remove_file(&checkpoint);
+
+    let run = Replay::new(
+        env!("CARGO_BIN_EXE_tail-stream"),
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/tail-stream-expired.json"
+        ),
+    )
+    .args([
+        "-t".as_ref(),
+        "users".as_ref(),
+        "--once".as_ref(),
+        "--checkpoint".as_ref(),
+        checkpoint.as_os_str(),
+    ])
+    .run();
+
+    // Nothing was read before the iterator expired, so the shard is opened
+    // at its newest record again rather than replaying it from the start.
+    run.assert_success()
+        .assert_requests_match(&["content-type", "x-amz-target"]);
+    assert_eq!(run.requests[4].json()["ShardIteratorType"], "LATEST");
+    assert_eq!(run.stdout, "");
+}
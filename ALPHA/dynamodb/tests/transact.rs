@@ -0,0 +1,67 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::testing::Replay;
+use std::fs;
+use std::path::PathBuf;
+
+#[test]
+fn transact_reports_why_it_was_canceled() {
+    let ops = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("transact-ops.json");
+    fs::write(
+        &ops,
+        r#"[
+            {"put": {"table": "users", "item": {"username": "jdoe", "age": 33}}},
+            {"update": {"table": "users", "key": {"username": "asmith"}, "set": {"age": 45}, "version": 2}},
+            {"check": {"table": "accounts", "key": {"id": "a1"}, "version": 7}}
+        ]"#,
+    )
+    .unwrap();
+
+    let run = Replay::new(
+        env!("CARGO_BIN_EXE_transact"),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/transact.json"),
+    )
+    .args([
+        "-f".as_ref(),
+        ops.as_os_str(),
+        "--token".as_ref(),
+        "test-token".as_ref(),
+    ])
+    .run();
+
+    assert!(!run.status.success(), "{}", run.stdout);
+    run.assert_requests_match(&["content-type", "x-amz-target"]);
+    assert!(
+        run.stdout
+            .contains("  2 update users {\"username\":\"asmith\"}: ConditionalCheckFailed\n"),
+        "unexpected output:\n{}",
+        run.stdout
+    );
+}
+
+#[test]
+fn transact_rejects_a_file_without_operations() {
+    let ops = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("transact-empty.json");
+    fs::write(&ops, "[]").unwrap();
+
+    let run = Replay::new(
+        env!("CARGO_BIN_EXE_transact"),
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/transact-empty.json"
+        ),
+    )
+    .args(["-f".as_ref(), ops.as_os_str()])
+    .run();
+
+    assert!(!run.status.success(), "{}", run.stdout);
+    run.assert_requests_match(&[]);
+    assert!(
+        run.stdout.contains("the file lists no operations"),
+        "unexpected output:\n{}",
+        run.stdout
+    );
+}
@@ -3,9 +3,10 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
+use alpha_common::waiter::{ec2, WaitOpt};
+use alpha_common::CommonOpt;
 use aws_sdk_ebs::model::ChecksumAlgorithm;
-use aws_sdk_ebs::{ByteStream, Client, Error, Region, PKG_VERSION};
+use aws_sdk_ebs::{ByteStream, Error, PKG_VERSION};
 use sha2::Digest;
 use structopt::StructOpt;
 
@@ -14,55 +15,40 @@ const EBS_BLOCK_SIZE: usize = 524288;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The snapshot's description.
     #[structopt(short, long)]
     description: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    wait: WaitOpt,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Creates an Amazon Elastic Block Store snapshot using generated data.
 /// # Arguments
 ///
 /// * `-d DESCRIPTION` - The description of the snapshot.
+/// * `[--wait]` - Whether to wait until the snapshot is completed.
+/// * `[--max-wait SECONDS]` - The most seconds to wait. Defaults to 600.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
         description,
-        region,
-        verbose,
+        wait,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("EBS client version: {}", PKG_VERSION);
-        println!(
-            "Region:             {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Description:        {}", description);
+    shared.banner("EBS", PKG_VERSION, &[("Description", &description)]);
 
-        println!();
-    }
-
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.ebs();
 
     let snapshot = client
         .start_snapshot()
@@ -81,8 +67,7 @@ async fn main() -> Result<(), Error> {
     blocks.push(block);
 
     // Append a block of all 0s.
-    let mut block: Vec<u8> = Vec::new();
-    block.resize(EBS_BLOCK_SIZE, 0);
+    let block: Vec<u8> = vec![0; EBS_BLOCK_SIZE];
     blocks.push(block);
 
     for (idx, block) in blocks.into_iter().enumerate() {
@@ -110,8 +95,22 @@ async fn main() -> Result<(), Error> {
         .await?;
 
     println!("Snapshot ID {}", snapshot_id);
-    println!("The state is 'completed' when all of the modified blocks have been transferred to Amazon S3.");
-    println!("Use the get-snapshot-state code example to get the state of the snapshot.");
+
+    match wait.waiter() {
+        Some(waiter) => {
+            println!("Waiting for the snapshot to be completed.");
+            if let Err(e) = ec2::snapshot_completed(&waiter, &shared.ec2(), &snapshot_id).await {
+                println!("Got an error waiting for the snapshot:");
+                println!("{}", e);
+                shared.exit(1);
+            }
+            println!("Snapshot is completed.");
+        }
+        None => {
+            println!("The state is 'completed' when all of the modified blocks have been transferred to Amazon S3.");
+            println!("Use the get-snapshot-state code example to get the state of the snapshot.");
+        }
+    }
 
     Ok(())
 }
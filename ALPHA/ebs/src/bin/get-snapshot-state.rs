@@ -3,24 +3,20 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
+use alpha_common::output::Listing;
+use alpha_common::CommonOpt;
 use aws_sdk_ec2::model::Filter;
-use aws_sdk_ec2::{Client, Error, Region, PKG_VERSION};
+use aws_sdk_ec2::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The ID of the snapshot.
     #[structopt(short, long)]
     snapshot_id: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Retrieves the state of an Amazon Elastic Block Store snapshot using Amazon EC2 API.
@@ -34,31 +30,16 @@ struct Opt {
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
-        region,
         snapshot_id,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("EC2 client version: {}", PKG_VERSION);
-        println!(
-            "Region:             {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Snapshot ID:        {}", snapshot_id);
-        println!();
-    }
+    shared.banner("EC2", PKG_VERSION, &[("Snapshot ID", &snapshot_id)]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.ec2();
 
     let resp = client
         .describe_snapshots()
@@ -71,16 +52,17 @@ async fn main() -> Result<(), Error> {
         .send()
         .await?;
 
-    println!(
-        "State: {}",
-        resp.snapshots
-            .unwrap()
-            .pop()
-            .unwrap()
-            .state
-            .unwrap()
-            .as_ref()
-    );
+    let snapshot = resp.snapshots.unwrap().pop().unwrap();
+
+    let mut listing = Listing::new(&["SnapshotId", "State"]);
+    listing.push(vec![
+        snapshot.snapshot_id.as_deref().into(),
+        snapshot.state.as_ref().map(|s| s.as_str()).into(),
+    ]);
+
+    shared.print(&listing, || {
+        println!("State: {}", snapshot.state.unwrap().as_ref());
+    });
 
     Ok(())
 }
@@ -3,75 +3,95 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_ec2::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::{date_time, Listing};
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_ec2::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Displays some information about the Amazon Elastic Block Store snapshots you own in the Region.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { pages, common } = Opt::from_args();
 
-    let Opt { region, verbose } = Opt::from_args();
+    let shared = common.load().await;
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    shared.banner("EC2", PKG_VERSION, &[]);
 
-    if verbose {
-        println!("EC2 client version: {}", PKG_VERSION);
-        println!(
-            "Region:             {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-
-        println!();
-    }
-
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.ec2();
 
     // "self" represents your account ID.
     // You can list the snapshots for any account by replacing
     // "self" with that account ID.
-    let resp = client.describe_snapshots().owner_ids("self").send().await?;
-    let snapshots = resp.snapshots.unwrap();
+    let snapshots = paginate(&pages, move |token, max_results| {
+        let request = client
+            .describe_snapshots()
+            .owner_ids("self")
+            .set_next_token(token)
+            .set_max_results(max_results);
+        async move {
+            request
+                .send()
+                .await
+                .map(|resp| Page::with_token(resp.snapshots.unwrap_or_default(), resp.next_token))
+        }
+    })
+    .try_collect()
+    .await?;
     let length = snapshots.len();
 
-    for snapshot in snapshots {
-        println!(
-            "ID:          {}",
-            snapshot.snapshot_id.as_deref().unwrap_or_default()
-        );
-        println!(
-            "Description: {}",
-            snapshot.description.as_deref().unwrap_or_default()
-        );
-        println!("State:       {}", snapshot.state.unwrap().as_ref());
-        println!();
+    let mut listing = Listing::new(&[
+        "SnapshotId",
+        "Description",
+        "State",
+        "VolumeSize",
+        "StartTime",
+    ]);
+    for snapshot in &snapshots {
+        listing.push(vec![
+            snapshot.snapshot_id.as_deref().into(),
+            snapshot.description.as_deref().into(),
+            snapshot.state.as_ref().map(|s| s.as_str()).into(),
+            snapshot.volume_size.into(),
+            date_time(snapshot.start_time.as_ref()),
+        ]);
     }
 
-    println!();
-    println!("Found {} snapshot(s)", length);
-    println!();
+    shared.print(&listing, || {
+        for snapshot in snapshots {
+            println!(
+                "ID:          {}",
+                snapshot.snapshot_id.as_deref().unwrap_or_default()
+            );
+            println!(
+                "Description: {}",
+                snapshot.description.as_deref().unwrap_or_default()
+            );
+            println!("State:       {}", snapshot.state.unwrap().as_ref());
+            println!();
+        }
+
+        println!();
+        println!("Found {} snapshot(s)", length);
+        println!();
+    });
 
     Ok(())
 }
@@ -3,43 +3,76 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_ec2::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::Listing;
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::{CommonOpt, Shared};
+use aws_sdk_ec2::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// To get info about one instance.
     #[structopt(short, long)]
     instance_id: Option<String>,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    pages: PageOpt,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 // Lists the state of an instance.
 async fn show_state(
+    shared: &Shared,
+    pages: &PageOpt,
     client: &aws_sdk_ec2::Client,
     ids: Vec<String>,
 ) -> Result<(), aws_sdk_ec2::Error> {
-    let resp = client
-        .describe_instances()
-        .set_instance_ids(Some(ids))
-        .send()
-        .await?;
+    let client = client.clone();
+    let reservations = paginate(pages, move |token, max_results| {
+        // A page size cannot be given along with instance IDs.
+        let max_results = max_results.filter(|_| ids.is_empty());
+        let request = client
+            .describe_instances()
+            .set_instance_ids(Some(ids.clone()))
+            .set_next_token(token)
+            .set_max_results(max_results);
+        async move {
+            request.send().await.map(|resp| {
+                Page::with_token(resp.reservations.unwrap_or_default(), resp.next_token)
+            })
+        }
+    })
+    .try_collect()
+    .await?;
+
+    let instances: Vec<_> = reservations
+        .into_iter()
+        .flat_map(|reservation| reservation.instances.unwrap_or_default())
+        .collect();
+
+    let mut listing = Listing::new(&["InstanceId", "InstanceType", "State"]);
+    for instance in &instances {
+        listing.push(vec![
+            instance.instance_id.as_deref().into(),
+            instance.instance_type.as_ref().map(|t| t.as_str()).into(),
+            instance
+                .state
+                .as_ref()
+                .and_then(|state| state.name.as_ref())
+                .map(|name| name.as_str())
+                .into(),
+        ]);
+    }
 
-    for reservation in resp.reservations.unwrap_or_default() {
-        for instance in reservation.instances.unwrap_or_default() {
+    shared.print(&listing, || {
+        for instance in instances {
             println!("Instance ID: {}", instance.instance_id.unwrap());
             println!("State:       {:?}", instance.state.unwrap().name.unwrap());
             println!();
         }
-    }
+    });
 
     Ok(())
 }
@@ -48,51 +81,31 @@ async fn show_state(
 /// # Arguments
 ///
 /// * `[-i INSTANCE-ID]` - The ID of an instance.
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///   If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///   If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
     let Opt {
-        region,
         instance_id,
-        verbose,
+        pages,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("EC2 client version: {}", PKG_VERSION);
-        println!(
-            "Region:             {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
+    shared.banner(
+        "EC2",
+        PKG_VERSION,
+        &[("Instance ID", &instance_id.as_deref().unwrap_or("(all)"))],
+    );
 
-        if instance_id.is_some() {
-            println!("Instance ID:        {:?}", instance_id);
-        }
-
-        println!();
-    }
-
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.ec2();
 
-    let mut ids: Vec<String> = Vec::new();
-    let id_opt: std::option::Option<std::vec::Vec<std::string::String>>;
-
-    match instance_id {
-        None => id_opt = None,
-        Some(i) => {
-            ids.push(i);
-            id_opt = Some(ids);
-        }
-    }
+    let ids: Vec<String> = instance_id.into_iter().collect();
 
-    show_state(&client, id_opt.unwrap()).await
+    show_state(&shared, &pages, &client, ids).await
 }
@@ -3,45 +3,98 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
+use alpha_common::output::Listing;
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::{CommonOpt, Shared};
+use aws_sdk_ec2::model::InstanceStatus;
 use aws_sdk_ec2::{Client, Error, Region, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional runtime information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 // Shows the events for every Region.
-async fn show_all_events(client: &aws_sdk_ec2::Client) -> Result<(), aws_sdk_ec2::Error> {
+async fn show_all_events(
+    shared: &Shared,
+    pages: &PageOpt,
+    client: &aws_sdk_ec2::Client,
+) -> Result<(), aws_sdk_ec2::Error> {
     let resp = client.describe_regions().send().await?;
 
+    let mut by_region = Vec::new();
     for region in resp.regions.unwrap_or_default() {
-        let reg: &'static str = Box::leak(region.region_name.unwrap().into_boxed_str());
-        show_events(reg).await;
+        let reg = region.region_name.unwrap();
+        let statuses = get_statuses(shared, pages, &reg).await;
+        by_region.push((reg, statuses));
     }
 
+    let mut listing = Listing::new(&[
+        "Region",
+        "InstanceId",
+        "InstanceEventId",
+        "Code",
+        "Description",
+    ]);
+    for (reg, statuses) in &by_region {
+        for status in statuses {
+            for event in status.events.iter().flatten() {
+                listing.push(vec![
+                    reg.as_str().into(),
+                    status.instance_id.as_deref().into(),
+                    event.instance_event_id.as_deref().into(),
+                    event.code.as_ref().map(|c| c.as_str()).into(),
+                    event.description.as_deref().into(),
+                ]);
+            }
+        }
+    }
+
+    shared.print(&listing, || {
+        for (reg, statuses) in by_region {
+            show_events(&reg, statuses);
+        }
+    });
+
     Ok(())
 }
 
-/// Shows the scheduled events for the Amazon Elastic Compute Cloud (Amazon EC2) instances in the Region.
-async fn show_events(reg: &'static str) {
-    let region_provider = RegionProviderChain::default_provider().or_else(reg);
-    let config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&config);
+/// Gets the status of the Amazon Elastic Compute Cloud (Amazon EC2) instances in the Region.
+async fn get_statuses(shared: &Shared, pages: &PageOpt, reg: &str) -> Vec<InstanceStatus> {
+    let config = shared
+        .ec2_config()
+        .region(Region::new(reg.to_string()))
+        .build();
+    let client = Client::from_conf(config);
+
+    let statuses = paginate(pages, move |token, max_results| {
+        let request = client
+            .describe_instance_status()
+            .set_next_token(token)
+            .set_max_results(max_results);
+        async move {
+            request.send().await.map(|resp| {
+                Page::with_token(resp.instance_statuses.unwrap_or_default(), resp.next_token)
+            })
+        }
+    })
+    .try_collect()
+    .await;
 
-    let resp = client.describe_instance_status().send().await;
+    statuses.unwrap()
+}
 
+/// Shows the scheduled events for the EC2 instances in the Region.
+fn show_events(reg: &str, statuses: Vec<InstanceStatus>) {
     println!("Instances in region {}:", reg);
     println!();
 
-    for status in resp.unwrap().instance_statuses.unwrap_or_default() {
+    for status in statuses {
         println!(
             "  Events scheduled for instance ID: {}",
             status.instance_id.as_deref().unwrap_or_default()
@@ -58,31 +111,21 @@ async fn show_events(reg: &'static str) {
 /// Lists the events of your EC2 instances in all available regions.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list in each Region.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-    let Opt { region, verbose } = Opt::from_args();
+    let Opt { pages, common } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("EC2 client version: {}", PKG_VERSION);
-        println!(
-            "Region:             {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!();
-    }
+    shared.banner("EC2", PKG_VERSION, &[]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.ec2();
 
-    show_all_events(&client).await
+    show_all_events(&shared, &pages, &client).await
 }
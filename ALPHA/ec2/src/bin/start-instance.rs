@@ -3,23 +3,22 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_ec2::{Client, Error, Region, PKG_VERSION};
+use alpha_common::waiter::{ec2, WaitOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_ec2::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The ID of the instance to stop.
     #[structopt(short, long)]
     instance_id: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    wait: WaitOpt,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 // Starts an instance.
@@ -35,36 +34,37 @@ async fn start_instance(client: &aws_sdk_ec2::Client, id: &str) -> Result<(), aw
 /// # Arguments
 ///
 /// * `-i INSTANCE-ID` - The ID of the instances to start.
+/// * `[--wait]` - Whether to wait until the instance is running.
+/// * `[--max-wait SECONDS]` - The most seconds to wait. Defaults to 600.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///   If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///   If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
     let Opt {
-        region,
         instance_id,
-        verbose,
+        wait,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("EC2 client version: {}", PKG_VERSION);
-        println!(
-            "Region:             {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Instance ID:        {}", instance_id);
-        println!();
-    }
+    shared.banner("EC2", PKG_VERSION, &[("Instance ID", &instance_id)]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.ec2();
 
-    start_instance(&client, &instance_id).await
+    start_instance(&client, &instance_id).await?;
+
+    if let Some(waiter) = wait.waiter() {
+        println!("Waiting for the instance to be running.");
+        if let Err(e) = ec2::instance_running(&waiter, &client, &instance_id).await {
+            println!("Got an error waiting for the instance:");
+            println!("{}", e);
+            shared.exit(1);
+        }
+        println!("Instance is running.");
+    }
+
+    Ok(())
 }
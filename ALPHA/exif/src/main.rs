@@ -1,8 +1,8 @@
 extern crate exif;
 
-use aws_sdk_dynamodb::model::AttributeValue;
-use aws_types::region;
-use aws_types::region::ProvideRegion;
+use alpha_common::CommonOpt;
+use dynamodb_code_examples::item::to_item;
+use serde::Serialize;
 use std::process;
 use structopt::StructOpt;
 
@@ -16,40 +16,35 @@ struct Opt {
     #[structopt(short, long)]
     filename: String,
 
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The DynamoDB table.
     #[structopt(short, long)]
     table: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 struct Litem {
     name: String,
     confidence: f32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default, Serialize)]
 struct Edata {
-    height: String,
-    width: String,
-    created: String,
+    height: Option<u32>,
+    width: Option<u32>,
+    created: Option<String>,
 }
 
-#[derive(Debug)]
-struct Ldata {
-    label1: String,
-    value1: String,
-    label2: String,
-    value2: String,
-    label3: String,
-    value3: String,
+/// What is stored in the table for each image.
+#[derive(Debug, Serialize)]
+struct ImageItem<'a> {
+    /// The table key.
+    filename: &'a str,
+    #[serde(flatten)]
+    edata: Edata,
+    labels: Vec<Litem>,
 }
 
 async fn add_file_to_bucket(client: &aws_sdk_s3::Client, bucket: &str, filename: &str) {
@@ -84,16 +79,9 @@ async fn add_file_to_bucket(client: &aws_sdk_s3::Client, bucket: &str, filename:
 }
 
 fn get_exif_data(filename: &str) -> Edata {
-    let height: String = "".to_owned();
-    let width: String = "".to_owned();
-    let created: String = "".to_owned();
-    let mut edata = Edata {
-        height,
-        width,
-        created,
-    };
+    let mut edata = Edata::default();
 
-    let file = std::fs::File::open(&filename).unwrap();
+    let file = std::fs::File::open(filename).unwrap();
     let mut bufreader = std::io::BufReader::new(&file);
     let exifreader = exif::Reader::new();
 
@@ -104,9 +92,9 @@ fn get_exif_data(filename: &str) -> Edata {
             for f in exif.fields() {
                 // Get EXIF values for image width, height, and when image was created.
                 match &*f.tag.to_string() {
-                    "ImageWidth" => edata.height.push_str(&*f.display_value().to_string()),
-                    "ImageLength" => edata.width.push_str(&*f.display_value().to_string()),
-                    "DateTimeOriginal" => edata.created.push_str(&*f.display_value().to_string()),
+                    "ImageWidth" => edata.width = f.value.get_uint(0),
+                    "ImageLength" => edata.height = f.value.get_uint(0),
+                    "DateTimeOriginal" => edata.created = Some(f.display_value().to_string()),
                     _ => {}
                 }
             }
@@ -131,30 +119,17 @@ async fn add_data_to_table(
     if verbose {
         println!("Added ")
     }
-    let filename_string = AttributeValue::S(filename.to_string());
-    let height_string = AttributeValue::S(edata.height);
-    let created_string = AttributeValue::S(edata.created);
-    let width_string = AttributeValue::S(edata.width);
-    let label1_label = AttributeValue::S(labels[0].name.to_string());
-    let label1_value = AttributeValue::S(labels[0].confidence.to_string());
-    let label2_label = AttributeValue::S(labels[1].name.to_string());
-    let label2_value = AttributeValue::S(labels[1].confidence.to_string());
-    let label3_label = AttributeValue::S(labels[2].name.to_string());
-    let label3_value = AttributeValue::S(labels[2].confidence.to_string());
+    let item = ImageItem {
+        filename,
+        edata,
+        labels,
+    };
+    let item = to_item(&item).expect("image data is always an item");
 
     match client
         .put_item()
         .table_name(table)
-        .item("filename", filename_string) // Table key.
-        .item("height", height_string)
-        .item("width", width_string)
-        .item("created", created_string)
-        .item("Label1", label1_label)
-        .item("Value1", label1_value)
-        .item("Label2", label2_label)
-        .item("Value2", label2_value)
-        .item("Label3", label3_label)
-        .item("Value3", label3_value)
+        .set_item(Some(item))
         .send()
         .await
     {
@@ -210,60 +185,35 @@ async fn get_label_data(
 
 #[tokio::main]
 async fn main() -> Result<(), exif::Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
         bucket,
         filename,
-        region,
         table,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let s3_provider = region::ChainProvider::first_try(region.clone().map(aws_sdk_s3::Region::new))
-        .or_default_provider()
-        .or_else(aws_sdk_s3::Region::new("us-west-2"));
-
-    let dynamo_provider =
-        region::ChainProvider::first_try(region.clone().map(aws_sdk_dynamodb::Region::new))
-            .or_default_provider()
-            .or_else(aws_sdk_dynamodb::Region::new("us-west-2"));
-
-    let rekognition_provider =
-        region::ChainProvider::first_try(region.clone().map(aws_sdk_rekognition::Region::new))
-            .or_default_provider()
-            .or_else(aws_sdk_rekognition::Region::new("us-west-2"));
-
-    println!();
-
-    if verbose {
-        println!(
-            "DynamoDB client version:    {}",
-            aws_sdk_dynamodb::PKG_VERSION
-        );
-        println!(
-            "Rekognition client version: {}",
-            aws_sdk_dynamodb::PKG_VERSION
-        );
-        println!(
-            "Region:                     {}",
-            dynamo_provider.region().unwrap().as_ref()
-        );
-        println!("Filename:                   {}", &filename);
-        println!("Bucket:                     {}", &bucket);
-        println!("Table:                      {}", &table);
-        println!();
-    }
-
-    let s3_conf = aws_sdk_s3::Config::builder().region(s3_provider).build();
-    let s3_client = aws_sdk_s3::Client::from_conf(s3_conf);
+    let shared = common.load().await;
+
+    shared.banner(
+        "DynamoDB",
+        aws_sdk_dynamodb::PKG_VERSION,
+        &[
+            (
+                "Rekognition client version",
+                &aws_sdk_rekognition::PKG_VERSION,
+            ),
+            ("S3 client version", &aws_sdk_s3::PKG_VERSION),
+            ("Filename", &filename),
+            ("Bucket", &bucket),
+            ("Table", &table),
+        ],
+    );
+
+    let s3_client = shared.s3();
 
     add_file_to_bucket(&s3_client, &bucket, &filename).await;
 
-    let dynamo_conf = aws_sdk_dynamodb::Config::builder()
-        .region(dynamo_provider)
-        .build();
-    let dynamo_client = aws_sdk_dynamodb::Client::from_conf(dynamo_conf);
+    let dynamo_client = shared.dynamodb();
 
     // Pass String values to the function as &str values.
 
@@ -287,15 +237,20 @@ async fn main() -> Result<(), exif::Error> {
 
     //add_exif_data_to_table(verbose, &dynamo_client, &table, &filename, edata).await;
 
-    let rekog_conf = aws_sdk_rekognition::Config::builder()
-        .region(rekognition_provider)
-        .build();
-    let rekog_client = aws_sdk_rekognition::Client::from_conf(rekog_conf);
+    let rekog_client = shared.rekognition();
 
     let labels = get_label_data(&rekog_client, &bucket, &filename).await;
 
     // Add data to table.
-    add_data_to_table(verbose, &dynamo_client, &table, &filename, edata, labels).await;
+    add_data_to_table(
+        shared.verbose(),
+        &dynamo_client,
+        &table,
+        &filename,
+        edata,
+        labels,
+    )
+    .await;
     /*
         async fn add_data_to_table(
         verbose: bool,
@@ -3,17 +3,13 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_iam::{Client, Error, Region, PKG_VERSION};
+use alpha_common::CommonOpt;
+use aws_sdk_iam::{Error, PKG_VERSION};
 use std::fs;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// Your account ID.
     #[structopt(short, long)]
     account: String,
@@ -30,9 +26,8 @@ struct Opt {
     #[structopt(short, long)]
     policy_file: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Creates an IAM role in the Region.
@@ -49,39 +44,31 @@ struct Opt {
 /// * `[-v]` - Whether to display information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
     let Opt {
         account,
         bucket,
         name,
         policy_file,
-        region,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("IAM client version: {}", PKG_VERSION);
-        println!(
-            "Region:             {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Account ID:         {}", &account);
-        println!("Bucket:             {}", &bucket);
-        println!("Role name:          {}", &name);
-        println!("Policy doc filename {}", &policy_file);
-        println!();
-    }
+    shared.banner(
+        "IAM",
+        PKG_VERSION,
+        &[
+            ("Account ID", &account),
+            ("Bucket", &bucket),
+            ("Role name", &name),
+            ("Policy doc filename", &policy_file),
+        ],
+    );
 
     // Read policy doc from file as a string
     let doc = fs::read_to_string(policy_file).expect("Unable to read file");
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.iam();
 
     let resp = client
         .create_role()
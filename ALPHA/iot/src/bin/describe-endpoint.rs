@@ -1,21 +1,16 @@
-use aws_sdk_iot::{Client, Config, Error, Region, PKG_VERSION};
-use aws_types::region;
-use aws_types::region::ProvideRegion;
+use alpha_common::output::Listing;
+use alpha_common::CommonOpt;
+use aws_sdk_iot::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The endpoint type.
     #[structopt(short, long)]
     endpoint_type: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /*
@@ -40,29 +35,16 @@ async fn is_good_type(t: String) -> bool {
 /// * `[-v]` - Whether to display information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
     let Opt {
-        region,
         endpoint_type,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region = region::ChainProvider::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("IoT client version: {}", PKG_VERSION);
-        println!("Region:             {}", region.region().unwrap().as_ref());
-        println!("Endpoint type:      {}", &endpoint_type);
+    shared.banner("IoT", PKG_VERSION, &[("Endpoint type", &endpoint_type)]);
 
-        println!();
-    }
-
-    let conf = Config::builder().region(region).build();
-    let client = Client::from_conf(conf);
+    let client = shared.iot();
 
     let resp = client
         .describe_endpoint()
@@ -70,9 +52,14 @@ async fn main() -> Result<(), Error> {
         .send()
         .await?;
 
-    println!("Endpoint address: {}", resp.endpoint_address.unwrap());
+    let mut listing = Listing::new(&["EndpointAddress"]);
+    listing.push(vec![resp.endpoint_address.as_deref().into()]);
 
-    println!();
+    shared.print(&listing, || {
+        println!("Endpoint address: {}", resp.endpoint_address.unwrap());
+
+        println!();
+    });
 
     Ok(())
 }
@@ -1,69 +1,83 @@
-use aws_sdk_iot::{Client, Config, Error, Region, PKG_VERSION};
-use aws_types::region;
-use aws_types::region::ProvideRegion;
+use alpha_common::output::Listing;
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_iot::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists the name, type, and ARN of your IoT things in the Region.
 ///
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///   If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///   If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-    let Opt { region, verbose } = Opt::from_args();
+    let Opt { pages, common } = Opt::from_args();
 
-    let region = region::ChainProvider::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
+    let shared = common.load().await;
 
-    println!();
+    shared.banner("IoT", PKG_VERSION, &[]);
 
-    if verbose {
-        println!("IoT client version: {}", PKG_VERSION);
-        println!("Region:             {}", region.region().unwrap().as_ref());
+    let client = shared.iot();
 
-        println!();
-    }
+    let things = paginate(&pages, move |token, max_results| {
+        let request = client
+            .list_things()
+            .set_next_token(token)
+            .set_max_results(max_results);
+        async move {
+            request
+                .send()
+                .await
+                .map(|resp| Page::with_token(resp.things.unwrap_or_default(), resp.next_token))
+        }
+    })
+    .try_collect()
+    .await?;
 
-    let conf = Config::builder().region(region).build();
-    let client = Client::from_conf(conf);
+    let mut listing = Listing::new(&["ThingName", "ThingTypeName", "ThingArn"]);
+    for thing in &things {
+        listing.push(vec![
+            thing.thing_name.as_deref().into(),
+            thing.thing_type_name.as_deref().into(),
+            thing.thing_arn.as_deref().into(),
+        ]);
+    }
 
-    let resp = client.list_things().send().await?;
+    shared.print(&listing, || {
+        println!("Things:");
 
-    println!("Things:");
+        for thing in things {
+            println!(
+                "  Name:  {}",
+                thing.thing_name.as_deref().unwrap_or_default()
+            );
+            println!(
+                "  Type:  {}",
+                thing.thing_type_name.as_deref().unwrap_or_default()
+            );
+            println!(
+                "  ARN:   {}",
+                thing.thing_arn.as_deref().unwrap_or_default()
+            );
+            println!();
+        }
 
-    for thing in resp.things.unwrap() {
-        println!(
-            "  Name:  {}",
-            thing.thing_name.as_deref().unwrap_or_default()
-        );
-        println!(
-            "  Type:  {}",
-            thing.thing_type_name.as_deref().unwrap_or_default()
-        );
-        println!(
-            "  ARN:   {}",
-            thing.thing_arn.as_deref().unwrap_or_default()
-        );
         println!();
-    }
-
-    println!();
+    });
 
     Ok(())
 }
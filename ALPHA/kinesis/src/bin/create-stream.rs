@@ -3,68 +3,66 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_kinesis::{Client, Error, Region, PKG_VERSION};
+use alpha_common::waiter::{kinesis, WaitOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_kinesis::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// THe name of the stream.
     #[structopt(short, long)]
     stream_name: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    wait: WaitOpt,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Creates an Amazon Kinesis data stream.
 /// # Arguments
 ///
 /// * `-s STREAM-NAME` - The name of the stream.
+/// * `[--wait]` - Whether to wait until the stream is active.
+/// * `[--max-wait SECONDS]` - The most seconds to wait. Defaults to 600.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
     let Opt {
         stream_name,
-        region,
-        verbose,
+        wait,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("Kinesis client version: {}", PKG_VERSION);
-        println!(
-            "Region:                 {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Stream name:            {}", &stream_name);
-        println!();
-    }
+    shared.banner("Kinesis", PKG_VERSION, &[("Stream name", &stream_name)]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.kinesis();
 
     client
         .create_stream()
-        .stream_name(stream_name)
+        .stream_name(&stream_name)
         .shard_count(4)
         .send()
         .await?;
 
     println!("Created stream");
 
+    if let Some(waiter) = wait.waiter() {
+        println!("Waiting for the stream to be active.");
+        if let Err(e) = kinesis::stream_active(&waiter, &client, &stream_name).await {
+            println!("Got an error waiting for the stream:");
+            println!("{}", e);
+            shared.exit(1);
+        }
+        println!("Stream is active.");
+    }
+
     Ok(())
 }
@@ -3,23 +3,19 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_kinesis::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::Listing;
+use alpha_common::CommonOpt;
+use aws_sdk_kinesis::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The name of the stream.
     #[structopt(short, long)]
     stream_name: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists your Amazon Kinesis data streams in the Region.
@@ -31,30 +27,16 @@ struct Opt {
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
     let Opt {
         stream_name,
-        region,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("Kinesis client version: {}", PKG_VERSION);
-        println!(
-            "Region:                 {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Stream name:            {}", &stream_name);
-        println!();
-    }
+    shared.banner("Kinesis", PKG_VERSION, &[("Stream name", &stream_name)]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.kinesis();
 
     let resp = client
         .describe_stream()
@@ -64,15 +46,32 @@ async fn main() -> Result<(), Error> {
 
     let desc = resp.stream_description.unwrap();
 
-    println!("Stream description:");
-    println!("  Name:              {}:", desc.stream_name.unwrap());
-    println!("  Status:            {:?}", desc.stream_status.unwrap());
-    println!("  Open shards:       {:?}", desc.shards.unwrap().len());
-    println!(
-        "  Retention (hours): {}",
-        desc.retention_period_hours.unwrap()
-    );
-    println!("  Encryption:        {:?}", desc.encryption_type.unwrap());
+    let mut listing = Listing::new(&[
+        "StreamName",
+        "StreamStatus",
+        "Shards",
+        "RetentionPeriodHours",
+        "EncryptionType",
+    ]);
+    listing.push(vec![
+        desc.stream_name.as_deref().into(),
+        desc.stream_status.as_ref().map(|s| s.as_str()).into(),
+        desc.shards.as_ref().map(Vec::len).into(),
+        desc.retention_period_hours.into(),
+        desc.encryption_type.as_ref().map(|e| e.as_str()).into(),
+    ]);
+
+    shared.print(&listing, || {
+        println!("Stream description:");
+        println!("  Name:              {}:", desc.stream_name.unwrap());
+        println!("  Status:            {:?}", desc.stream_status.unwrap());
+        println!("  Open shards:       {:?}", desc.shards.unwrap().len());
+        println!(
+            "  Retention (hours): {}",
+            desc.retention_period_hours.unwrap()
+        );
+        println!("  Encryption:        {:?}", desc.encryption_type.unwrap());
+    });
 
     Ok(())
 }
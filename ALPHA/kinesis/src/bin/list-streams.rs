@@ -3,54 +3,65 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_kinesis::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::Listing;
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_kinesis::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
-    let Opt { region, verbose } = Opt::from_args();
-
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
-
-    if verbose {
-        println!("Kinesis client version: {}", PKG_VERSION);
-        println!(
-            "Region:                 {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!();
-    }
+    let Opt { pages, common } = Opt::from_args();
+
+    let shared = common.load().await;
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    shared.banner("Kinesis", PKG_VERSION, &[]);
 
-    let resp = client.list_streams().send().await?;
+    let client = shared.kinesis();
 
-    println!("Stream names:");
+    // The next page starts after the last stream name in this one.
+    let streams = paginate(&pages, move |start, limit| {
+        let request = client
+            .list_streams()
+            .set_exclusive_start_stream_name(start)
+            .set_limit(limit);
+        async move {
+            request.send().await.map(|resp| {
+                let names = resp.stream_names.unwrap_or_default();
+                let next = match resp.has_more_streams {
+                    Some(true) => names.last().cloned(),
+                    _ => None,
+                };
+                Page::new(names, next)
+            })
+        }
+    })
+    .try_collect()
+    .await?;
 
-    let streams = resp.stream_names.unwrap_or_default();
+    let mut listing = Listing::new(&["StreamName"]);
     for stream in &streams {
-        println!("  {}", stream);
+        listing.push(vec![stream.as_str().into()]);
     }
 
-    println!("Found {} stream(s)", streams.len());
+    shared.print(&listing, || {
+        println!("Stream names:");
+
+        for stream in &streams {
+            println!("  {}", stream);
+        }
+
+        println!("Found {} stream(s)", streams.len());
+    });
 
     Ok(())
 }
@@ -3,16 +3,12 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_kinesis::{Blob, Client, Error, Region, PKG_VERSION};
+use alpha_common::CommonOpt;
+use aws_sdk_kinesis::{Blob, Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The data to add to the stream.
     #[structopt(short, long)]
     data: String,
@@ -25,9 +21,8 @@ struct Opt {
     #[structopt(short, long)]
     stream_name: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Adds a record to an Amazon Kinesis data stream.
@@ -42,38 +37,26 @@ struct Opt {
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
         data,
         key,
         stream_name,
-        region,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("Kinesis client version: {}", PKG_VERSION);
-        println!(
-            "Region:                 {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Data:");
-        println!();
-        println!("{}", &data);
-        println!();
-        println!("Partition key:          {}", &key);
-        println!("Stream name:            {}", &stream_name);
-        println!();
-    }
+    shared.banner(
+        "Kinesis",
+        PKG_VERSION,
+        &[
+            ("Data", &data),
+            ("Partition key", &key),
+            ("Stream name", &stream_name),
+        ],
+    );
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.kinesis();
 
     let blob = Blob::new(data);
 
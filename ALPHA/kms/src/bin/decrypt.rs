@@ -3,17 +3,13 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_kms::{Blob, Client, Error, Region, PKG_VERSION};
+use alpha_common::CommonOpt;
+use aws_sdk_kms::{Blob, Error, PKG_VERSION};
 use std::fs;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The encryption key.
     #[structopt(short, long)]
     key: String,
@@ -22,9 +18,8 @@ struct Opt {
     #[structopt(short, long)]
     input_file: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Decrypts a string encrypted by AWS KMS.
@@ -38,33 +33,17 @@ struct Opt {
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
         key,
         input_file,
-        region,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("KMS client version: {}", PKG_VERSION);
-        println!(
-            "Region:             {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Key:                {}", &key);
-        println!("Input:              {}", &input_file);
-        println!();
-    }
+    shared.banner("KMS", PKG_VERSION, &[("Key", &key), ("Input", &input_file)]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.kms();
 
     // Open input text file and get contents as a string
     // input is a base-64 encoded string, so decode it:
@@ -3,18 +3,14 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_kms::{Blob, Client, Error, Region, PKG_VERSION};
+use alpha_common::CommonOpt;
+use aws_sdk_kms::{Blob, Error, PKG_VERSION};
 use std::fs::File;
 use std::io::Write;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The encryption key.
     #[structopt(short, long)]
     key: String,
@@ -27,9 +23,8 @@ struct Opt {
     #[structopt(short, long)]
     out_file: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Encrypts a string using an AWS KMS key.
@@ -44,35 +39,22 @@ struct Opt {
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
         key,
         out_file,
-        region,
         text,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
-
-    if verbose {
-        println!("KMS client version: {}", PKG_VERSION);
-        println!(
-            "Region:             {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Key:                {}", &key);
-        println!("Text:               {}", &text);
-        println!("Output file:        {}", &out_file);
-        println!();
-    }
+    let shared = common.load().await;
+
+    shared.banner(
+        "KMS",
+        PKG_VERSION,
+        &[("Key", &key), ("Text", &text), ("Output file", &out_file)],
+    );
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.kms();
 
     let blob = Blob::new(text.as_bytes());
 
@@ -82,12 +64,12 @@ async fn main() -> Result<(), Error> {
     let blob = resp.ciphertext_blob.expect("Could not get encrypted text");
     let bytes = blob.as_ref();
 
-    let s = base64::encode(&bytes);
+    let s = base64::encode(bytes);
 
     let mut ofile = File::create(&out_file).expect("unable to create file");
     ofile.write_all(s.as_bytes()).expect("unable to write");
 
-    if verbose {
+    if shared.verbose() {
         println!("Wrote the following to {:?}", out_file);
         println!("{}", s);
     }
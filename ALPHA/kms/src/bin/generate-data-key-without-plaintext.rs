@@ -3,24 +3,19 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
+use alpha_common::CommonOpt;
 use aws_sdk_kms::model::DataKeySpec;
-use aws_sdk_kms::{Client, Error, Region, PKG_VERSION};
+use aws_sdk_kms::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The encryption key.
     #[structopt(short, long)]
     key: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Creates an AWS KMS data key without plaintext.
@@ -33,31 +28,13 @@ struct Opt {
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { key, common } = Opt::from_args();
 
-    let Opt {
-        key,
-        region,
-        verbose,
-    } = Opt::from_args();
-
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("KMS client version: {}", PKG_VERSION);
-        println!(
-            "Region:             {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("KMS key:            {}", &key);
-        println!();
-    }
+    shared.banner("KMS", PKG_VERSION, &[("KMS key", &key)]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.kms();
 
     let resp = client
         .generate_data_key_without_plaintext()
@@ -70,7 +47,7 @@ async fn main() -> Result<(), Error> {
     let blob = resp.ciphertext_blob.expect("Could not get encrypted text");
     let bytes = blob.as_ref();
 
-    let s = base64::encode(&bytes);
+    let s = base64::encode(bytes);
 
     println!();
     println!("Data key:");
@@ -3,24 +3,19 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_kms::{Client, Error, Region, PKG_VERSION};
+use alpha_common::CommonOpt;
+use aws_sdk_kms::{Error, PKG_VERSION};
 use std::process;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The # of bytes. Must be less than 1024.
     #[structopt(short, long)]
     length: i32,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Creates a random byte string that is cryptographically secure.
@@ -33,22 +28,13 @@ struct Opt {
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
-    let Opt {
-        length,
-        region,
-        verbose,
-    } = Opt::from_args();
+    let Opt { length, common } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
     // Trap out-of-range-values:
     match length {
-        1...1024 => {
+        1..=1024 => {
             println!("Generating a {} byte random string", length);
         }
         _ => {
@@ -57,20 +43,9 @@ async fn main() -> Result<(), Error> {
         }
     }
 
-    println!();
-
-    if verbose {
-        println!("KMS client version: {}", PKG_VERSION);
-        println!(
-            "Region:             {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Length:             {}", &length);
-        println!();
-    }
+    shared.banner("KMS", PKG_VERSION, &[("Length", &length)]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.kms();
 
     let resp = client
         .generate_random()
@@ -82,7 +57,7 @@ async fn main() -> Result<(), Error> {
     let blob = resp.plaintext.expect("Could not get encrypted text");
     let bytes = blob.as_ref();
 
-    let s = base64::encode(&bytes);
+    let s = base64::encode(bytes);
 
     println!();
     println!("Data key:");
@@ -3,23 +3,38 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
+use alpha_common::CommonOpt;
 use aws_hyper::StandardClient;
 use aws_sdk_kms::operation::GenerateRandom;
-use aws_sdk_kms::{Config, Region};
+use aws_sdk_kms::PKG_VERSION;
+use structopt::StructOpt;
 
-/// Creates a random byte string that is cryptographically secure in __us-east-1__.
+#[derive(Debug, StructOpt)]
+struct Opt {
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+/// Creates a random byte string that is cryptographically secure.
+/// # Arguments
+///
+/// * `[-r REGION]` - The Region in which the client is created.
+///    If not supplied, uses the value of the **AWS_REGION** environment variable.
+///    If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() {
-    let config = Config::builder()
-        // region can also be loaded from AWS_DEFAULT_REGION, just remove this line.
-        .region(Region::new("us-east-1"))
-        // creds loaded from environment variables, or they can be hard coded.
-        // Other credential providers not currently supported
-        .build();
+    let Opt { common } = Opt::from_args();
+
+    let shared = common.load().await;
+
+    shared.banner("KMS", PKG_VERSION, &[]);
+
+    let config = shared.kms_config().build();
     // NB: This example uses the "low level internal API" for demonstration purposes
     // This is sometimes necessary to get precise control over behavior, but in most cases
     // using `kms::Client` is recommended.
-    let client: StandardClient = aws_hyper::Client::https();
+    let client: StandardClient = aws_hyper::Client::new(shared.connector());
     let data = client
         .call(
             GenerateRandom::builder()
@@ -27,6 +42,7 @@ async fn main() {
                 .build()
                 .expect("valid operation")
                 .make_operation(&config)
+                .await
                 .expect("valid operation"),
         )
         .await
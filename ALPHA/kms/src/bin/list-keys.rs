@@ -3,62 +3,73 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_kms::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::Listing;
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_kms::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 /// Lists your AWS KMS keys in the Region.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { pages, common } = Opt::from_args();
 
-    let Opt { region, verbose } = Opt::from_args();
+    let shared = common.load().await;
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    shared.banner("KMS", PKG_VERSION, &[]);
 
-    if verbose {
-        println!("KMS client version: {}", PKG_VERSION);
-        println!(
-            "Region:             {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!();
-    }
-
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.kms();
 
-    let resp = client.list_keys().send().await?;
-
-    let keys = resp.keys.unwrap_or_default();
+    let keys = paginate(&pages, move |marker, limit| {
+        let request = client.list_keys().set_marker(marker).set_limit(limit);
+        async move {
+            request.send().await.map(|resp| {
+                let next = if resp.truncated {
+                    resp.next_marker
+                } else {
+                    None
+                };
+                Page::with_token(resp.keys.unwrap_or_default(), next)
+            })
+        }
+    })
+    .try_collect()
+    .await?;
 
     let len = keys.len();
 
-    for key in keys {
-        println!("Key ARN: {}", key.key_arn.as_deref().unwrap_or_default());
+    let mut listing = Listing::new(&["KeyId", "KeyArn"]);
+    for key in &keys {
+        listing.push(vec![
+            key.key_id.as_deref().into(),
+            key.key_arn.as_deref().into(),
+        ]);
     }
 
-    println!();
-    println!("Found {} keys", len);
+    shared.print(&listing, || {
+        for key in keys {
+            println!("Key ARN: {}", key.key_arn.as_deref().unwrap_or_default());
+        }
+
+        println!();
+        println!("Found {} keys", len);
+    });
 
     Ok(())
 }
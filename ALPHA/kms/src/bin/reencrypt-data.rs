@@ -3,8 +3,8 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_kms::{Blob, Client, Error, Region, PKG_VERSION};
+use alpha_common::CommonOpt;
+use aws_sdk_kms::{Blob, Error, PKG_VERSION};
 use std::fs;
 use std::fs::File;
 use std::io::Write;
@@ -12,10 +12,6 @@ use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The original encryption key.
     #[structopt(short, long)]
     first_key: String,
@@ -32,9 +28,8 @@ struct Opt {
     #[structopt(short, long)]
     output_file: String,
 
-    /// Whether to display additional runtime information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Re-encrypts a string with an AWS KMS key.
@@ -50,37 +45,28 @@ struct Opt {
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
         first_key,
         new_key,
         input_file,
         output_file,
-        region,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
-
-    if verbose {
-        println!("KMS client version:     {}", PKG_VERSION);
-        println!(
-            "Region:             {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Input key:              {}", &first_key);
-        println!("Output key:             {}", &new_key);
-        println!("Input filename:         {}", &input_file);
-        println!("Output filename:        {}", &output_file);
-        println!();
-    }
+    let shared = common.load().await;
+
+    shared.banner(
+        "KMS",
+        PKG_VERSION,
+        &[
+            ("Input key", &first_key),
+            ("Output key", &new_key),
+            ("Input filename", &input_file),
+            ("Output filename", &output_file),
+        ],
+    );
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.kms();
 
     // Get blob from input file
     // Open input text file and get contents as a string
@@ -101,13 +87,13 @@ async fn main() -> Result<(), Error> {
     let blob = resp.ciphertext_blob.expect("Could not get encrypted text");
     let bytes = blob.as_ref();
 
-    let s = base64::encode(&bytes);
+    let s = base64::encode(bytes);
     let o = &output_file;
 
     let mut ofile = File::create(o).expect("unable to create file");
     ofile.write_all(s.as_bytes()).expect("unable to write");
 
-    if verbose {
+    if shared.verbose() {
         println!("Wrote the following to {}:", output_file);
         println!("{}", s);
     } else {
@@ -0,0 +1,31 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::testing::Replay;
+use std::fs;
+
+#[test]
+fn encrypt_writes_ciphertext_as_base64() {
+    let out_file = std::env::temp_dir().join(format!("alpha-encrypt-{}.txt", std::process::id()));
+
+    let run = Replay::new(
+        env!("CARGO_BIN_EXE_encrypt"),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/encrypt.json"),
+    )
+    .args(["-k", "alias/alpha-test", "-t", "Hello, KMS!", "-o"])
+    .args([&out_file])
+    .run();
+
+    run.assert_success()
+        .assert_requests_match(&["content-type", "x-amz-target"]);
+    assert_eq!(
+        run.requests[0].json()["Plaintext"],
+        base64::encode("Hello, KMS!")
+    );
+
+    let written = fs::read_to_string(&out_file).expect("encrypt should write the output file");
+    fs::remove_file(&out_file).ok();
+    assert_eq!(written, "AQICAHhVb2pUZXN0Q2lwaGVydGV4dA==");
+}
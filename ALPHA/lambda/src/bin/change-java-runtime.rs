@@ -3,24 +3,19 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
+use alpha_common::CommonOpt;
 use aws_sdk_lambda::model::Runtime;
-use aws_sdk_lambda::{Client, Error, Region, PKG_VERSION};
+use aws_sdk_lambda::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The Lambda function's ARN.
     #[structopt(short, long)]
     arn: String,
 
-    /// Whether to display additional runtime information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Sets a Lambda function's Java runtime to Corretto.
@@ -33,31 +28,13 @@ struct Opt {
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { arn, common } = Opt::from_args();
 
-    let Opt {
-        arn,
-        region,
-        verbose,
-    } = Opt::from_args();
+    let shared = common.load().await;
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
-
-    if verbose {
-        println!("Lambda client version: {}", PKG_VERSION);
-        println!(
-            "Region:                {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Lambda function ARN:  {}", &arn);
-        println!();
-    }
+    shared.banner("Lambda", PKG_VERSION, &[("Lambda function ARN", &arn)]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.lambda();
 
     // Get function's runtime
     let resp = client.list_functions().send().await?;
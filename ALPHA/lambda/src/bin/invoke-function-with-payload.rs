@@ -3,16 +3,12 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_lambda::{Blob, Client, Error, Region, PKG_VERSION};
+use alpha_common::CommonOpt;
+use aws_sdk_lambda::{Blob, Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The AWS Lambda function's Amazon Resource Name (ARN).
     #[structopt(short, long)]
     arn: String,
@@ -21,9 +17,8 @@ struct Opt {
     #[structopt(short, long)]
     payload: String,
 
-    /// Whether to display additional runtime information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Invokes a Lambda function by its ARN.
@@ -39,28 +34,18 @@ async fn main() -> Result<(), Error> {
     let Opt {
         arn,
         payload,
-        region,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("Lambda client version: {}", PKG_VERSION);
-        println!(
-            "Region:                {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Lambda function ARN:   {}", arn);
-        println!("Payload:               {}", payload);
-        println!();
-    }
+    shared.banner(
+        "Lambda",
+        PKG_VERSION,
+        &[("Lambda function ARN", &arn), ("Payload", &payload)],
+    );
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.lambda();
 
     let blob = std::fs::read(payload).unwrap();
 
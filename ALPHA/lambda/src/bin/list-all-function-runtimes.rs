@@ -3,53 +3,89 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
+use alpha_common::output::Listing;
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::{CommonOpt, Shared};
+use aws_sdk_lambda::model::FunctionConfiguration;
 use aws_sdk_lambda::{Client, Error, Region, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region in which the client is created.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// Just show runtimes for indicated language.
     /// dotnet, go, node, java, etc.
     #[structopt(short, long)]
     language: Option<String>,
 
-    /// Whether to display additional runtime information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    pages: PageOpt,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists the ARNs and runtimes of all Lambda functions in all Regions.
-async fn show_lambdas(verbose: bool, language: &str, reg: &'static str) {
-    let region_provider = RegionProviderChain::default_provider().or_else(reg);
-    let config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&config);
-
-    let resp = client.list_functions().send().await;
-    let functions = resp.unwrap().functions.unwrap_or_default();
+// Gets the functions in the Region whose runtime matches `language`,
+// along with the total number of functions in the Region.
+async fn get_lambdas(
+    shared: &Shared,
+    pages: &PageOpt,
+    language: &str,
+    reg: &str,
+) -> (Vec<FunctionConfiguration>, usize) {
+    let config = shared
+        .lambda_config()
+        .region(Region::new(reg.to_string()))
+        .build();
+    let client = Client::from_conf(config);
+
+    let functions = paginate(pages, move |marker, max_items| {
+        let request = client
+            .list_functions()
+            .set_marker(marker)
+            .set_max_items(max_items);
+        async move {
+            request
+                .send()
+                .await
+                .map(|resp| Page::with_token(resp.functions.unwrap_or_default(), resp.next_marker))
+        }
+    })
+    .try_collect()
+    .await
+    .unwrap();
     let max_functions = functions.len();
-    let mut num_functions = 0;
+
+    let functions = functions
+        .into_iter()
+        .filter(|function| {
+            let rt_str = function.runtime.as_ref().unwrap().as_str();
+            // If language is set (!= ""), show only those with that runtime.
+            rt_str
+                .to_ascii_lowercase()
+                .contains(&language.to_ascii_lowercase())
+                || language.is_empty()
+        })
+        .collect();
+
+    (functions, max_functions)
+}
+
+fn show_lambdas(
+    shared: &Shared,
+    reg: &str,
+    functions: Vec<FunctionConfiguration>,
+    max_functions: usize,
+) {
+    let num_functions = functions.len();
 
     for function in functions {
-        let rt_str: String = String::from(function.runtime.unwrap().as_ref());
-        // If language is set (!= ""), show only those with that runtime.
-        let ok = rt_str
-            .to_ascii_lowercase()
-            .contains(&language.to_ascii_lowercase());
-        if ok || language.is_empty() {
-            println!("  ARN:     {}", function.function_arn.unwrap());
-            println!("  Runtime: {}", rt_str);
-            println!();
-
-            num_functions += 1;
-        }
+        println!("  ARN:     {}", function.function_arn.unwrap());
+        println!("  Runtime: {}", function.runtime.unwrap().as_ref());
+        println!();
     }
 
-    if num_functions > 0 || verbose {
+    if num_functions > 0 || shared.verbose() {
         println!(
             "Found {} function(s) (out of {}) in {} region.",
             num_functions, max_functions, reg
@@ -61,46 +97,64 @@ async fn show_lambdas(verbose: bool, language: &str, reg: &'static str) {
 /// Lists the ARNs and runtimes of your Lambda functions in all available regions.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list in each Region.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
         language,
-        region,
-        verbose,
+        pages,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("EC2 client version:    {}", aws_sdk_ec2::PKG_VERSION);
-        println!("Lambda client version: {}", PKG_VERSION);
-        println!(
-            "Region:                {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!();
-    }
+    shared.banner(
+        "Lambda",
+        PKG_VERSION,
+        &[("EC2 client version", &aws_sdk_ec2::PKG_VERSION)],
+    );
 
     // Get list of available regions.
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let ec2_client = aws_sdk_ec2::Client::new(&shared_config);
+    let ec2_client = shared.ec2();
 
     let resp = ec2_client.describe_regions().send().await;
 
+    let mut by_region = Vec::new();
     for region in resp.unwrap().regions.unwrap_or_default() {
-        let reg: &'static str = Box::leak(region.region_name.unwrap().into_boxed_str());
-        show_lambdas(verbose, language.as_deref().unwrap_or_default(), reg).await;
+        let reg = region.region_name.unwrap();
+        let (functions, max_functions) = get_lambdas(
+            &shared,
+            &pages,
+            language.as_deref().unwrap_or_default(),
+            &reg,
+        )
+        .await;
+        by_region.push((reg, functions, max_functions));
     }
 
+    let mut listing = Listing::new(&["Region", "FunctionName", "FunctionArn", "Runtime"]);
+    for (reg, functions, _) in &by_region {
+        for function in functions {
+            listing.push(vec![
+                reg.as_str().into(),
+                function.function_name.as_deref().into(),
+                function.function_arn.as_deref().into(),
+                function.runtime.as_ref().map(|r| r.as_str()).into(),
+            ]);
+        }
+    }
+
+    shared.print(&listing, || {
+        for (reg, functions, max_functions) in by_region {
+            show_lambdas(&shared, &reg, functions, max_functions);
+        }
+    });
+
     Ok(())
 }
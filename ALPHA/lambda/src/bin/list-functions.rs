@@ -3,64 +3,75 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_lambda::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::Listing;
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_lambda::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional runtime information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists the Amazon Resource Names (ARNs) of your AWS Lambda functions in the Region.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { pages, common } = Opt::from_args();
 
-    let Opt { region, verbose } = Opt::from_args();
+    let shared = common.load().await;
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    shared.banner("Lambda", PKG_VERSION, &[]);
 
-    if verbose {
-        println!("Lambda client version: {}", PKG_VERSION);
-        println!(
-            "Region:                {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!();
-    }
-
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
-
-    let resp = client.list_functions().send().await?;
+    let client = shared.lambda();
 
-    println!("Function ARNs:");
-
-    let functions = resp.functions.unwrap_or_default();
+    let functions = paginate(&pages, move |marker, max_items| {
+        let request = client
+            .list_functions()
+            .set_marker(marker)
+            .set_max_items(max_items);
+        async move {
+            request
+                .send()
+                .await
+                .map(|resp| Page::with_token(resp.functions.unwrap_or_default(), resp.next_marker))
+        }
+    })
+    .try_collect()
+    .await?;
     let num_funcs = functions.len();
 
-    for function in functions {
-        println!("{}", function.function_arn.unwrap_or_default());
+    let mut listing = Listing::new(&["FunctionName", "FunctionArn", "Runtime"]);
+    for function in &functions {
+        listing.push(vec![
+            function.function_name.as_deref().into(),
+            function.function_arn.as_deref().into(),
+            function.runtime.as_ref().map(|r| r.as_str()).into(),
+        ]);
     }
 
-    println!();
-    println!("Found {} functions in the region", num_funcs);
+    shared.print(&listing, || {
+        println!("Function ARNs:");
+
+        for function in functions {
+            println!("{}", function.function_arn.unwrap_or_default());
+        }
+
+        println!();
+        println!("Found {} functions in the region", num_funcs);
+    });
 
     Ok(())
 }
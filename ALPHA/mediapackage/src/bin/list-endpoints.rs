@@ -3,62 +3,74 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_mediapackage::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::Listing;
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_mediapackage::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists your AWS Elemental MediaPackage endpoint descriptions and URLs in the Region.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { pages, common } = Opt::from_args();
 
-    let Opt { region, verbose } = Opt::from_args();
+    let shared = common.load().await;
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    shared.banner("MediaPackage", PKG_VERSION, &[]);
 
-    if verbose {
-        println!("MediaPackage client version: {}", PKG_VERSION);
-        println!(
-            "Region:                      {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!();
-    }
+    let client = shared.mediapackage();
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let endpoints = paginate(&pages, move |token, max_results| {
+        let request = client
+            .list_origin_endpoints()
+            .set_next_token(token)
+            .set_max_results(max_results);
+        async move {
+            request.send().await.map(|resp| {
+                Page::with_token(resp.origin_endpoints.unwrap_or_default(), resp.next_token)
+            })
+        }
+    })
+    .try_collect()
+    .await?;
 
-    let or_endpoints = client.list_origin_endpoints().send().await?;
+    let mut listing = Listing::new(&["Id", "Description", "Url"]);
+    for e in &endpoints {
+        listing.push(vec![
+            e.id.as_deref().into(),
+            e.description.as_deref().into(),
+            e.url.as_deref().into(),
+        ]);
+    }
 
-    println!("Endpoints:");
+    shared.print(&listing, || {
+        println!("Endpoints:");
 
-    for e in or_endpoints.origin_endpoints.unwrap_or_default() {
-        let endpoint_url = e.url.as_deref().unwrap_or_default();
-        let endpoint_description = e.description.as_deref().unwrap_or_default();
-        println!("  Description: {}", endpoint_description);
-        println!("  URL :        {}", endpoint_url);
-        println!();
-    }
+        for e in &endpoints {
+            let endpoint_url = e.url.as_deref().unwrap_or_default();
+            let endpoint_description = e.description.as_deref().unwrap_or_default();
+            println!("  Description: {}", endpoint_description);
+            println!("  URL :        {}", endpoint_url);
+            println!();
+        }
+    });
 
     Ok(())
 }
@@ -3,70 +3,81 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_polly::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::Listing;
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_polly::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to isplay additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Displays a list of the voices in the Region.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { pages, common } = Opt::from_args();
 
-    let Opt { region, verbose } = Opt::from_args();
+    let shared = common.load().await;
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    println!();
+    shared.banner("Polly", PKG_VERSION, &[]);
 
-    if verbose {
-        println!("Polly client version: {}", PKG_VERSION);
-        println!(
-            "Region:               {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!();
-    }
+    let client = shared.polly();
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    // DescribeVoices has no page size.
+    let voices = paginate(&pages, move |token, _| {
+        let request = client.describe_voices().set_next_token(token);
+        async move {
+            request
+                .send()
+                .await
+                .map(|resp| Page::with_token(resp.voices.unwrap_or_default(), resp.next_token))
+        }
+    })
+    .try_collect()
+    .await?;
 
-    let resp = client.describe_voices().send().await?;
+    let mut listing = Listing::new(&["Id", "Name", "LanguageName", "Gender"]);
+    for voice in &voices {
+        listing.push(vec![
+            voice.id.as_ref().map(|id| id.as_str()).into(),
+            voice.name.as_deref().into(),
+            voice.language_name.as_deref().into(),
+            voice.gender.as_ref().map(|g| g.as_str()).into(),
+        ]);
+    }
 
-    println!("Voices:");
+    shared.print(&listing, || {
+        println!("Voices:");
 
-    let voices = resp.voices.unwrap_or_default();
-    for voice in &voices {
-        println!(
-            "  Name:     {}",
-            voice.name.as_deref().unwrap_or("No name!")
-        );
-        println!(
-            "  Language: {}",
-            voice.language_name.as_deref().unwrap_or("No language!")
-        );
+        for voice in &voices {
+            println!(
+                "  Name:     {}",
+                voice.name.as_deref().unwrap_or("No name!")
+            );
+            println!(
+                "  Language: {}",
+                voice.language_name.as_deref().unwrap_or("No language!")
+            );
 
-        println!();
-    }
+            println!();
+        }
 
-    println!("Found {} voices", voices.len());
+        println!("Found {} voices", voices.len());
+    });
 
     Ok(())
 }
@@ -3,79 +3,91 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_polly::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::Listing;
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_polly::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Displays a list of the lexicons in the Region.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { pages, common } = Opt::from_args();
 
-    let Opt { region, verbose } = Opt::from_args();
+    let shared = common.load().await;
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
+    shared.banner("Polly", PKG_VERSION, &[]);
 
-    println!();
+    let client = shared.polly();
 
-    if verbose {
-        println!("Polly client version: {}", PKG_VERSION);
-        println!(
-            "Region:               {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!();
-    }
-
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    // ListLexicons has no page size.
+    let lexicons = paginate(&pages, move |token, _| {
+        let request = client.list_lexicons().set_next_token(token);
+        async move {
+            request
+                .send()
+                .await
+                .map(|resp| Page::with_token(resp.lexicons.unwrap_or_default(), resp.next_token))
+        }
+    })
+    .try_collect()
+    .await?;
 
-    let resp = client.list_lexicons().send().await?;
-
-    println!("Lexicons:");
+    let mut listing = Listing::new(&["Name", "LanguageCode", "LexemesCount"]);
+    for lexicon in &lexicons {
+        let attributes = lexicon.attributes.as_ref();
+        listing.push(vec![
+            lexicon.name.as_deref().into(),
+            attributes
+                .and_then(|attrib| attrib.language_code.as_ref())
+                .map(|code| code.as_str())
+                .into(),
+            attributes.map(|attrib| attrib.lexemes_count).into(),
+        ]);
+    }
 
-    let lexicons = resp.lexicons.unwrap_or_default();
+    shared.print(&listing, || {
+        println!("Lexicons:");
 
-    for lexicon in &lexicons {
-        println!(
-            "  Name:     {}",
-            lexicon.name.as_deref().unwrap_or_default()
-        );
-        println!(
-            "  Language: {:?}\n",
-            lexicon
-                .attributes
-                .as_ref()
-                .map(|attrib| attrib
-                    .language_code
+        for lexicon in &lexicons {
+            println!(
+                "  Name:     {}",
+                lexicon.name.as_deref().unwrap_or_default()
+            );
+            println!(
+                "  Language: {:?}\n",
+                lexicon
+                    .attributes
                     .as_ref()
-                    .expect("languages must have language codes"))
-                .expect("languages must have attributes")
-        );
-    }
+                    .map(|attrib| attrib
+                        .language_code
+                        .as_ref()
+                        .expect("languages must have language codes"))
+                    .expect("languages must have attributes")
+            );
+        }
 
-    println!();
-    println!("Found {} lexicons.", lexicons.len());
-    println!();
+        println!();
+        println!("Found {} lexicons.", lexicons.len());
+        println!();
+    });
 
     Ok(())
 }
@@ -3,16 +3,12 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_polly::{Client, Error, Region, PKG_VERSION};
+use alpha_common::CommonOpt;
+use aws_sdk_polly::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The name of the lexicon.
     #[structopt(short, long)]
     name: String,
@@ -25,9 +21,8 @@ struct Opt {
     #[structopt(short, long)]
     to: String,
 
-    /// Whether to show additional output.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Stores a pronunciation lexicon in a Region.
@@ -42,36 +37,26 @@ struct Opt {
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
         from,
         name,
-        region,
         to,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("Polly client version:    {}", PKG_VERSION);
-        println!(
-            "Region:               {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Lexicon name:            {}", &name);
-        println!("Text to replace:         {}", &from);
-        println!("Replacement text:        {}", &to);
-        println!();
-    }
+    shared.banner(
+        "Polly",
+        PKG_VERSION,
+        &[
+            ("Lexicon name", &name),
+            ("Text to replace", &from),
+            ("Replacement text", &to),
+        ],
+    );
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.polly();
 
     let content = format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>
     <lexicon version=\"1.0\" xmlns=\"http://www.w3.org/2005/01/pronunciation-lexicon\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\"
@@ -3,26 +3,21 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
+use alpha_common::CommonOpt;
 use aws_sdk_polly::model::{OutputFormat, VoiceId};
-use aws_sdk_polly::{Client, Error, Region, PKG_VERSION};
+use aws_sdk_polly::{Error, PKG_VERSION};
 use std::fs;
 use structopt::StructOpt;
 use tokio::io::AsyncWriteExt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The file containing the text to synthesize.
     #[structopt(short, long)]
     filename: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Synthesizes UTF-8 input, plain text or SSML, to a stream of bytes in a file.
@@ -36,32 +31,13 @@ struct Opt {
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
-    let Opt {
-        filename,
-        region,
-        verbose,
-    } = Opt::from_args();
-
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
+    let Opt { filename, common } = Opt::from_args();
 
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("Polly client version: {}", PKG_VERSION);
-        println!(
-            "Region:               {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Filename:             {}", &filename);
-        println!();
-    }
+    shared.banner("Polly", PKG_VERSION, &[("Filename", &filename)]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.polly();
 
     let content = fs::read_to_string(&filename);
 
@@ -3,70 +3,67 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
+use alpha_common::waiter::{qldb, WaitOpt};
+use alpha_common::CommonOpt;
 use aws_sdk_qldb::model::PermissionsMode;
-use aws_sdk_qldb::{Client, Error, Region, PKG_VERSION};
+use aws_sdk_qldb::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The name of the ledger.
     #[structopt(short, long)]
     ledger: String,
 
-    /// Whether to display additional runtime information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    wait: WaitOpt,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Creates an Amazon Quantum Ledger Database (Amazon QLDB) ledger in the Region.
 /// # Arguments
 ///
 /// * `-l LEDGER` - The name of the ledger.
+/// * `[--wait]` - Whether to wait until the ledger is active.
+/// * `[--max-wait SECONDS]` - The most seconds to wait. Defaults to 600.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
         ledger,
-        region,
-        verbose,
+        wait,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
+    let shared = common.load().await;
 
-    println!();
+    shared.banner("QLDB", PKG_VERSION, &[]);
 
-    if verbose {
-        println!("QLDB client version: {}", PKG_VERSION);
-        println!(
-            "Region:              {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!();
-    }
-
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.qldb();
 
     let result = client
         .create_ledger()
-        .name(ledger)
+        .name(&ledger)
         .permissions_mode(PermissionsMode::AllowAll)
         .send()
         .await?;
 
     println!("ARN: {}", result.arn.unwrap());
 
+    if let Some(waiter) = wait.waiter() {
+        println!("Waiting for the ledger to be active.");
+        if let Err(e) = qldb::ledger_active(&waiter, &client, &ledger).await {
+            println!("Got an error waiting for the ledger:");
+            println!("{}", e);
+            shared.exit(1);
+        }
+        println!("Ledger is active.");
+    }
+
     Ok(())
 }
@@ -3,63 +3,69 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_qldb::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::{date_time, Listing};
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_qldb::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists your Amazon Quantum Ledger Database (Amazon QLDB) ledgers.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { pages, common } = Opt::from_args();
 
-    let Opt { region, verbose } = Opt::from_args();
+    let shared = common.load().await;
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
+    shared.banner("OLDB", PKG_VERSION, &[]);
 
-    println!();
+    let client = shared.qldb();
 
-    if verbose {
-        println!("OLDB client version: {}", PKG_VERSION);
-        println!(
-            "Region:              {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!();
-    }
-
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let ledgers = paginate(&pages, move |token, max_results| {
+        let request = client
+            .list_ledgers()
+            .set_next_token(token)
+            .set_max_results(max_results);
+        async move {
+            request
+                .send()
+                .await
+                .map(|resp| Page::with_token(resp.ledgers.unwrap_or_default(), resp.next_token))
+        }
+    })
+    .try_collect()
+    .await?;
 
-    let result = client.list_ledgers().send().await?;
+    let mut listing = Listing::new(&["Name", "State", "CreationDateTime"]);
+    for ledger in &ledgers {
+        listing.push(vec![
+            ledger.name.as_deref().into(),
+            ledger.state.as_ref().map(|s| s.as_str()).into(),
+            date_time(ledger.creation_date_time.as_ref()),
+        ]);
+    }
 
-    if let Some(ledgers) = result.ledgers {
+    shared.print(&listing, || {
         for ledger in ledgers {
             println!("* {:?}", ledger);
         }
-
-        if result.next_token.is_some() {
-            todo!("pagination is not yet demonstrated")
-        }
-    }
+    });
 
     Ok(())
 }
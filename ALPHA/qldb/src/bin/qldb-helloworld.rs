@@ -3,24 +3,19 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
+use alpha_common::CommonOpt;
 use aws_sdk_qldbsession::model::StartSessionRequest;
-use aws_sdk_qldbsession::{Client, Error, Region, PKG_VERSION};
+use aws_sdk_qldbsession::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The name of the ledger.
     #[structopt(short, long)]
     ledger: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Creates a low-level Amazon Quantum Ledger Database (Amazon QLDB) session in the Region.
@@ -33,32 +28,13 @@ struct Opt {
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
-    let Opt {
-        ledger,
-        region,
-        verbose,
-    } = Opt::from_args();
-
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
+    let Opt { ledger, common } = Opt::from_args();
 
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("OLDB client version: {}", PKG_VERSION);
-        println!(
-            "Region:              {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Ledger:              {}", ledger);
-        println!();
-    }
+    shared.banner("OLDB", PKG_VERSION, &[("Ledger", &ledger)]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.qldbsession();
 
     let result = client
         .send_command()
@@ -3,16 +3,17 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_rdsdata::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::Listing;
+use alpha_common::CommonOpt;
+use aws_sdk_rdsdata::model::{ArrayValue, Field};
+use aws_sdk_rdsdata::output::ExecuteStatementOutput;
+use aws_sdk_rdsdata::{Error, PKG_VERSION};
+use aws_smithy_types::base64;
+use serde_json::Value;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The SQL query string.
     #[structopt(short, long)]
     query: String,
@@ -25,70 +26,107 @@ struct Opt {
     #[structopt(short, long)]
     secret_arn: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Sends a query to an Aurora serverless cluster in the Region.
 /// # Arguments
 ///
 /// * `-q QUERY` - The SQL query to run against the cluster.
-///    It should look something like: __"SELECT * FROM pg_catalog.pg_tables limit 1"__.
-///    Don't forget you'll likely have to escape some characters.
+///   It should look something like: __"SELECT * FROM pg_catalog.pg_tables limit 1"__.
+///   Don't forget you'll likely have to escape some characters.
 /// * `-c CLUSTER_ARN` - The ARN of your Aurora Serverless DB cluster.
-///    It should look something like __arn:aws:rds:us-west-2:AWS_ACCOUNT:cluster:database-2__.
+///   It should look something like __arn:aws:rds:us-west-2:AWS_ACCOUNT:cluster:database-2__.
 /// * `-s SECRET_ARN` - The ARN of the Secrets Manager secret.
-///    It should look something like: __arn:aws:secretsmanager:us-west-2:AWS_ACCOUNT:secret:database2/test/postgres-b8maVb__.
+///   It should look something like: __arn:aws:secretsmanager:us-west-2:AWS_ACCOUNT:secret:database2/test/postgres-b8maVb__.
 /// * `[-r REGION]` - The Region in which the client is created.
-///    If not supplied, uses the value of the **AWS_REGION** environment variable.
-///    If the environment variable is not set, defaults to **us-west-2**.
+///   If not supplied, uses the value of the **AWS_REGION** environment variable.
+///   If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
+// Converts a field of a result row to the JSON value it holds.
+fn field_json(field: &Field) -> Value {
+    match field {
+        Field::ArrayValue(array) => array_json(array),
+        Field::BlobValue(b) => base64::encode(b.as_ref()).into(),
+        Field::BooleanValue(b) => (*b).into(),
+        Field::DoubleValue(d) => (*d).into(),
+        Field::LongValue(l) => (*l).into(),
+        Field::StringValue(s) => s.as_str().into(),
+        _ => Value::Null,
+    }
+}
+
+fn array_json(array: &ArrayValue) -> Value {
+    match array {
+        ArrayValue::ArrayValues(arrays) => arrays.iter().map(array_json).collect(),
+        ArrayValue::BooleanValues(values) => values.clone().into(),
+        ArrayValue::DoubleValues(values) => values.clone().into(),
+        ArrayValue::LongValues(values) => values.clone().into(),
+        ArrayValue::StringValues(values) => values.clone().into(),
+        _ => Value::Null,
+    }
+}
+
+// One row per record, with the columns named by the result metadata.
+fn to_listing(result: &ExecuteStatementOutput) -> Listing {
+    let records = result.records.as_deref().unwrap_or_default();
+    let names: Vec<String> = match &result.column_metadata {
+        Some(columns) => columns
+            .iter()
+            .enumerate()
+            .map(|(i, c)| c.name.clone().unwrap_or_else(|| format!("Column{}", i + 1)))
+            .collect(),
+        None => (1..=records.first().map(Vec::len).unwrap_or_default())
+            .map(|i| format!("Column{}", i))
+            .collect(),
+    };
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+
+    let mut listing = Listing::new(&names);
+    for record in records {
+        listing.push(record.iter().map(field_json).collect());
+    }
+    listing
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
         cluster_arn,
         query,
-        region,
         secret_arn,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-
-    println!();
-
-    if verbose {
-        println!("RDS data client version: {}", PKG_VERSION);
-        println!(
-            "Region:                  {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Cluster ARN:             {}", &cluster_arn);
-        println!("Secrets ARN:             {}", &secret_arn);
-        println!("Query:");
-        println!("  {}", &query);
-        println!();
-    }
+    let shared = common.load().await;
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    shared.banner(
+        "RDS data",
+        PKG_VERSION,
+        &[
+            ("Cluster ARN", &cluster_arn),
+            ("Secrets ARN", &secret_arn),
+            ("Query", &query),
+        ],
+    );
+
+    let client = shared.rdsdata();
 
     let st = client
         .execute_statement()
         .resource_arn(cluster_arn)
         .database("postgres") // Do not confuse this with db instance name
         .sql(query)
-        .secret_arn(secret_arn);
+        .secret_arn(secret_arn)
+        .include_result_metadata(true);
 
     let result = st.send().await?;
 
-    println!("{:?}", result);
-    println!();
+    shared.print(&to_listing(&result), || {
+        println!("{:?}", result);
+        println!();
+    });
 
     Ok(())
 }
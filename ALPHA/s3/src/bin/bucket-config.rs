@@ -0,0 +1,348 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use std::path::PathBuf;
+
+use alpha_common::{CommonOpt, Shared};
+use aws_sdk_s3::model::{BucketVersioningStatus, VersioningConfiguration};
+use aws_sdk_s3::{Client, Error, PKG_VERSION};
+use s3_code_examples::bucket_config::{
+    read_json, unless_missing, CorsJson, EncryptionJson, LifecycleJson, TaggingJson,
+};
+use serde::Serialize;
+use serde_json::Value;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The name of the bucket.
+    #[structopt(short, long)]
+    bucket: String,
+
+    #[structopt(subcommand)]
+    command: Command,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Shows, turns on, or suspends versioning.
+    Versioning {
+        /// Turn versioning on.
+        #[structopt(long, conflicts_with = "suspend")]
+        enable: bool,
+
+        /// Suspend versioning. Existing versions are kept.
+        #[structopt(long)]
+        suspend: bool,
+    },
+
+    /// Shows or sets default encryption.
+    Encryption(FileOpt),
+
+    /// Shows or sets lifecycle rules.
+    Lifecycle(FileOpt),
+
+    /// Shows or sets CORS rules.
+    Cors(FileOpt),
+
+    /// Shows or sets tags.
+    Tagging(FileOpt),
+
+    /// Shows or sets the bucket policy.
+    Policy(FileOpt),
+}
+
+#[derive(Debug, StructOpt)]
+struct FileOpt {
+    /// A JSON file with the configuration to set, in the form it is shown in.
+    #[structopt(short, long, parse(from_os_str), conflicts_with = "remove")]
+    file: Option<PathBuf>,
+
+    /// Remove the configuration.
+    #[structopt(long)]
+    remove: bool,
+}
+
+fn show(json: &impl Serialize) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(json).expect("configurations serialize")
+    );
+}
+
+fn read<T: serde::de::DeserializeOwned>(shared: &Shared, path: &std::path::Path) -> T {
+    match read_json(path) {
+        Ok(value) => value,
+        Err(e) => {
+            println!("Got an error reading the configuration:");
+            println!("{}", e);
+            shared.exit(1);
+        }
+    }
+}
+
+async fn versioning(
+    client: &Client,
+    bucket: &str,
+    enable: bool,
+    suspend: bool,
+) -> Result<(), Error> {
+    if enable || suspend {
+        let status = if enable {
+            BucketVersioningStatus::Enabled
+        } else {
+            BucketVersioningStatus::Suspended
+        };
+        let config = VersioningConfiguration::builder()
+            .status(status.clone())
+            .build();
+        client
+            .put_bucket_versioning()
+            .bucket(bucket)
+            .versioning_configuration(config)
+            .send()
+            .await?;
+        println!("Versioning for {} is {}", bucket, status.as_str());
+    } else {
+        let resp = client.get_bucket_versioning().bucket(bucket).send().await?;
+        println!(
+            "Versioning for {} is {}",
+            bucket,
+            resp.status.as_ref().map_or("never enabled", |s| s.as_str())
+        );
+    }
+    Ok(())
+}
+
+async fn encryption(
+    shared: &Shared,
+    client: &Client,
+    bucket: &str,
+    opt: FileOpt,
+) -> Result<(), Error> {
+    if opt.remove {
+        client
+            .delete_bucket_encryption()
+            .bucket(bucket)
+            .send()
+            .await?;
+        println!("Removed default encryption from {}", bucket);
+        return Ok(());
+    }
+    if let Some(file) = opt.file {
+        let json: EncryptionJson = read(shared, &file);
+        let config = match json.to_sdk() {
+            Ok(config) => config,
+            Err(e) => {
+                println!("Got an error reading the configuration:");
+                println!("{}", e);
+                shared.exit(1);
+            }
+        };
+        client
+            .put_bucket_encryption()
+            .bucket(bucket)
+            .server_side_encryption_configuration(config)
+            .send()
+            .await?;
+        println!("Set default encryption for {}", bucket);
+        return Ok(());
+    }
+    let resp = unless_missing(
+        client.get_bucket_encryption().bucket(bucket).send().await,
+        "ServerSideEncryptionConfigurationNotFoundError",
+    )?;
+    match resp.and_then(|r| r.server_side_encryption_configuration) {
+        Some(config) => show(&EncryptionJson::from_sdk(&config.rules.unwrap_or_default())),
+        None => println!("{} has no default encryption", bucket),
+    }
+    Ok(())
+}
+
+async fn lifecycle(
+    shared: &Shared,
+    client: &Client,
+    bucket: &str,
+    opt: FileOpt,
+) -> Result<(), Error> {
+    if opt.remove {
+        client
+            .delete_bucket_lifecycle()
+            .bucket(bucket)
+            .send()
+            .await?;
+        println!("Removed the lifecycle rules of {}", bucket);
+        return Ok(());
+    }
+    if let Some(file) = opt.file {
+        let json: LifecycleJson = read(shared, &file);
+        let config = match json.to_sdk() {
+            Ok(config) => config,
+            Err(e) => {
+                println!("Got an error reading the configuration:");
+                println!("{}", e);
+                shared.exit(1);
+            }
+        };
+        client
+            .put_bucket_lifecycle_configuration()
+            .bucket(bucket)
+            .lifecycle_configuration(config)
+            .send()
+            .await?;
+        println!("Set {} lifecycle rules for {}", json.rules.len(), bucket);
+        return Ok(());
+    }
+    let resp = unless_missing(
+        client
+            .get_bucket_lifecycle_configuration()
+            .bucket(bucket)
+            .send()
+            .await,
+        "NoSuchLifecycleConfiguration",
+    )?;
+    match resp {
+        Some(resp) => show(&LifecycleJson::from_sdk(&resp.rules.unwrap_or_default())),
+        None => println!("{} has no lifecycle rules", bucket),
+    }
+    Ok(())
+}
+
+async fn cors(shared: &Shared, client: &Client, bucket: &str, opt: FileOpt) -> Result<(), Error> {
+    if opt.remove {
+        client.delete_bucket_cors().bucket(bucket).send().await?;
+        println!("Removed the CORS rules of {}", bucket);
+        return Ok(());
+    }
+    if let Some(file) = opt.file {
+        let json: CorsJson = read(shared, &file);
+        client
+            .put_bucket_cors()
+            .bucket(bucket)
+            .cors_configuration(json.to_sdk())
+            .send()
+            .await?;
+        println!("Set {} CORS rules for {}", json.cors_rules.len(), bucket);
+        return Ok(());
+    }
+    let resp = unless_missing(
+        client.get_bucket_cors().bucket(bucket).send().await,
+        "NoSuchCORSConfiguration",
+    )?;
+    match resp {
+        Some(resp) => show(&CorsJson::from_sdk(&resp.cors_rules.unwrap_or_default())),
+        None => println!("{} has no CORS rules", bucket),
+    }
+    Ok(())
+}
+
+async fn tagging(
+    shared: &Shared,
+    client: &Client,
+    bucket: &str,
+    opt: FileOpt,
+) -> Result<(), Error> {
+    if opt.remove {
+        client.delete_bucket_tagging().bucket(bucket).send().await?;
+        println!("Removed the tags of {}", bucket);
+        return Ok(());
+    }
+    if let Some(file) = opt.file {
+        let json: TaggingJson = read(shared, &file);
+        client
+            .put_bucket_tagging()
+            .bucket(bucket)
+            .tagging(json.to_sdk())
+            .send()
+            .await?;
+        println!("Set {} tags for {}", json.tag_set.len(), bucket);
+        return Ok(());
+    }
+    let resp = unless_missing(
+        client.get_bucket_tagging().bucket(bucket).send().await,
+        "NoSuchTagSet",
+    )?;
+    match resp {
+        Some(resp) => show(&TaggingJson::from_sdk(&resp.tag_set.unwrap_or_default())),
+        None => println!("{} has no tags", bucket),
+    }
+    Ok(())
+}
+
+async fn policy(shared: &Shared, client: &Client, bucket: &str, opt: FileOpt) -> Result<(), Error> {
+    if opt.remove {
+        client.delete_bucket_policy().bucket(bucket).send().await?;
+        println!("Removed the policy of {}", bucket);
+        return Ok(());
+    }
+    if let Some(file) = opt.file {
+        let json: Value = read(shared, &file);
+        client
+            .put_bucket_policy()
+            .bucket(bucket)
+            .policy(json.to_string())
+            .send()
+            .await?;
+        println!("Set the policy for {}", bucket);
+        return Ok(());
+    }
+    let resp = unless_missing(
+        client.get_bucket_policy().bucket(bucket).send().await,
+        "NoSuchBucketPolicy",
+    )?;
+    match resp.and_then(|r| r.policy) {
+        Some(policy) => match serde_json::from_str::<Value>(&policy) {
+            Ok(json) => show(&json),
+            Err(_) => println!("{}", policy),
+        },
+        None => println!("{} has no policy", bucket),
+    }
+    Ok(())
+}
+
+/// Shows or changes the versioning, default encryption, lifecycle rules,
+/// CORS rules, tags, or policy of an Amazon S3 bucket.
+/// Configurations are read from and shown as JSON in the same form as the AWS CLI's.
+/// # Arguments
+///
+/// * `-b BUCKET` - The name of the bucket.
+/// * `versioning [--enable | --suspend]` - Shows, turns on, or suspends versioning.
+/// * `encryption [-f FILE | --remove]` - Shows or sets default encryption.
+/// * `lifecycle [-f FILE | --remove]` - Shows or sets lifecycle rules.
+/// * `cors [-f FILE | --remove]` - Shows or sets CORS rules.
+/// * `tagging [-f FILE | --remove]` - Shows or sets tags.
+/// * `policy [-f FILE | --remove]` - Shows or sets the bucket policy.
+/// * `[-r REGION]` - The Region in which the client is created.
+///   If not supplied, uses the value of the **AWS_REGION** environment variable.
+///   If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt {
+        bucket,
+        command,
+        common,
+    } = Opt::from_args();
+
+    let shared = common.load().await;
+
+    shared.banner("S3", PKG_VERSION, &[("Bucket", &bucket)]);
+
+    let client = shared.s3();
+
+    match command {
+        Command::Versioning { enable, suspend } => {
+            versioning(&client, &bucket, enable, suspend).await
+        }
+        Command::Encryption(opt) => encryption(&shared, &client, &bucket, opt).await,
+        Command::Lifecycle(opt) => lifecycle(&shared, &client, &bucket, opt).await,
+        Command::Cors(opt) => cors(&shared, &client, &bucket, opt).await,
+        Command::Tagging(opt) => tagging(&shared, &client, &bucket, opt).await,
+        Command::Policy(opt) => policy(&shared, &client, &bucket, opt).await,
+    }
+}
@@ -3,24 +3,19 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
+use alpha_common::CommonOpt;
 use aws_sdk_s3::model::{BucketLocationConstraint, CreateBucketConfiguration};
-use aws_sdk_s3::{Client, Error, Region, PKG_VERSION};
+use aws_sdk_s3::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The name of the bucket.
     #[structopt(short, long)]
     bucket: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Creates an Amazon S3 bucket in the Region.
@@ -33,35 +28,15 @@ struct Opt {
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
-    let Opt {
-        region,
-        bucket,
-        verbose,
-    } = Opt::from_args();
-
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
+    let Opt { bucket, common } = Opt::from_args();
 
-    println!();
+    let shared = common.load().await;
 
-    let r_rgr = region_provider.region().await.unwrap();
-    let r_str = r_rgr.as_ref();
+    let r_str = shared.region().as_ref();
 
-    if verbose {
-        println!("S3 client version: {}", PKG_VERSION);
-        println!(
-            "Region:            {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Bucket:            {}", &bucket);
-        println!();
-    }
+    shared.banner("S3", PKG_VERSION, &[("Bucket", &bucket)]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.s3();
 
     let constraint = BucketLocationConstraint::from(r_str);
     let cfg = CreateBucketConfiguration::builder()
@@ -0,0 +1,304 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::output::Listing;
+use alpha_common::CommonOpt;
+use aws_sdk_s3::{Client, Error, PKG_VERSION};
+use s3_code_examples::bucket_config::{
+    unless_missing, CorsJson, EncryptionJson, LifecycleJson, TaggingJson,
+};
+use serde_json::{json, Value};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The names of the buckets.
+    #[structopt(short, long, required = true)]
+    bucket: Vec<String>,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+/// The configurations of a bucket, as JSON in the same form as `bucket-config` shows them.
+/// Configurations the bucket doesn't have are `Value::Null`, and those that
+/// could not be read are `{"Error": "..."}`, with the error in `errors`.
+struct Description {
+    versioning: Value,
+    mfa_delete: Value,
+    encryption: Value,
+    lifecycle: Value,
+    cors: Value,
+    tagging: Value,
+    policy: Value,
+    errors: Vec<(&'static str, Error)>,
+}
+
+// An error getting one configuration doesn't stop the others being read.
+async fn describe(client: &Client, bucket: &str) -> Description {
+    let mut errors = Vec::new();
+    let mut record = |name: &'static str, result: Result<Value, Error>| match result {
+        Ok(value) => value,
+        Err(e) => {
+            let value = json!({ "Error": e.to_string() });
+            errors.push((name, e));
+            value
+        }
+    };
+
+    let versioning = client
+        .get_bucket_versioning()
+        .bucket(bucket)
+        .send()
+        .await
+        .map_err(Error::from);
+    let (versioning, mfa_delete) = match versioning {
+        Ok(versioning) => (
+            versioning.status.as_ref().map(|s| s.as_str()).into(),
+            versioning.mfa_delete.as_ref().map(|s| s.as_str()).into(),
+        ),
+        Err(e) => {
+            let value = record("versioning", Err(e));
+            (value.clone(), value)
+        }
+    };
+
+    let encryption = record(
+        "default encryption",
+        unless_missing(
+            client.get_bucket_encryption().bucket(bucket).send().await,
+            "ServerSideEncryptionConfigurationNotFoundError",
+        )
+        .map(|resp| {
+            resp.and_then(|r| r.server_side_encryption_configuration)
+                .map_or(Value::Null, |config| {
+                    json!(EncryptionJson::from_sdk(&config.rules.unwrap_or_default()))
+                })
+        }),
+    );
+
+    let lifecycle = record(
+        "lifecycle rules",
+        unless_missing(
+            client
+                .get_bucket_lifecycle_configuration()
+                .bucket(bucket)
+                .send()
+                .await,
+            "NoSuchLifecycleConfiguration",
+        )
+        .map(|resp| {
+            resp.map_or(Value::Null, |r| {
+                json!(LifecycleJson::from_sdk(&r.rules.unwrap_or_default()))
+            })
+        }),
+    );
+
+    let cors = record(
+        "CORS rules",
+        unless_missing(
+            client.get_bucket_cors().bucket(bucket).send().await,
+            "NoSuchCORSConfiguration",
+        )
+        .map(|resp| {
+            resp.map_or(Value::Null, |r| {
+                json!(CorsJson::from_sdk(&r.cors_rules.unwrap_or_default()))
+            })
+        }),
+    );
+
+    let tagging = record(
+        "tags",
+        unless_missing(
+            client.get_bucket_tagging().bucket(bucket).send().await,
+            "NoSuchTagSet",
+        )
+        .map(|resp| {
+            resp.map_or(Value::Null, |r| {
+                json!(TaggingJson::from_sdk(&r.tag_set.unwrap_or_default()))
+            })
+        }),
+    );
+
+    let policy = record(
+        "policy",
+        unless_missing(
+            client.get_bucket_policy().bucket(bucket).send().await,
+            "NoSuchBucketPolicy",
+        )
+        .map(|resp| {
+            resp.and_then(|r| r.policy).map_or(Value::Null, |policy| {
+                serde_json::from_str(&policy).unwrap_or(Value::String(policy))
+            })
+        }),
+    );
+
+    Description {
+        versioning,
+        mfa_delete,
+        encryption,
+        lifecycle,
+        cors,
+        tagging,
+        policy,
+        errors,
+    }
+}
+
+fn failed(value: &Value) -> bool {
+    value["Error"].is_string()
+}
+
+fn count(value: &Value, field: &str, one: &str, many: &str) -> String {
+    if failed(value) {
+        return "error".to_string();
+    }
+    match value[field].as_array().map(Vec::len) {
+        None => "none".to_string(),
+        Some(1) => format!("1 {}", one),
+        Some(n) => format!("{} {}", n, many),
+    }
+}
+
+fn print_text(bucket: &str, d: &Description) {
+    let encryption = match &d.encryption["Rules"][0] {
+        _ if failed(&d.encryption) => "error".to_string(),
+        Value::Null => "none".to_string(),
+        rule => {
+            let default = &rule["ApplyServerSideEncryptionByDefault"];
+            let mut text = default["SSEAlgorithm"]
+                .as_str()
+                .unwrap_or("none")
+                .to_string();
+            if let Some(key) = default["KMSMasterKeyID"].as_str() {
+                text.push_str(&format!(" with key {}", key));
+            }
+            if rule["BucketKeyEnabled"] == true {
+                text.push_str(", bucket key enabled");
+            }
+            text
+        }
+    };
+    let tags = match d.tagging["TagSet"].as_array() {
+        _ if failed(&d.tagging) => "error".to_string(),
+        Some(tags) if !tags.is_empty() => tags
+            .iter()
+            .map(|tag| {
+                format!(
+                    "{}={}",
+                    tag["Key"].as_str().unwrap_or_default(),
+                    tag["Value"].as_str().unwrap_or_default()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        _ => "none".to_string(),
+    };
+    let statements = match &d.policy["Statement"] {
+        _ if failed(&d.policy) => "error".to_string(),
+        Value::Null => "none".to_string(),
+        Value::Array(statements) if statements.len() != 1 => {
+            format!("{} statements", statements.len())
+        }
+        _ => "1 statement".to_string(),
+    };
+
+    println!("{}", bucket);
+    if failed(&d.versioning) {
+        println!("  Versioning: error");
+    } else {
+        println!(
+            "  Versioning: {} (MFA delete {})",
+            d.versioning.as_str().unwrap_or("never enabled"),
+            d.mfa_delete.as_str().unwrap_or("never enabled")
+        );
+    }
+    println!("  Encryption: {}", encryption);
+    println!(
+        "  Lifecycle:  {}",
+        count(&d.lifecycle, "Rules", "rule", "rules")
+    );
+    println!(
+        "  CORS:       {}",
+        count(&d.cors, "CORSRules", "rule", "rules")
+    );
+    println!("  Tags:       {}", tags);
+    println!("  Policy:     {}", statements);
+    println!();
+}
+
+/// Describes the versioning, default encryption, lifecycle rules, CORS rules,
+/// tags, and policy of Amazon S3 buckets, for an audit.
+/// With `--output json` each configuration is shown in full, in the same form as `bucket-config` shows it.
+/// A configuration that can't be read, such as for lack of permission, is
+/// shown as an error, the rest of the audit goes on, and the errors are
+/// reported at the end.
+/// # Arguments
+///
+/// * `-b BUCKET...` - The names of the buckets. Repeat to describe several.
+/// * `[-r REGION]` - The Region in which the client is created.
+///   If not supplied, uses the value of the **AWS_REGION** environment variable.
+///   If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt { bucket, common } = Opt::from_args();
+
+    let shared = common.load().await;
+
+    shared.banner("S3", PKG_VERSION, &[("Buckets", &bucket.join(", "))]);
+
+    let client = shared.s3();
+
+    let mut descriptions = Vec::new();
+    for name in &bucket {
+        descriptions.push(describe(&client, name).await);
+    }
+
+    let mut listing = Listing::new(&[
+        "Bucket",
+        "Versioning",
+        "MfaDelete",
+        "Encryption",
+        "Lifecycle",
+        "Cors",
+        "Tagging",
+        "Policy",
+    ]);
+    for (name, d) in bucket.iter().zip(&descriptions) {
+        listing.push(vec![
+            name.as_str().into(),
+            d.versioning.clone(),
+            d.mfa_delete.clone(),
+            d.encryption.clone(),
+            d.lifecycle.clone(),
+            d.cors.clone(),
+            d.tagging.clone(),
+            d.policy.clone(),
+        ]);
+    }
+
+    shared.print(&listing, || {
+        for (name, d) in bucket.iter().zip(&descriptions) {
+            print_text(name, d);
+        }
+    });
+
+    let failed: Vec<_> = bucket
+        .iter()
+        .zip(&descriptions)
+        .flat_map(|(name, d)| d.errors.iter().map(move |(what, e)| (name, what, e)))
+        .collect();
+    if !failed.is_empty() {
+        eprintln!();
+        for (name, what, e) in failed {
+            eprintln!("Got an error getting the {} of {}:", what, name);
+            eprintln!("{}", e);
+        }
+        shared.exit(1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::CommonOpt;
+use aws_sdk_s3::{Error, PKG_VERSION};
+use s3_code_examples::transfer::{self, TransferOpt};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The name of the bucket.
+    #[structopt(short, long)]
+    bucket: String,
+
+    /// The key of the object.
+    #[structopt(short, long)]
+    key: String,
+
+    /// The file to download to. Defaults to the last part of the key.
+    #[structopt(short, long, parse(from_os_str))]
+    file: Option<PathBuf>,
+
+    #[structopt(flatten)]
+    transfer: TransferOpt,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+/// Downloads an object from an Amazon S3 bucket, in parallel parts or ranges
+/// if it is larger than the threshold, and checks it against the object's
+/// ETag before writing it to the file.
+/// # Arguments
+///
+/// * `-b BUCKET` - The name of the bucket.
+/// * `-k KEY` - The key of the object.
+/// * `[-f FILE]` - The file to download to. Defaults to the last part of the key.
+/// * `[--threshold SIZE]` - Objects larger than this are downloaded in parts.
+///   Defaults to 16MiB.
+/// * `[--part-size SIZE]` - The size of each range. Defaults to 8MiB.
+/// * `[--concurrency N]` - The most parts to download at once. Defaults to 4.
+/// * `[-r REGION]` - The Region in which the client is created.
+///   If not supplied, uses the value of the **AWS_REGION** environment variable.
+///   If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt {
+        bucket,
+        key,
+        file,
+        transfer,
+        common,
+    } = Opt::from_args();
+
+    let file = file.unwrap_or_else(|| PathBuf::from(key.rsplit('/').next().unwrap_or(&key)));
+
+    let shared = common.load().await;
+
+    shared.banner(
+        "S3",
+        PKG_VERSION,
+        &[
+            ("Bucket", &bucket),
+            ("Key", &key),
+            ("File", &file.display().to_string()),
+        ],
+    );
+
+    let client = shared.s3();
+
+    let progress = transfer::progress_bar(0);
+    let result = transfer::download(&client, &bucket, &key, &file, &transfer, &progress).await;
+    progress.finish_and_clear();
+
+    match result {
+        Ok(download) => println!(
+            "Downloaded {} to {} in {} parts. ETag: {}",
+            key,
+            file.display(),
+            download.parts,
+            download.etag
+        ),
+        Err(e) => {
+            println!("Got an error downloading the object:");
+            println!("{}", e);
+            shared.exit(1);
+        }
+    }
+
+    Ok(())
+}
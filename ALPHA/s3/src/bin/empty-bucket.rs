@@ -0,0 +1,124 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use std::io::{self, BufRead, Write};
+
+use alpha_common::CommonOpt;
+use aws_sdk_s3::{Error, PKG_VERSION};
+use s3_code_examples::empty;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The name of the bucket.
+    #[structopt(short, long)]
+    bucket: String,
+
+    /// Only delete objects whose keys start with this prefix.
+    #[structopt(short, long, conflicts_with = "delete-bucket")]
+    prefix: Option<String>,
+
+    /// Delete the bucket too, once it is empty.
+    #[structopt(long)]
+    delete_bucket: bool,
+
+    /// Do not ask before deleting.
+    #[structopt(short, long)]
+    yes: bool,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+/// Asks for the bucket's name to be typed, to make sure it is the one meant.
+fn confirm(bucket: &str) -> bool {
+    print!("Type the name of the bucket to continue: ");
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    match io::stdin().lock().read_line(&mut answer) {
+        Ok(_) => answer.trim() == bucket,
+        Err(_) => false,
+    }
+}
+
+/// Permanently deletes every version of every object, and every delete marker,
+/// from an Amazon S3 bucket, and optionally the bucket itself.
+/// Asks for the name of the bucket before deleting anything, unless `--yes` is given.
+/// # Arguments
+///
+/// * `-b BUCKET` - The name of the bucket.
+/// * `[-p PREFIX]` - Only delete objects whose keys start with this prefix.
+/// * `[--delete-bucket]` - Delete the bucket too, once it is empty.
+/// * `[-y]` - Do not ask before deleting.
+/// * `[-r REGION]` - The Region in which the client is created.
+///   If not supplied, uses the value of the **AWS_REGION** environment variable.
+///   If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt {
+        bucket,
+        prefix,
+        delete_bucket,
+        yes,
+        common,
+    } = Opt::from_args();
+
+    let shared = common.load().await;
+
+    shared.banner("S3", PKG_VERSION, &[("Bucket", &bucket)]);
+
+    let client = shared.s3();
+
+    let objects = empty::list_versions(&client, &bucket, prefix.as_deref()).await?;
+    let total = objects.len();
+
+    if total == 0 && !delete_bucket {
+        println!("Bucket {} has nothing to delete.", bucket);
+        return Ok(());
+    }
+
+    let what = match (&prefix, delete_bucket) {
+        (Some(prefix), _) => format!("under {} in bucket {}", prefix, bucket),
+        (None, true) => format!("in bucket {}, then the bucket itself", bucket),
+        (None, false) => format!("in bucket {}", bucket),
+    };
+    println!(
+        "This permanently deletes {} object versions and delete markers {}.",
+        total, what
+    );
+    if !yes && !confirm(&bucket) {
+        println!("Nothing was deleted.");
+        return Ok(());
+    }
+
+    let failed = empty::delete_versions(&client, &bucket, objects, |deleted| {
+        println!("  deleted {} of {}", deleted, total);
+    })
+    .await?;
+
+    if !failed.is_empty() {
+        println!("Could not delete {} object versions:", failed.len());
+        for e in &failed {
+            println!(
+                "  {} (version {}): {}: {}",
+                e.key,
+                e.version_id.as_deref().unwrap_or("null"),
+                e.code,
+                e.message
+            );
+        }
+        shared.exit(1);
+    }
+
+    if delete_bucket {
+        client.delete_bucket().bucket(&bucket).send().await?;
+        println!("Deleted bucket {}.", bucket);
+    } else {
+        println!("Emptied bucket {}.", bucket);
+    }
+
+    Ok(())
+}
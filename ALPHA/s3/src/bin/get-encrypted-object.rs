@@ -0,0 +1,92 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::CommonOpt;
+use aws_sdk_s3::{Error, PKG_VERSION};
+use s3_code_examples::envelope;
+use std::fs;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The name of the bucket.
+    #[structopt(short, long)]
+    bucket: String,
+
+    /// The key of the object.
+    #[structopt(short, long)]
+    key: String,
+
+    /// The file to write the decrypted object to. Defaults to the last part of the key.
+    #[structopt(short, long, parse(from_os_str))]
+    file: Option<PathBuf>,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+/// Downloads an object that `put-encrypted-object` uploaded to an Amazon S3
+/// bucket, unwraps its data key with AWS KMS, and decrypts it locally.
+/// Nothing is written unless the object passes authentication.
+/// # Arguments
+///
+/// * `-b BUCKET` - The name of the bucket.
+/// * `-k KEY` - The key of the object.
+/// * `[-f FILE]` - The file to write the decrypted object to.
+///   Defaults to the last part of the key.
+/// * `[-r REGION]` - The Region in which the clients are created.
+///   If not supplied, uses the value of the **AWS_REGION** environment variable.
+///   If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt {
+        bucket,
+        key,
+        file,
+        common,
+    } = Opt::from_args();
+
+    let file = file.unwrap_or_else(|| PathBuf::from(key.rsplit('/').next().unwrap_or(&key)));
+
+    let shared = common.load().await;
+
+    shared.banner(
+        "S3",
+        PKG_VERSION,
+        &[
+            ("Bucket", &bucket),
+            ("Key", &key),
+            ("File", &file.display().to_string()),
+        ],
+    );
+
+    let s3 = shared.s3();
+    let kms = shared.kms();
+
+    let plaintext = match envelope::get_object(&s3, &kms, &bucket, &key).await {
+        Ok(plaintext) => plaintext,
+        Err(e) => {
+            println!("Got an error downloading the object:");
+            println!("{}", e);
+            shared.exit(1);
+        }
+    };
+    if let Err(e) = fs::write(&file, &plaintext) {
+        println!("Got an error writing {}:", file.display());
+        println!("{}", e);
+        shared.exit(1);
+    }
+
+    println!(
+        "Downloaded and decrypted {} to {} ({} bytes)",
+        key,
+        file.display(),
+        plaintext.len()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,192 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use std::collections::BTreeMap;
+
+use alpha_common::output::{date_time, Listing};
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::{CommonOpt, OutputFormat};
+use aws_sdk_s3::{Error, PKG_VERSION};
+use s3_code_examples::inventory::{self, Details};
+use serde_json::{json, Value};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The name of the bucket.
+    #[structopt(short, long)]
+    bucket: String,
+
+    /// Only report on objects with keys that start with this prefix.
+    #[structopt(short, long, default_value = "")]
+    prefix: String,
+
+    /// Get each object's tags.
+    #[structopt(long)]
+    tags: bool,
+
+    /// Get each object's content type and user metadata.
+    #[structopt(long)]
+    metadata: bool,
+
+    /// The most objects to get tags or metadata for at once.
+    #[structopt(long, default_value = "8")]
+    concurrency: usize,
+
+    /// Report the totals per prefix and storage class instead of each object.
+    #[structopt(long)]
+    totals: bool,
+
+    #[structopt(flatten)]
+    pages: PageOpt,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+fn map_value(map: &Option<BTreeMap<String, String>>) -> Value {
+    match map {
+        Some(map) => map
+            .iter()
+            .map(|(k, v)| (k.clone(), Value::from(v.as_str())))
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+        None => Value::Null,
+    }
+}
+
+/// Reports the size, storage class, last-modified time, and ETag of the
+/// objects in an Amazon S3 bucket, and optionally their tags and metadata,
+/// with totals per prefix and storage class.
+/// Use `--output csv` or `--output json` for a report to process further.
+/// With `--output json` the report is `{"Objects": [...], "Totals": [...]}`;
+/// other formats hold only the objects, and `--totals` reports the totals.
+/// # Arguments
+///
+/// * `-b BUCKET` - The name of the bucket.
+/// * `[-p PREFIX]` - Only report on objects with keys that start with this prefix.
+///   Totals are per prefix one level below it.
+/// * `[--tags]` - Get each object's tags, with one request per object.
+/// * `[--metadata]` - Get each object's content type and user metadata,
+///   with one request per object.
+/// * `[--concurrency N]` - The most objects to get tags or metadata for at once.
+///   Defaults to 8.
+/// * `[--totals]` - Report only the totals per prefix and storage class.
+/// * `[--max-items MAX-ITEMS]` - The most objects to report on.
+/// * `[--page-size PAGE-SIZE]` - The number of objects to list at a time.
+/// * `[-r REGION]` - The Region in which the client is created.
+///   If not supplied, uses the value of the **AWS_REGION** environment variable.
+///   If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt {
+        bucket,
+        prefix,
+        tags,
+        metadata,
+        concurrency,
+        totals,
+        pages,
+        common,
+    } = Opt::from_args();
+
+    let shared = common.load().await;
+
+    shared.banner(
+        "S3",
+        PKG_VERSION,
+        &[("Bucket", &bucket), ("Prefix", &prefix)],
+    );
+
+    let client = shared.s3();
+
+    let objects = {
+        let client = client.clone();
+        let bucket = bucket.clone();
+        let prefix = prefix.clone();
+        paginate(&pages, move |token, max_keys| {
+            let request = client
+                .list_objects_v2()
+                .bucket(&bucket)
+                .prefix(&prefix)
+                .set_continuation_token(token)
+                .set_max_keys(max_keys);
+            async move {
+                request.send().await.map(|resp| {
+                    Page::with_token(
+                        resp.contents.unwrap_or_default(),
+                        resp.next_continuation_token,
+                    )
+                })
+            }
+        })
+        .try_collect()
+        .await?
+    };
+
+    let details = Details { tags, metadata };
+    let entries = inventory::entries(&client, &bucket, &objects, details, concurrency).await?;
+    let sums = inventory::totals(&prefix, &entries);
+
+    let mut totals_listing = Listing::new(&["Prefix", "StorageClass", "Objects", "Bytes"]);
+    for ((group, class), total) in &sums {
+        totals_listing.push(vec![
+            group.as_str().into(),
+            class.as_str().into(),
+            total.objects.into(),
+            total.bytes.into(),
+        ]);
+    }
+    if totals {
+        shared.print(&totals_listing, || print!("{}", totals_listing.to_table()));
+        return Ok(());
+    }
+
+    let mut columns = vec!["Key", "Size", "StorageClass", "LastModified", "ETag"];
+    if tags {
+        columns.push("Tags");
+    }
+    if metadata {
+        columns.extend(["ContentType", "Metadata"]);
+    }
+    let mut listing = Listing::new(&columns);
+    for entry in &entries {
+        let mut row = vec![
+            entry.key.as_str().into(),
+            entry.size.into(),
+            entry.storage_class.as_str().into(),
+            date_time(entry.last_modified.as_ref()),
+            entry.etag.as_deref().into(),
+        ];
+        if tags {
+            row.push(map_value(&entry.tags));
+        }
+        if metadata {
+            row.push(entry.content_type.as_deref().into());
+            row.push(map_value(&entry.metadata));
+        }
+        listing.push(row);
+    }
+
+    if shared.output() == OutputFormat::Json {
+        let report = json!({ "Objects": listing.to_json(), "Totals": totals_listing.to_json() });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("JSON values serialize")
+        );
+        return Ok(());
+    }
+    shared.print(&listing, || {
+        print!("{}", listing.to_table());
+        println!();
+        print!("{}", totals_listing.to_table());
+        let bytes: i64 = entries.iter().map(|e| e.size).sum();
+        println!();
+        println!("Found {} objects holding {} bytes.", entries.len(), bytes);
+    });
+
+    Ok(())
+}
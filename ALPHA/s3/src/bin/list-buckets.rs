@@ -3,102 +3,156 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_s3::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::{date_time, Listing};
+use alpha_common::CommonOpt;
+use aws_sdk_s3::model::Bucket;
+use aws_sdk_s3::{Error, PKG_VERSION};
+use s3_code_examples::location::bucket_regions;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// Whether to only get buckets in the Region.
-    #[structopt(short, long)]
+    #[structopt(short, long, conflicts_with = "by-region")]
     strict: bool,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    /// Whether to show every bucket's Region, grouping buckets by Region.
+    #[structopt(long)]
+    by_region: bool,
+
+    /// The most bucket locations to look up at once.
+    #[structopt(long, default_value = "16")]
+    concurrency: usize,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists your Amazon S3 buckets, or just the buckets in the Region.
 /// # Arguments
 ///
 /// * `[-s]` - Only list bucket in the Region.
+/// * `[--by-region]` - Show every bucket's Region, grouping buckets by Region.
+/// * `[--concurrency N]` - The most bucket locations to look up at once. Defaults to 16.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///   If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///   If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
-        region,
         strict,
-        verbose,
+        by_region,
+        concurrency,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-
-    println!();
-    let region_str: String = String::from(region_provider.region().await.unwrap().as_ref());
-
-    println!();
-
-    if verbose {
-        println!("S3 client version: {}", PKG_VERSION);
-        println!(
-            "Region:            {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
+    let shared = common.load().await;
 
-        if strict {
-            println!("Only lists buckets in the Region.");
-        } else {
-            println!("Lists all buckets.");
-        }
+    let region_str = shared.region().as_ref();
 
-        println!();
-    }
+    shared.banner(
+        "S3",
+        PKG_VERSION,
+        &[(
+            "Listing",
+            &if strict {
+                "Only buckets in the Region"
+            } else if by_region {
+                "All buckets, by Region"
+            } else {
+                "All buckets"
+            },
+        )],
+    );
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.s3();
 
     let resp = client.list_buckets().send().await?;
     let buckets = resp.buckets.unwrap_or_default();
     let num_buckets = buckets.len();
 
-    let mut in_region = 0;
-
-    for bucket in &buckets {
-        if strict {
-            let r = client
-                .get_bucket_location()
-                .bucket(bucket.name.as_deref().unwrap_or_default())
-                .send()
-                .await?;
-
-            if r.location_constraint.unwrap().as_ref() == region_str {
-                println!("{}", bucket.name.as_deref().unwrap_or_default());
-                in_region += 1;
+    let mut shown: Vec<(&Bucket, Option<String>)> = Vec::new();
+    let mut failed = Vec::new();
+
+    if strict || by_region {
+        let names: Vec<String> = buckets
+            .iter()
+            .map(|bucket| bucket.name.clone().unwrap_or_default())
+            .collect();
+        let regions = bucket_regions(&client, &names, concurrency).await;
+        for ((bucket, name), region) in buckets.iter().zip(names).zip(regions) {
+            match region {
+                Ok(region) if strict && region != region_str => {}
+                Ok(region) => shown.push((bucket, Some(region))),
+                Err(e) => failed.push((name, e)),
             }
-        } else {
-            println!("{}", bucket.name.as_deref().unwrap_or_default());
         }
+        // Stable, so buckets stay in name order within a Region.
+        shown.sort_by(|a, b| a.1.cmp(&b.1));
+    } else {
+        shown.extend(buckets.iter().map(|bucket| (bucket, None)));
     }
 
-    println!();
-    if strict {
-        println!(
-            "Found {} buckets in the {} region out of a total of {} buckets.",
-            in_region, region_str, num_buckets
-        );
+    let mut listing = if by_region {
+        Listing::new(&["Name", "CreationDate", "Region"])
     } else {
-        println!("Found {} buckets in all regions.", num_buckets);
+        Listing::new(&["Name", "CreationDate"])
+    };
+    for (bucket, region) in &shown {
+        let mut row = vec![
+            bucket.name.as_deref().into(),
+            date_time(bucket.creation_date.as_ref()),
+        ];
+        if by_region {
+            row.push(region.as_deref().into());
+        }
+        listing.push(row);
+    }
+
+    shared.print(&listing, || {
+        let mut current = None;
+        for (bucket, region) in &shown {
+            let name = bucket.name.as_deref().unwrap_or_default();
+            if by_region {
+                if current != Some(region) {
+                    println!("{}:", region.as_deref().unwrap_or_default());
+                    current = Some(region);
+                }
+                println!("  {}", name);
+            } else {
+                println!("{}", name);
+            }
+        }
+
+        println!();
+        if strict {
+            println!(
+                "Found {} buckets in the {} region out of a total of {} buckets.",
+                shown.len(),
+                region_str,
+                num_buckets
+            );
+        } else if by_region {
+            let mut regions: Vec<_> = shown.iter().map(|(_, region)| region).collect();
+            regions.dedup();
+            println!(
+                "Found {} buckets in {} regions.",
+                shown.len(),
+                regions.len()
+            );
+        } else {
+            println!("Found {} buckets in all regions.", num_buckets);
+        }
+    });
+
+    if !failed.is_empty() {
+        eprintln!();
+        for (name, e) in &failed {
+            eprintln!("Got an error getting the Region of {}:", name);
+            eprintln!("{}", e);
+        }
+        shared.exit(1);
     }
 
     Ok(())
@@ -3,72 +3,96 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_s3::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::{date_time, Listing};
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_s3::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The name of the bucket.
     #[structopt(short, long)]
     bucket: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    pages: PageOpt,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists the versions of the objects in an Amazon S3 bucket.
 /// # Arguments
 ///
 /// * `-b BUCKET` - The name of the bucket.
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///   If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///   If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
-        region,
         bucket,
-        verbose,
+        pages,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("S3 client version: {}", PKG_VERSION);
-        println!(
-            "Region:            {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Bucket:            {}", &bucket);
-        println!();
-    }
+    shared.banner("S3", PKG_VERSION, &[("Bucket", &bucket)]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.s3();
 
-    let resp = client.list_object_versions().bucket(&bucket).send().await?;
+    // A page of versions ends at a key and version ID,
+    // and the next page starts with the versions after them.
+    let versions = paginate(&pages, move |markers, max_keys| {
+        let (key_marker, version_id_marker) = match markers {
+            Some((key, version_id)) => (Some(key), version_id),
+            None => (None, None),
+        };
+        let request = client
+            .list_object_versions()
+            .bucket(&bucket)
+            .set_key_marker(key_marker)
+            .set_version_id_marker(version_id_marker)
+            .set_max_keys(max_keys);
+        async move {
+            request.send().await.map(|resp| {
+                let next = match resp.next_key_marker {
+                    Some(key) if resp.is_truncated => Some((key, resp.next_version_id_marker)),
+                    _ => None,
+                };
+                Page::new(resp.versions.unwrap_or_default(), next)
+            })
+        }
+    })
+    .try_collect()
+    .await?;
 
-    for version in resp.versions.unwrap_or_default() {
-        println!("{}", version.key.as_deref().unwrap_or_default());
-        println!(
-            "  version ID: {}",
-            version.version_id.as_deref().unwrap_or_default()
-        );
-        println!();
+    let mut listing = Listing::new(&["Key", "VersionId", "IsLatest", "Size", "LastModified"]);
+    for version in &versions {
+        listing.push(vec![
+            version.key.as_deref().into(),
+            version.version_id.as_deref().into(),
+            version.is_latest.into(),
+            version.size.into(),
+            date_time(version.last_modified.as_ref()),
+        ]);
     }
 
+    shared.print(&listing, || {
+        for version in versions {
+            println!("{}", version.key.as_deref().unwrap_or_default());
+            println!(
+                "  version ID: {}",
+                version.version_id.as_deref().unwrap_or_default()
+            );
+            println!();
+        }
+    });
+
     Ok(())
 }
@@ -3,67 +3,82 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_s3::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::{date_time, Listing};
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_s3::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The name of the bucket.
     #[structopt(short, long)]
     bucket: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    pages: PageOpt,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists the objects in an Amazon S3 bucket.
 /// # Arguments
 ///
 /// * `-b BUCKET` - The name of the bucket.
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///   If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///   If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
-        region,
         bucket,
-        verbose,
+        pages,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("S3 client version: {}", PKG_VERSION);
-        println!(
-            "Region:            {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Bucket:            {}", &bucket);
-        println!();
-    }
+    shared.banner("S3", PKG_VERSION, &[("Bucket", &bucket)]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.s3();
 
-    let resp = client.list_objects_v2().bucket(&bucket).send().await?;
+    let objects = paginate(&pages, move |token, max_keys| {
+        let request = client
+            .list_objects_v2()
+            .bucket(&bucket)
+            .set_continuation_token(token)
+            .set_max_keys(max_keys);
+        async move {
+            request.send().await.map(|resp| {
+                Page::with_token(
+                    resp.contents.unwrap_or_default(),
+                    resp.next_continuation_token,
+                )
+            })
+        }
+    })
+    .try_collect()
+    .await?;
 
-    for object in resp.contents.unwrap_or_default() {
-        println!("{}", object.key.as_deref().unwrap_or_default());
+    let mut listing = Listing::new(&["Key", "Size", "LastModified", "StorageClass"]);
+    for object in &objects {
+        listing.push(vec![
+            object.key.as_deref().into(),
+            object.size.into(),
+            date_time(object.last_modified.as_ref()),
+            object.storage_class.as_ref().map(|c| c.as_str()).into(),
+        ]);
     }
 
+    shared.print(&listing, || {
+        for object in objects {
+            println!("{}", object.key.as_deref().unwrap_or_default());
+        }
+    });
+
     Ok(())
 }
@@ -0,0 +1,140 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
+
+use alpha_common::CommonOpt;
+use aws_sdk_s3::{Error, PKG_VERSION};
+use s3_code_examples::presign::{self, Method, Presign};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The name of the bucket.
+    #[structopt(short, long)]
+    bucket: String,
+
+    /// The key of the object.
+    #[structopt(short, long)]
+    key: String,
+
+    /// What the URL allows: get or put.
+    #[structopt(short, long, default_value = "get")]
+    method: Method,
+
+    /// How many seconds the URL is valid for, up to 604800 (one week).
+    #[structopt(short, long, default_value = "900")]
+    expires_in: u64,
+
+    /// For get, the Content-Type to respond with.
+    /// For put, the Content-Type to send the upload with. A presigned URL
+    /// cannot sign it, so S3 accepts an upload with any Content-Type.
+    #[structopt(short, long)]
+    content_type: Option<String>,
+
+    /// For put, only accept the contents of this file, by signing its MD5.
+    #[structopt(long, parse(from_os_str))]
+    md5_of: Option<PathBuf>,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+/// Creates a presigned URL that gets or puts an object in an Amazon S3 bucket
+/// without AWS credentials, until it expires.
+/// # Arguments
+///
+/// * `-b BUCKET` - The name of the bucket.
+/// * `-k KEY` - The key of the object.
+/// * `[-m METHOD]` - What the URL allows: get or put. Defaults to get.
+/// * `[-e SECONDS]` - How many seconds the URL is valid for. Defaults to 900.
+/// * `[-c CONTENT-TYPE]` - For get, the Content-Type to respond with.
+///   For put, the Content-Type to send the upload with. It is not signed,
+///   so S3 does not enforce it.
+/// * `[--md5-of FILE]` - For put, only accept the contents of this file.
+/// * `[-r REGION]` - The Region in which the client is created.
+///   If not supplied, uses the value of the **AWS_REGION** environment variable.
+///   If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt {
+        bucket,
+        key,
+        method,
+        expires_in,
+        content_type,
+        md5_of,
+        common,
+    } = Opt::from_args();
+
+    let content_md5 = match (&md5_of, method) {
+        (None, _) => None,
+        (Some(_), Method::Get) => {
+            println!("--md5-of only applies to put.");
+            process::exit(1);
+        }
+        (Some(path), Method::Put) => match fs::read(path) {
+            Ok(data) => Some(base64::encode(md5::compute(data).0)),
+            Err(e) => {
+                println!("Got an error reading {}:", path.display());
+                println!("{}", e);
+                process::exit(1);
+            }
+        },
+    };
+
+    let shared = common.load().await;
+
+    shared.banner(
+        "S3",
+        PKG_VERSION,
+        &[
+            ("Bucket", &bucket),
+            ("Key", &key),
+            ("Method", &method.as_str()),
+        ],
+    );
+
+    let client = shared.s3();
+
+    let request = Presign {
+        method,
+        bucket,
+        key,
+        content_type,
+        content_md5,
+        expires_in: Duration::from_secs(expires_in),
+    };
+    let presigned = match presign::presign(&client, &request).await {
+        Ok(presigned) => presigned,
+        Err(e) => {
+            println!("Got an error presigning the URL:");
+            println!("{}", e);
+            shared.exit(1);
+        }
+    };
+
+    println!(
+        "Presigned {} URL, valid for {} seconds:",
+        presigned.method.as_str(),
+        expires_in
+    );
+    println!("{}", presigned.url);
+    if !presigned.headers.is_empty() {
+        println!("Send these headers with it:");
+        for (name, value) in &presigned.headers {
+            println!("  {}: {}", name, value);
+        }
+    }
+    if request.method == Method::Put && request.content_type.is_some() {
+        println!("The Content-Type is not signed, so S3 accepts the upload with any Content-Type.");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::CommonOpt;
+use aws_sdk_s3::{Error, PKG_VERSION};
+use s3_code_examples::envelope;
+use std::fs;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The name of the bucket.
+    #[structopt(short, long)]
+    bucket: String,
+
+    /// The key of the object. Defaults to the name of the file.
+    #[structopt(short, long)]
+    key: Option<String>,
+
+    /// The file to encrypt and upload.
+    #[structopt(short, long, parse(from_os_str))]
+    file: PathBuf,
+
+    /// The ID, ARN, or alias of the KMS key that wraps the object's data key.
+    #[structopt(long)]
+    kms_key: String,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+/// Encrypts a file locally with a new AWS KMS data key, using AES-256-GCM,
+/// and uploads it to an Amazon S3 bucket with the wrapped data key and IV
+/// in the object's metadata. S3 never sees the contents or the data key.
+/// # Arguments
+///
+/// * `-b BUCKET` - The name of the bucket.
+/// * `[-k KEY]` - The key of the object. Defaults to the name of the file.
+/// * `-f FILE` - The file to encrypt and upload.
+/// * `--kms-key KMS-KEY` - The ID, ARN, or alias of the KMS key.
+/// * `[-r REGION]` - The Region in which the clients are created.
+///   If not supplied, uses the value of the **AWS_REGION** environment variable.
+///   If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt {
+        bucket,
+        key,
+        file,
+        kms_key,
+        common,
+    } = Opt::from_args();
+
+    let key = key.unwrap_or_else(|| {
+        file.file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    });
+
+    let shared = common.load().await;
+
+    let plaintext = match fs::read(&file) {
+        Ok(data) => data,
+        Err(e) => {
+            println!("Got an error reading {}:", file.display());
+            println!("{}", e);
+            shared.exit(1);
+        }
+    };
+
+    shared.banner(
+        "S3",
+        PKG_VERSION,
+        &[("Bucket", &bucket), ("Key", &key), ("KMS key", &kms_key)],
+    );
+
+    let s3 = shared.s3();
+    let kms = shared.kms();
+
+    let len = plaintext.len();
+    if let Err(e) = envelope::put_object(&s3, &kms, &bucket, &key, &kms_key, plaintext).await {
+        println!("Got an error uploading the object:");
+        println!("{}", e);
+        shared.exit(1);
+    }
+
+    println!(
+        "Uploaded {} ({} bytes) to {}, encrypted with a data key from {}",
+        file.display(),
+        len,
+        key,
+        kms_key
+    );
+
+    Ok(())
+}
@@ -0,0 +1,172 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use std::io;
+
+use alpha_common::CommonOpt;
+use aws_sdk_s3::model::{
+    CompressionType, CsvInput, CsvOutput, FileHeaderInfo, InputSerialization, JsonInput,
+    JsonOutput, JsonType, OutputSerialization, ParquetInput,
+};
+use aws_sdk_s3::{Error, PKG_VERSION};
+use s3_code_examples::select::{self, Query};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The name of the bucket.
+    #[structopt(short, long)]
+    bucket: String,
+
+    /// The key of the object to query.
+    #[structopt(short, long)]
+    key: String,
+
+    /// The SQL expression, such as "SELECT * FROM S3Object s WHERE s.status = '500'".
+    #[structopt(short, long)]
+    query: String,
+
+    /// How the object is formatted.
+    #[structopt(long, default_value = "CSV", possible_values = &["CSV", "JSON", "Parquet"])]
+    input_format: String,
+
+    /// For CSV objects, whether the first line names the columns (USE),
+    /// is to be skipped (IGNORE), or is a record (NONE).
+    #[structopt(long, default_value = "USE", possible_values = &["USE", "IGNORE", "NONE"])]
+    header: String,
+
+    /// For JSON objects, whether there is one object per line (LINES),
+    /// or the whole object is one JSON document (DOCUMENT).
+    #[structopt(long, default_value = "LINES", possible_values = &["LINES", "DOCUMENT"])]
+    json_type: String,
+
+    /// How the object is compressed. Parquet objects can't be compressed as a whole.
+    #[structopt(long, default_value = "NONE", possible_values = &["NONE", "GZIP", "BZIP2"])]
+    compression: String,
+
+    /// How to format the results.
+    #[structopt(long, default_value = "CSV", possible_values = &["CSV", "JSON"])]
+    results: String,
+
+    /// Report the bytes scanned as the query runs, and the totals at the end, on stderr.
+    #[structopt(long)]
+    progress: bool,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+fn input_serialization(
+    format: &str,
+    header: &str,
+    json_type: &str,
+    compression: &str,
+) -> InputSerialization {
+    let input = InputSerialization::builder().compression_type(CompressionType::from(compression));
+    match format {
+        "JSON" => input.json(
+            JsonInput::builder()
+                .r#type(JsonType::from(json_type))
+                .build(),
+        ),
+        "Parquet" => input.parquet(ParquetInput::builder().build()),
+        _ => input.csv(
+            CsvInput::builder()
+                .file_header_info(FileHeaderInfo::from(header))
+                .build(),
+        ),
+    }
+    .build()
+}
+
+fn output_serialization(format: &str) -> OutputSerialization {
+    let output = OutputSerialization::builder();
+    match format {
+        "JSON" => output.json(JsonOutput::builder().record_delimiter("\n").build()),
+        _ => output.csv(CsvOutput::builder().build()),
+    }
+    .build()
+}
+
+/// Runs an S3 Select SQL query against a CSV, JSON, or Parquet object in an
+/// Amazon S3 bucket, and writes the results to stdout as they arrive,
+/// without downloading the object.
+/// # Arguments
+///
+/// * `-b BUCKET` - The name of the bucket.
+/// * `-k KEY` - The key of the object to query.
+/// * `-q QUERY` - The SQL expression, such as
+///   `SELECT s.path FROM S3Object s WHERE s.status = '500'`.
+/// * `[--input-format FORMAT]` - CSV, JSON, or Parquet. Defaults to CSV.
+/// * `[--header HEADER]` - For CSV, whether the first line names the columns (USE),
+///   is skipped (IGNORE), or is a record (NONE). Defaults to USE.
+/// * `[--json-type TYPE]` - For JSON, LINES or DOCUMENT. Defaults to LINES.
+/// * `[--compression COMPRESSION]` - NONE, GZIP, or BZIP2. Defaults to NONE.
+/// * `[--results FORMAT]` - CSV or JSON. Defaults to CSV.
+/// * `[--progress]` - Report the bytes scanned as the query runs, on stderr.
+/// * `[-r REGION]` - The Region in which the client is created.
+///   If not supplied, uses the value of the **AWS_REGION** environment variable.
+///   If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt {
+        bucket,
+        key,
+        query,
+        input_format,
+        header,
+        json_type,
+        compression,
+        results,
+        progress,
+        common,
+    } = Opt::from_args();
+
+    // Stdout holds only the results, whatever --output is.
+    let shared = common.load().await.raw_stdout();
+
+    shared.banner(
+        "S3",
+        PKG_VERSION,
+        &[("Bucket", &bucket), ("Key", &key), ("Query", &query)],
+    );
+
+    let client = shared.s3();
+
+    let query = Query {
+        bucket,
+        key,
+        expression: query,
+        input: input_serialization(&input_format, &header, &json_type, &compression),
+        output: output_serialization(&results),
+        progress,
+    };
+    let stdout = io::stdout();
+    let selected = select::select(&client, &query, &mut stdout.lock(), |p| {
+        eprintln!(
+            "Scanned {} bytes, processed {}, returned {}",
+            p.bytes_scanned, p.bytes_processed, p.bytes_returned
+        );
+    })
+    .await;
+
+    match selected {
+        Ok(stats) => {
+            if let (true, Some(stats)) = (progress, stats) {
+                eprintln!(
+                    "Done. Scanned {} bytes, processed {}, returned {}",
+                    stats.bytes_scanned, stats.bytes_processed, stats.bytes_returned
+                );
+            }
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("Got an error running the query:");
+            eprintln!("{}", e);
+            shared.exit(1);
+        }
+    }
+}
@@ -0,0 +1,261 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process;
+
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_s3::model::ObjectIdentifier;
+use aws_sdk_s3::{Error, PKG_VERSION};
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use indicatif::ProgressBar;
+use s3_code_examples::empty;
+use s3_code_examples::sync::{self, Filter, RemoteObject};
+use s3_code_examples::transfer::{self, TransferOpt};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The name of the bucket.
+    #[structopt(short, long)]
+    bucket: String,
+
+    /// The key prefix to sync to. Defaults to the whole bucket.
+    #[structopt(short, long, default_value = "")]
+    prefix: String,
+
+    /// The local directory to sync from.
+    #[structopt(short, long, parse(from_os_str))]
+    dir: PathBuf,
+
+    /// Delete objects under the prefix that have no file in the directory.
+    #[structopt(long)]
+    delete: bool,
+
+    /// Only sync paths that match this glob, such as **/*.html. Can be repeated.
+    #[structopt(long, number_of_values = 1)]
+    include: Vec<String>,
+
+    /// Do not sync paths that match this glob, such as drafts/**. Can be repeated.
+    /// Objects that match are not deleted either.
+    #[structopt(long, number_of_values = 1)]
+    exclude: Vec<String>,
+
+    /// Show what would be uploaded and deleted, without changing anything.
+    #[structopt(long)]
+    dry_run: bool,
+
+    /// The most files to upload at once. Each file uploads up to
+    /// --concurrency parts at once.
+    #[structopt(long, default_value = "4")]
+    files: usize,
+
+    #[structopt(flatten)]
+    transfer: TransferOpt,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+/// Makes a prefix of an Amazon S3 bucket match a local directory, uploading
+/// new and changed files and, with `--delete`, deleting objects that have no file.
+/// Files are compared with their objects by size, modification time, and ETag.
+/// Objects are deleted up to 1,000 in each request, and any that could not be
+/// deleted are listed with the reason.
+/// # Arguments
+///
+/// * `-b BUCKET` - The name of the bucket.
+/// * `[-p PREFIX]` - The key prefix to sync to. Defaults to the whole bucket.
+/// * `-d DIR` - The local directory to sync from.
+/// * `[--delete]` - Delete objects under the prefix that have no file.
+/// * `[--include GLOB]...` - Only sync paths that match one of these globs.
+/// * `[--exclude GLOB]...` - Do not sync paths that match any of these globs.
+/// * `[--dry-run]` - Show what would change, without changing anything.
+/// * `[--threshold SIZE]` - Files larger than this are uploaded in parts.
+///   Defaults to 16MiB.
+/// * `[--part-size SIZE]` - The size of each part. Defaults to 8MiB.
+/// * `[--files N]` - The most files to upload at once. Defaults to 4.
+/// * `[--concurrency N]` - The most parts of each file to upload at once. Defaults to 4.
+/// * `[-r REGION]` - The Region in which the client is created.
+///   If not supplied, uses the value of the **AWS_REGION** environment variable.
+///   If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt {
+        bucket,
+        prefix,
+        dir,
+        delete,
+        include,
+        exclude,
+        dry_run,
+        files: file_concurrency,
+        transfer,
+        common,
+    } = Opt::from_args();
+
+    let filter = match Filter::new(&include, &exclude) {
+        Ok(filter) => filter,
+        Err(e) => {
+            println!("Got an error reading the globs:");
+            println!("{}", e);
+            process::exit(1);
+        }
+    };
+    let files = match sync::local_files(&dir, &filter) {
+        Ok(files) => files,
+        Err(e) => {
+            println!("Got an error reading {}:", dir.display());
+            println!("{}", e);
+            process::exit(1);
+        }
+    };
+    let prefix = sync::key_prefix(&prefix);
+
+    let shared = common.load().await;
+
+    shared.banner(
+        "S3",
+        PKG_VERSION,
+        &[
+            ("Bucket", &bucket),
+            ("Prefix", &prefix),
+            ("Directory", &dir.display().to_string()),
+        ],
+    );
+
+    let client = shared.s3();
+
+    let list_client = client.clone();
+    let list_bucket = bucket.clone();
+    let list_prefix = prefix.clone();
+    let objects = paginate(&PageOpt::default(), move |token, max_keys| {
+        let request = list_client
+            .list_objects_v2()
+            .bucket(&list_bucket)
+            .prefix(&list_prefix)
+            .set_continuation_token(token)
+            .set_max_keys(max_keys);
+        async move {
+            request.send().await.map(|resp| {
+                Page::with_token(
+                    resp.contents.unwrap_or_default(),
+                    resp.next_continuation_token,
+                )
+            })
+        }
+    })
+    .try_collect()
+    .await?;
+
+    let remote: HashMap<String, RemoteObject> = objects
+        .iter()
+        .filter_map(|object| {
+            let relative = object.key.as_deref()?.strip_prefix(prefix.as_str())?;
+            Some((relative.to_string(), RemoteObject::from(object)))
+        })
+        .collect();
+
+    let mut uploads = Vec::new();
+    for (relative, file) in &files {
+        match sync::compare(file, remote.get(relative), transfer.part_size).await {
+            Ok(Some(reason)) => uploads.push((relative, file, reason)),
+            Ok(None) => {}
+            Err(e) => {
+                println!("Got an error reading {}:", file.path.display());
+                println!("{}", e);
+                shared.exit(1);
+            }
+        }
+    }
+    let mut deletes: Vec<&String> = remote
+        .keys()
+        .filter(|relative| {
+            delete
+                && !relative.is_empty()
+                && !files.contains_key(*relative)
+                && filter.matches(relative)
+        })
+        .collect();
+    deletes.sort();
+
+    let mark = if dry_run { "(dry run) " } else { "" };
+    let unchanged = files.len() - uploads.len();
+
+    let uploaded = stream::iter(&uploads)
+        .map(|(relative, file, reason)| {
+            let key = format!("{}{}", prefix, relative);
+            let client = &client;
+            let bucket = &bucket;
+            let transfer = &transfer;
+            async move {
+                let line = format!(
+                    "{}upload: {} to s3://{}/{} ({})",
+                    mark,
+                    file.path.display(),
+                    bucket,
+                    key,
+                    reason
+                );
+                if !dry_run {
+                    let progress = ProgressBar::hidden();
+                    transfer::upload(client, bucket, &key, &file.path, transfer, &progress)
+                        .await
+                        .map_err(|e| (file.path.display().to_string(), e))?;
+                }
+                println!("{}", line);
+                Ok(())
+            }
+        })
+        .buffered(file_concurrency.max(1))
+        .try_collect::<()>()
+        .await;
+    if let Err((path, e)) = uploaded {
+        println!("Got an error uploading {}:", path);
+        println!("{}", e);
+        shared.exit(1);
+    }
+
+    let keys: Vec<String> = deletes
+        .iter()
+        .map(|relative| format!("{}{}", prefix, relative))
+        .collect();
+    let failed = if dry_run || keys.is_empty() {
+        Vec::new()
+    } else {
+        let objects = keys
+            .iter()
+            .map(|key| ObjectIdentifier::builder().key(key).build())
+            .collect();
+        empty::delete_versions(&client, &bucket, objects, |_| {}).await?
+    };
+    for key in &keys {
+        if !failed.iter().any(|e| &e.key == key) {
+            println!("{}delete: s3://{}/{}", mark, bucket, key);
+        }
+    }
+
+    println!();
+    println!(
+        "{}Uploaded {} files, deleted {} objects, and left {} files unchanged.",
+        mark,
+        uploads.len(),
+        deletes.len() - failed.len(),
+        unchanged
+    );
+
+    if !failed.is_empty() {
+        println!("Could not delete {} objects:", failed.len());
+        for e in &failed {
+            println!("  s3://{}/{}: {}: {}", bucket, e.key, e.code, e.message);
+        }
+        shared.exit(1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::CommonOpt;
+use aws_sdk_s3::{Error, PKG_VERSION};
+use s3_code_examples::transfer::{self, TransferOpt};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The name of the bucket.
+    #[structopt(short, long)]
+    bucket: String,
+
+    /// The key to upload to. Defaults to the file's name.
+    #[structopt(short, long)]
+    key: Option<String>,
+
+    /// The file to upload.
+    #[structopt(short, long, parse(from_os_str))]
+    file: PathBuf,
+
+    #[structopt(flatten)]
+    transfer: TransferOpt,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
+}
+
+/// Uploads a file to an Amazon S3 bucket, in parts if it is larger than the
+/// threshold, checking each part and the whole object against their MD5.
+/// If an upload in parts is interrupted, running the same command again
+/// resumes it, and only sends the parts that did not finish.
+/// # Arguments
+///
+/// * `-b BUCKET` - The name of the bucket.
+/// * `[-k KEY]` - The key to upload to. Defaults to the file's name.
+/// * `-f FILE` - The file to upload.
+/// * `[--threshold SIZE]` - Files larger than this are uploaded in parts.
+///   Defaults to 16MiB.
+/// * `[--part-size SIZE]` - The size of each part. Defaults to 8MiB.
+/// * `[--concurrency N]` - The most parts to upload at once. Defaults to 4.
+/// * `[-r REGION]` - The Region in which the client is created.
+///   If not supplied, uses the value of the **AWS_REGION** environment variable.
+///   If the environment variable is not set, defaults to **us-west-2**.
+/// * `[-v]` - Whether to display additional information.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let Opt {
+        bucket,
+        key,
+        file,
+        transfer,
+        common,
+    } = Opt::from_args();
+
+    let key = key.unwrap_or_else(|| {
+        file.file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    });
+
+    let shared = common.load().await;
+
+    shared.banner(
+        "S3",
+        PKG_VERSION,
+        &[
+            ("Bucket", &bucket),
+            ("Key", &key),
+            ("File", &file.display().to_string()),
+        ],
+    );
+
+    let client = shared.s3();
+
+    let progress = transfer::progress_bar(0);
+    let result = transfer::upload(&client, &bucket, &key, &file, &transfer, &progress).await;
+    progress.finish_and_clear();
+
+    match result {
+        Ok(upload) if upload.reused > 0 => println!(
+            "Uploaded {} in {} parts, {} of them by an earlier run. ETag: {}",
+            key, upload.parts, upload.reused, upload.etag
+        ),
+        Ok(upload) if upload.parts > 1 => println!(
+            "Uploaded {} in {} parts. ETag: {}",
+            key, upload.parts, upload.etag
+        ),
+        Ok(upload) => println!("Uploaded {}. ETag: {}", key, upload.etag),
+        Err(e) => {
+            println!("Got an error uploading the file:");
+            println!("{}", e);
+            println!("Run the same command again to resume the upload.");
+            shared.exit(1);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,642 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Bucket configurations as JSON, in the same shape as the AWS CLI's
+//! `s3api` commands read and write them, so that files can be shared
+//! between the two.
+//!
+//! Each JSON type converts to the SDK's type for the put request, and is
+//! built from the SDK's type in the get response, so a configuration that
+//! is read back can be edited and put again.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use aws_sdk_s3::model::{
+    AbortIncompleteMultipartUpload, BucketLifecycleConfiguration, CorsConfiguration, CorsRule,
+    ExpirationStatus, LifecycleExpiration, LifecycleRule, LifecycleRuleAndOperator,
+    LifecycleRuleFilter, NoncurrentVersionExpiration, NoncurrentVersionTransition,
+    ServerSideEncryption, ServerSideEncryptionByDefault, ServerSideEncryptionConfiguration,
+    ServerSideEncryptionRule, Tag, Tagging, Transition, TransitionStorageClass,
+};
+use aws_sdk_s3::SdkError;
+use aws_smithy_types::date_time::Format;
+use aws_smithy_types::retry::ProvideErrorKind;
+use aws_smithy_types::DateTime;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// A configuration file that could not be read.
+#[derive(Debug)]
+pub struct ConfigError(String);
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for ConfigError {}
+
+/// Reads a configuration from the JSON file at `path`.
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, ConfigError> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| ConfigError(format!("cannot read {}: {}", path.display(), e)))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| ConfigError(format!("cannot parse {}: {}", path.display(), e)))
+}
+
+/// The output of a get request, or `None` if it failed with the error
+/// `code` that S3 returns when the bucket has no such configuration.
+pub fn unless_missing<T, E>(
+    result: Result<T, SdkError<E>>,
+    code: &str,
+) -> Result<Option<T>, aws_sdk_s3::Error>
+where
+    E: ProvideErrorKind,
+    aws_sdk_s3::Error: From<SdkError<E>>,
+{
+    match result {
+        Ok(output) => Ok(Some(output)),
+        Err(SdkError::ServiceError { err, .. }) if err.code() == Some(code) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn parse_date(date: &Option<String>) -> Result<Option<DateTime>, ConfigError> {
+    date.as_deref()
+        .map(|d| {
+            DateTime::from_str(d, Format::DateTime).map_err(|_| {
+                ConfigError(format!(
+                    "expected a date such as 2022-01-01T00:00:00Z, got {}",
+                    d
+                ))
+            })
+        })
+        .transpose()
+}
+
+fn format_date(date: &Option<DateTime>) -> Option<String> {
+    date.as_ref().and_then(|d| d.fmt(Format::DateTime).ok())
+}
+
+/// A tag, as in `{"Key": "team", "Value": "data"}`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct TagJson {
+    pub key: String,
+    pub value: String,
+}
+
+impl From<&TagJson> for Tag {
+    fn from(tag: &TagJson) -> Self {
+        Tag::builder().key(&tag.key).value(&tag.value).build()
+    }
+}
+
+impl From<&Tag> for TagJson {
+    fn from(tag: &Tag) -> Self {
+        TagJson {
+            key: tag.key.clone().unwrap_or_default(),
+            value: tag.value.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// A bucket's tags, as in `{"TagSet": [{"Key": "team", "Value": "data"}]}`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct TaggingJson {
+    pub tag_set: Vec<TagJson>,
+}
+
+impl TaggingJson {
+    /// The tagging to put.
+    pub fn to_sdk(&self) -> Tagging {
+        Tagging::builder()
+            .set_tag_set(Some(self.tag_set.iter().map(Tag::from).collect()))
+            .build()
+    }
+
+    /// The tagging from a get response.
+    pub fn from_sdk(tags: &[Tag]) -> Self {
+        TaggingJson {
+            tag_set: tags.iter().map(TagJson::from).collect(),
+        }
+    }
+}
+
+/// A CORS rule.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct CorsRuleJson {
+    #[serde(rename = "ID", default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_headers: Vec<String>,
+    pub allowed_methods: Vec<String>,
+    pub allowed_origins: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expose_headers: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age_seconds: Option<i32>,
+}
+
+/// A bucket's CORS rules, as in `{"CORSRules": [...]}`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CorsJson {
+    #[serde(rename = "CORSRules")]
+    pub cors_rules: Vec<CorsRuleJson>,
+}
+
+impl CorsJson {
+    /// The CORS configuration to put.
+    pub fn to_sdk(&self) -> CorsConfiguration {
+        let rules = self
+            .cors_rules
+            .iter()
+            .map(|rule| {
+                CorsRule::builder()
+                    .set_id(rule.id.clone())
+                    .set_allowed_headers(Some(rule.allowed_headers.clone()))
+                    .set_allowed_methods(Some(rule.allowed_methods.clone()))
+                    .set_allowed_origins(Some(rule.allowed_origins.clone()))
+                    .set_expose_headers(Some(rule.expose_headers.clone()))
+                    .set_max_age_seconds(rule.max_age_seconds)
+                    .build()
+            })
+            .collect();
+        CorsConfiguration::builder()
+            .set_cors_rules(Some(rules))
+            .build()
+    }
+
+    /// The CORS configuration from a get response.
+    pub fn from_sdk(rules: &[CorsRule]) -> Self {
+        let rules = rules
+            .iter()
+            .map(|rule| CorsRuleJson {
+                id: rule.id.clone(),
+                allowed_headers: rule.allowed_headers.clone().unwrap_or_default(),
+                allowed_methods: rule.allowed_methods.clone().unwrap_or_default(),
+                allowed_origins: rule.allowed_origins.clone().unwrap_or_default(),
+                expose_headers: rule.expose_headers.clone().unwrap_or_default(),
+                max_age_seconds: Some(rule.max_age_seconds).filter(|s| *s != 0),
+            })
+            .collect();
+        CorsJson { cors_rules: rules }
+    }
+}
+
+/// Which objects a lifecycle rule applies to: those with a key prefix,
+/// those with a tag, or those with a prefix and all of several tags.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct FilterJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<TagJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub and: Option<AndJson>,
+}
+
+/// The prefix and tags that objects must all have.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct AndJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<TagJson>,
+}
+
+/// When current versions expire.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct ExpirationJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub days: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expired_object_delete_marker: Option<bool>,
+}
+
+/// When current versions move to another storage class.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct TransitionJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub days: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    pub storage_class: String,
+}
+
+/// When noncurrent versions move to another storage class.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct NoncurrentTransitionJson {
+    pub noncurrent_days: i32,
+    pub storage_class: String,
+}
+
+/// When noncurrent versions expire.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct NoncurrentExpirationJson {
+    pub noncurrent_days: i32,
+}
+
+/// When unfinished multipart uploads are aborted.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct AbortJson {
+    pub days_after_initiation: i32,
+}
+
+/// A lifecycle rule.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct LifecycleRuleJson {
+    #[serde(rename = "ID", default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// `Enabled` or `Disabled`.
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<FilterJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiration: Option<ExpirationJson>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transitions: Vec<TransitionJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub noncurrent_version_expiration: Option<NoncurrentExpirationJson>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub noncurrent_version_transitions: Vec<NoncurrentTransitionJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abort_incomplete_multipart_upload: Option<AbortJson>,
+}
+
+/// A bucket's lifecycle rules, as in `{"Rules": [...]}`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct LifecycleJson {
+    pub rules: Vec<LifecycleRuleJson>,
+}
+
+fn filter_to_sdk(filter: &FilterJson) -> Result<LifecycleRuleFilter, ConfigError> {
+    match filter {
+        FilterJson {
+            prefix: Some(prefix),
+            tag: None,
+            and: None,
+        } => Ok(LifecycleRuleFilter::Prefix(prefix.clone())),
+        FilterJson {
+            prefix: None,
+            tag: Some(tag),
+            and: None,
+        } => Ok(LifecycleRuleFilter::Tag(tag.into())),
+        FilterJson {
+            prefix: None,
+            tag: None,
+            and: Some(and),
+        } => Ok(LifecycleRuleFilter::And(
+            LifecycleRuleAndOperator::builder()
+                .set_prefix(and.prefix.clone())
+                .set_tags(Some(and.tags.iter().map(Tag::from).collect()))
+                .build(),
+        )),
+        FilterJson {
+            prefix: None,
+            tag: None,
+            and: None,
+        } => Ok(LifecycleRuleFilter::Prefix(String::new())),
+        _ => Err(ConfigError(
+            "a lifecycle filter has one of Prefix, Tag, or And".to_string(),
+        )),
+    }
+}
+
+fn filter_from_sdk(filter: &LifecycleRuleFilter) -> FilterJson {
+    match filter {
+        LifecycleRuleFilter::Prefix(prefix) => FilterJson {
+            prefix: Some(prefix.clone()),
+            ..FilterJson::default()
+        },
+        LifecycleRuleFilter::Tag(tag) => FilterJson {
+            tag: Some(tag.into()),
+            ..FilterJson::default()
+        },
+        LifecycleRuleFilter::And(and) => FilterJson {
+            and: Some(AndJson {
+                prefix: and.prefix.clone(),
+                tags: and.tags.iter().flatten().map(TagJson::from).collect(),
+            }),
+            ..FilterJson::default()
+        },
+        _ => FilterJson::default(),
+    }
+}
+
+impl LifecycleRuleJson {
+    fn to_sdk(&self) -> Result<LifecycleRule, ConfigError> {
+        let status = match self.status.as_str() {
+            "Enabled" => ExpirationStatus::Enabled,
+            "Disabled" => ExpirationStatus::Disabled,
+            other => {
+                return Err(ConfigError(format!(
+                    "expected Status Enabled or Disabled, got {}",
+                    other
+                )))
+            }
+        };
+        let expiration = match &self.expiration {
+            Some(e) => Some(
+                LifecycleExpiration::builder()
+                    .set_days(e.days)
+                    .set_date(parse_date(&e.date)?)
+                    .set_expired_object_delete_marker(e.expired_object_delete_marker)
+                    .build(),
+            ),
+            None => None,
+        };
+        let transitions = self
+            .transitions
+            .iter()
+            .map(|t| {
+                Ok(Transition::builder()
+                    .set_days(t.days)
+                    .set_date(parse_date(&t.date)?)
+                    .storage_class(TransitionStorageClass::from(t.storage_class.as_str()))
+                    .build())
+            })
+            .collect::<Result<Vec<_>, ConfigError>>()?;
+        let noncurrent_transitions = self
+            .noncurrent_version_transitions
+            .iter()
+            .map(|t| {
+                NoncurrentVersionTransition::builder()
+                    .noncurrent_days(t.noncurrent_days)
+                    .storage_class(TransitionStorageClass::from(t.storage_class.as_str()))
+                    .build()
+            })
+            .collect();
+        Ok(LifecycleRule::builder()
+            .set_id(self.id.clone())
+            .status(status)
+            .filter(filter_to_sdk(&self.filter.clone().unwrap_or_default())?)
+            .set_expiration(expiration)
+            .set_transitions(Some(transitions))
+            .set_noncurrent_version_expiration(self.noncurrent_version_expiration.as_ref().map(
+                |e| {
+                    NoncurrentVersionExpiration::builder()
+                        .noncurrent_days(e.noncurrent_days)
+                        .build()
+                },
+            ))
+            .set_noncurrent_version_transitions(Some(noncurrent_transitions))
+            .set_abort_incomplete_multipart_upload(
+                self.abort_incomplete_multipart_upload.as_ref().map(|a| {
+                    AbortIncompleteMultipartUpload::builder()
+                        .days_after_initiation(a.days_after_initiation)
+                        .build()
+                }),
+            )
+            .build())
+    }
+
+    fn from_sdk(rule: &LifecycleRule) -> Self {
+        LifecycleRuleJson {
+            id: rule.id.clone(),
+            status: rule
+                .status
+                .as_ref()
+                .map(|s| s.as_str().to_string())
+                .unwrap_or_default(),
+            filter: match (&rule.filter, &rule.prefix) {
+                (Some(filter), _) => Some(filter_from_sdk(filter)),
+                (None, Some(prefix)) => Some(FilterJson {
+                    prefix: Some(prefix.clone()),
+                    ..FilterJson::default()
+                }),
+                (None, None) => None,
+            },
+            expiration: rule.expiration.as_ref().map(|e| ExpirationJson {
+                days: Some(e.days).filter(|d| *d != 0),
+                date: format_date(&e.date),
+                expired_object_delete_marker: Some(true).filter(|_| e.expired_object_delete_marker),
+            }),
+            transitions: rule
+                .transitions
+                .iter()
+                .flatten()
+                .map(|t| TransitionJson {
+                    days: Some(t.days).filter(|d| *d != 0),
+                    date: format_date(&t.date),
+                    storage_class: t
+                        .storage_class
+                        .as_ref()
+                        .map(|c| c.as_str().to_string())
+                        .unwrap_or_default(),
+                })
+                .collect(),
+            noncurrent_version_expiration: rule.noncurrent_version_expiration.as_ref().map(|e| {
+                NoncurrentExpirationJson {
+                    noncurrent_days: e.noncurrent_days,
+                }
+            }),
+            noncurrent_version_transitions: rule
+                .noncurrent_version_transitions
+                .iter()
+                .flatten()
+                .map(|t| NoncurrentTransitionJson {
+                    noncurrent_days: t.noncurrent_days,
+                    storage_class: t
+                        .storage_class
+                        .as_ref()
+                        .map(|c| c.as_str().to_string())
+                        .unwrap_or_default(),
+                })
+                .collect(),
+            abort_incomplete_multipart_upload: rule.abort_incomplete_multipart_upload.as_ref().map(
+                |a| AbortJson {
+                    days_after_initiation: a.days_after_initiation,
+                },
+            ),
+        }
+    }
+}
+
+impl LifecycleJson {
+    /// The lifecycle configuration to put.
+    pub fn to_sdk(&self) -> Result<BucketLifecycleConfiguration, ConfigError> {
+        let rules = self
+            .rules
+            .iter()
+            .map(LifecycleRuleJson::to_sdk)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(BucketLifecycleConfiguration::builder()
+            .set_rules(Some(rules))
+            .build())
+    }
+
+    /// The lifecycle configuration from a get response.
+    pub fn from_sdk(rules: &[LifecycleRule]) -> Self {
+        LifecycleJson {
+            rules: rules.iter().map(LifecycleRuleJson::from_sdk).collect(),
+        }
+    }
+}
+
+/// How new objects are encrypted by default.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptionByDefaultJson {
+    /// `AES256` for S3-managed keys, or `aws:kms` for a KMS key.
+    #[serde(rename = "SSEAlgorithm")]
+    pub sse_algorithm: String,
+    #[serde(
+        rename = "KMSMasterKeyID",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub kms_master_key_id: Option<String>,
+}
+
+/// A default encryption rule.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct EncryptionRuleJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apply_server_side_encryption_by_default: Option<EncryptionByDefaultJson>,
+    #[serde(default)]
+    pub bucket_key_enabled: bool,
+}
+
+/// A bucket's default encryption, as in
+/// `{"Rules": [{"ApplyServerSideEncryptionByDefault": {"SSEAlgorithm": "aws:kms", ...}}]}`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct EncryptionJson {
+    pub rules: Vec<EncryptionRuleJson>,
+}
+
+impl EncryptionJson {
+    /// The encryption configuration to put.
+    pub fn to_sdk(&self) -> Result<ServerSideEncryptionConfiguration, ConfigError> {
+        let rules = self
+            .rules
+            .iter()
+            .map(|rule| {
+                let by_default = match &rule.apply_server_side_encryption_by_default {
+                    Some(d) => {
+                        let algorithm = match d.sse_algorithm.as_str() {
+                            "AES256" => ServerSideEncryption::Aes256,
+                            "aws:kms" => ServerSideEncryption::AwsKms,
+                            other => {
+                                return Err(ConfigError(format!(
+                                    "expected SSEAlgorithm AES256 or aws:kms, got {}",
+                                    other
+                                )))
+                            }
+                        };
+                        Some(
+                            ServerSideEncryptionByDefault::builder()
+                                .sse_algorithm(algorithm)
+                                .set_kms_master_key_id(d.kms_master_key_id.clone())
+                                .build(),
+                        )
+                    }
+                    None => None,
+                };
+                Ok(ServerSideEncryptionRule::builder()
+                    .set_apply_server_side_encryption_by_default(by_default)
+                    .bucket_key_enabled(rule.bucket_key_enabled)
+                    .build())
+            })
+            .collect::<Result<Vec<_>, ConfigError>>()?;
+        Ok(ServerSideEncryptionConfiguration::builder()
+            .set_rules(Some(rules))
+            .build())
+    }
+
+    /// The encryption configuration from a get response.
+    pub fn from_sdk(rules: &[ServerSideEncryptionRule]) -> Self {
+        let rules = rules
+            .iter()
+            .map(|rule| EncryptionRuleJson {
+                apply_server_side_encryption_by_default: rule
+                    .apply_server_side_encryption_by_default
+                    .as_ref()
+                    .map(|d| EncryptionByDefaultJson {
+                        sse_algorithm: d
+                            .sse_algorithm
+                            .as_ref()
+                            .map(|a| a.as_str().to_string())
+                            .unwrap_or_default(),
+                        kms_master_key_id: d.kms_master_key_id.clone(),
+                    }),
+                bucket_key_enabled: rule.bucket_key_enabled,
+            })
+            .collect();
+        EncryptionJson { rules }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lifecycle_rules_round_trip() {
+        let json = r#"{"Rules": [
+            {"ID": "logs", "Status": "Enabled", "Filter": {"Prefix": "logs/"},
+             "Expiration": {"Days": 365},
+             "Transitions": [{"Days": 30, "StorageClass": "GLACIER"}],
+             "AbortIncompleteMultipartUpload": {"DaysAfterInitiation": 7}},
+            {"Status": "Disabled",
+             "Filter": {"And": {"Prefix": "tmp/", "Tags": [{"Key": "scratch", "Value": "yes"}]}},
+             "NoncurrentVersionExpiration": {"NoncurrentDays": 30}}
+        ]}"#;
+        let lifecycle: LifecycleJson = serde_json::from_str(json).unwrap();
+
+        let sdk = lifecycle.to_sdk().unwrap();
+        let rules = sdk.rules.unwrap();
+        assert_eq!(
+            rules[0].filter,
+            Some(LifecycleRuleFilter::Prefix("logs/".into()))
+        );
+        assert_eq!(rules[1].status, Some(ExpirationStatus::Disabled));
+        assert_eq!(LifecycleJson::from_sdk(&rules), lifecycle);
+
+        let typo = r#"{"Rules": [{"Status": "Enabled", "Expiraton": {"Days": 1}}]}"#;
+        assert!(serde_json::from_str::<LifecycleJson>(typo).is_err());
+    }
+
+    #[test]
+    fn encryption_round_trips() {
+        let json = r#"{"Rules": [{"ApplyServerSideEncryptionByDefault":
+            {"SSEAlgorithm": "aws:kms", "KMSMasterKeyID": "alias/data"},
+            "BucketKeyEnabled": true}]}"#;
+        let encryption: EncryptionJson = serde_json::from_str(json).unwrap();
+
+        let rules = encryption.to_sdk().unwrap().rules.unwrap();
+        let by_default = rules[0].apply_server_side_encryption_by_default.as_ref();
+        assert_eq!(
+            by_default.and_then(|d| d.sse_algorithm.clone()),
+            Some(ServerSideEncryption::AwsKms)
+        );
+        assert_eq!(EncryptionJson::from_sdk(&rules), encryption);
+
+        let unknown =
+            r#"{"Rules": [{"ApplyServerSideEncryptionByDefault": {"SSEAlgorithm": "DES"}}]}"#;
+        let unknown: EncryptionJson = serde_json::from_str(unknown).unwrap();
+        assert!(unknown.to_sdk().is_err());
+    }
+}
@@ -0,0 +1,134 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Deleting every version of every object in a bucket.
+//!
+//! Deleting an object from a versioned bucket only adds a delete marker, and
+//! a bucket cannot be deleted while it holds any object version or delete
+//! marker. Emptying a bucket therefore deletes each version and each delete
+//! marker by its version ID, up to 1,000 in each `DeleteObjects` request.
+
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use aws_sdk_s3::error::{DeleteObjectsError, ListObjectVersionsError};
+use aws_sdk_s3::model::{Delete, ObjectIdentifier};
+use aws_sdk_s3::{Client, SdkError};
+
+/// The most objects one `DeleteObjects` request can delete.
+pub const MAX_BATCH: usize = 1000;
+
+/// Every object version and delete marker in `bucket` whose key starts with
+/// `prefix`, ordered by key.
+pub async fn list_versions(
+    client: &Client,
+    bucket: &str,
+    prefix: Option<&str>,
+) -> Result<Vec<ObjectIdentifier>, SdkError<ListObjectVersionsError>> {
+    let client = client.clone();
+    let bucket = bucket.to_string();
+    let prefix = prefix.map(str::to_string);
+
+    // A page ends at a key and version ID, and the next page starts with
+    // the versions after them.
+    let mut objects = paginate(&PageOpt::default(), move |markers, max_keys| {
+        let (key_marker, version_id_marker) = match markers {
+            Some((key, version_id)) => (Some(key), version_id),
+            None => (None, None),
+        };
+        let request = client
+            .list_object_versions()
+            .bucket(&bucket)
+            .set_prefix(prefix.clone())
+            .set_key_marker(key_marker)
+            .set_version_id_marker(version_id_marker)
+            .set_max_keys(max_keys);
+        async move {
+            request.send().await.map(|resp| {
+                let next = match resp.next_key_marker {
+                    Some(key) if resp.is_truncated => Some((key, resp.next_version_id_marker)),
+                    _ => None,
+                };
+                let versions = resp
+                    .versions
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|v| (v.key, v.version_id));
+                let markers = resp
+                    .delete_markers
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|m| (m.key, m.version_id));
+                let items = versions
+                    .chain(markers)
+                    .map(|(key, version_id)| {
+                        ObjectIdentifier::builder()
+                            .set_key(key)
+                            .set_version_id(version_id)
+                            .build()
+                    })
+                    .collect();
+                Page::new(items, next)
+            })
+        }
+    })
+    .try_collect()
+    .await?;
+
+    // Each page lists its versions, then its delete markers.
+    objects.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(objects)
+}
+
+/// An object version that `DeleteObjects` could not delete.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyError {
+    /// The object's key.
+    pub key: String,
+    /// The version that was not deleted.
+    pub version_id: Option<String>,
+    /// The error code, such as `AccessDenied`.
+    pub code: String,
+    /// The error message.
+    pub message: String,
+}
+
+/// Deletes `objects` from `bucket` in batches of [`MAX_BATCH`], calling
+/// `progress` with the number deleted so far after each batch.
+///
+/// Objects that could not be deleted are returned rather than stopping
+/// the deletion, so that one locked object does not keep the rest.
+/// Objects without a version ID are deleted as `DeleteObject` would,
+/// which in a versioned bucket adds a delete marker.
+pub async fn delete_versions(
+    client: &Client,
+    bucket: &str,
+    objects: Vec<ObjectIdentifier>,
+    mut progress: impl FnMut(usize),
+) -> Result<Vec<KeyError>, SdkError<DeleteObjectsError>> {
+    let mut failed = Vec::new();
+    let mut deleted = 0;
+    for batch in objects.chunks(MAX_BATCH) {
+        let delete = Delete::builder()
+            .set_objects(Some(batch.to_vec()))
+            .quiet(true)
+            .build();
+        let resp = client
+            .delete_objects()
+            .bucket(bucket)
+            .delete(delete)
+            .send()
+            .await?;
+        // In quiet mode, only the objects that were not deleted are listed.
+        let errors = resp.errors.unwrap_or_default();
+        deleted += batch.len() - errors.len();
+        failed.extend(errors.into_iter().map(|e| KeyError {
+            key: e.key.unwrap_or_default(),
+            version_id: e.version_id,
+            code: e.code.unwrap_or_default(),
+            message: e.message.unwrap_or_default(),
+        }));
+        progress(deleted);
+    }
+    Ok(failed)
+}
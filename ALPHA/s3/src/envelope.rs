@@ -0,0 +1,309 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Client-side envelope encryption: each object is encrypted locally with
+//! its own AES-256-GCM data key from AWS KMS `GenerateDataKey`, and the data
+//! key, wrapped by the KMS key, is stored in the object's metadata next to
+//! the IV. Only someone allowed to call KMS `Decrypt` with that KMS key can
+//! unwrap the data key and read the object; S3 only ever sees ciphertext.
+//!
+//! The metadata is laid out as the AWS S3 Encryption Client (v2) lays it
+//! out with KMS keys, so either can read what the other wrote. The
+//! encryption context names the content algorithm, so that KMS refuses to
+//! unwrap the key if the metadata has been changed to claim another one.
+
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryInto;
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use aws_sdk_kms::model::DataKeySpec;
+use aws_sdk_s3::ByteStream;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+
+/// The content encryption algorithm, as the metadata names it.
+pub const CEK_ALG: &str = "AES/GCM/NoPadding";
+/// How the data key is wrapped: by KMS, with an encryption context.
+pub const WRAP_ALG: &str = "kms+context";
+/// The encryption context key that names the content algorithm.
+const CONTEXT_CEK_ALG: &str = "aws:x-amz-cek-alg";
+
+// The object metadata keys.
+const KEY: &str = "x-amz-key-v2";
+const IV: &str = "x-amz-iv";
+const CEK: &str = "x-amz-cek-alg";
+const WRAP: &str = "x-amz-wrap-alg";
+const MATDESC: &str = "x-amz-matdesc";
+const TAG_LEN: &str = "x-amz-tag-len";
+const PLAINTEXT_LEN: &str = "x-amz-unencrypted-content-length";
+
+/// Why an object could not be encrypted, stored, fetched, or decrypted.
+#[derive(Debug)]
+pub enum EnvelopeError {
+    /// KMS could not generate or unwrap the data key.
+    Kms(aws_sdk_kms::Error),
+    /// S3 could not store or return the object.
+    S3(aws_sdk_s3::Error),
+    /// The object's body could not be read.
+    Io(io::Error),
+    /// The object's metadata is missing or not as this module writes it.
+    Metadata(String),
+    /// The object could not be encrypted or decrypted with the data key,
+    /// such as when KMS returned none, or one that is not an AES-256 key.
+    DataKey(String),
+    /// The object could not be decrypted: it, its IV, or its key was changed.
+    Integrity,
+}
+
+impl fmt::Display for EnvelopeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvelopeError::Kms(e) => write!(f, "{}", e),
+            EnvelopeError::S3(e) => write!(f, "{}", e),
+            EnvelopeError::Io(e) => write!(f, "{}", e),
+            EnvelopeError::Metadata(e) => write!(f, "the object is not envelope encrypted: {}", e),
+            EnvelopeError::DataKey(e) => write!(f, "cannot use the data key: {}", e),
+            EnvelopeError::Integrity => {
+                f.write_str("the object failed authentication and may have been tampered with")
+            }
+        }
+    }
+}
+
+impl Error for EnvelopeError {}
+
+/// Where to find what is needed to decrypt an object.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Envelope {
+    /// The data key, wrapped by the KMS key.
+    pub wrapped_key: Vec<u8>,
+    /// The GCM IV the object was encrypted with.
+    pub iv: [u8; NONCE_LEN],
+    /// The encryption context the data key was wrapped with.
+    pub context: BTreeMap<String, String>,
+}
+
+impl Envelope {
+    /// The object metadata that records this envelope for `plaintext_len` bytes.
+    pub fn to_metadata(&self, plaintext_len: usize) -> HashMap<String, String> {
+        let matdesc = serde_json::to_string(&self.context).expect("strings serialize");
+        vec![
+            (KEY, base64::encode(&self.wrapped_key)),
+            (IV, base64::encode(self.iv)),
+            (CEK, CEK_ALG.to_string()),
+            (WRAP, WRAP_ALG.to_string()),
+            (MATDESC, matdesc),
+            (TAG_LEN, (AES_256_GCM.tag_len() * 8).to_string()),
+            (PLAINTEXT_LEN, plaintext_len.to_string()),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect()
+    }
+
+    /// Reads the envelope from an object's metadata, refusing any
+    /// algorithm other than the ones this module writes.
+    pub fn from_metadata(metadata: &HashMap<String, String>) -> Result<Self, EnvelopeError> {
+        let field = |name: &str| {
+            metadata
+                .get(name)
+                .ok_or_else(|| EnvelopeError::Metadata(format!("no {} metadata", name)))
+        };
+        let expect = |name: &str, value: &str| match field(name)? {
+            actual if actual == value => Ok(()),
+            actual => Err(EnvelopeError::Metadata(format!(
+                "{} is {}, not {}",
+                name, actual, value
+            ))),
+        };
+        expect(CEK, CEK_ALG)?;
+        expect(WRAP, WRAP_ALG)?;
+
+        let bad = |name: &str| EnvelopeError::Metadata(format!("{} is malformed", name));
+        let wrapped_key = base64::decode(field(KEY)?).map_err(|_| bad(KEY))?;
+        let iv = base64::decode(field(IV)?).map_err(|_| bad(IV))?;
+        let iv = iv.try_into().map_err(|_| bad(IV))?;
+        let context: BTreeMap<String, String> =
+            serde_json::from_str(field(MATDESC)?).map_err(|_| bad(MATDESC))?;
+        if context.get(CONTEXT_CEK_ALG).map(String::as_str) != Some(CEK_ALG) {
+            return Err(bad(MATDESC));
+        }
+        Ok(Envelope {
+            wrapped_key,
+            iv,
+            context,
+        })
+    }
+}
+
+fn aes_key(key: &[u8]) -> Result<LessSafeKey, EnvelopeError> {
+    UnboundKey::new(&AES_256_GCM, key)
+        .map(LessSafeKey::new)
+        .map_err(|_| EnvelopeError::DataKey(format!("expected 32 bytes, got {}", key.len())))
+}
+
+/// Encrypts `plaintext` in place with `key` and `iv`, appending the GCM tag.
+/// Fails if `key` is not an AES-256 key.
+pub fn seal(key: &[u8], iv: [u8; NONCE_LEN], plaintext: &mut Vec<u8>) -> Result<(), EnvelopeError> {
+    aes_key(key)?
+        .seal_in_place_append_tag(Nonce::assume_unique_for_key(iv), Aad::empty(), plaintext)
+        .map_err(|_| EnvelopeError::DataKey("the object is too large to encrypt".to_string()))
+}
+
+/// Decrypts `ciphertext`, which ends with its GCM tag, with `key` and `iv`,
+/// returning the plaintext unless the ciphertext fails authentication.
+pub fn open(
+    key: &[u8],
+    iv: [u8; NONCE_LEN],
+    mut ciphertext: Vec<u8>,
+) -> Result<Vec<u8>, EnvelopeError> {
+    let len = aes_key(key)?
+        .open_in_place(
+            Nonce::assume_unique_for_key(iv),
+            Aad::empty(),
+            &mut ciphertext,
+        )
+        .map_err(|_| EnvelopeError::Integrity)?
+        .len();
+    ciphertext.truncate(len);
+    Ok(ciphertext)
+}
+
+fn missing_data_key() -> EnvelopeError {
+    EnvelopeError::DataKey("KMS returned no data key".to_string())
+}
+
+/// Encrypts `plaintext` with a new data key from the KMS key `kms_key_id`
+/// and puts it in `bucket` under `key`, with the wrapped data key in its metadata.
+pub async fn put_object(
+    s3: &aws_sdk_s3::Client,
+    kms: &aws_sdk_kms::Client,
+    bucket: &str,
+    key: &str,
+    kms_key_id: &str,
+    plaintext: Vec<u8>,
+) -> Result<(), EnvelopeError> {
+    let context: BTreeMap<String, String> =
+        [(CONTEXT_CEK_ALG.to_string(), CEK_ALG.to_string())].into();
+    let data_key = kms
+        .generate_data_key()
+        .key_id(kms_key_id)
+        .key_spec(DataKeySpec::Aes256)
+        .set_encryption_context(Some(context.clone().into_iter().collect()))
+        .send()
+        .await
+        .map_err(|e| EnvelopeError::Kms(e.into()))?;
+    let (data_key_plaintext, wrapped_key) = match (data_key.plaintext, data_key.ciphertext_blob) {
+        (Some(plaintext), Some(wrapped)) => (plaintext, wrapped),
+        _ => return Err(missing_data_key()),
+    };
+
+    let mut iv = [0; NONCE_LEN];
+    SystemRandom::new().fill(&mut iv).map_err(|_| {
+        EnvelopeError::Io(io::Error::other("the system has no source of randomness"))
+    })?;
+    let envelope = Envelope {
+        wrapped_key: wrapped_key.into_inner(),
+        iv,
+        context,
+    };
+    let metadata = envelope.to_metadata(plaintext.len());
+    let mut body = plaintext;
+    seal(data_key_plaintext.as_ref(), iv, &mut body)?;
+
+    s3.put_object()
+        .bucket(bucket)
+        .key(key)
+        .set_metadata(Some(metadata))
+        .body(ByteStream::from(body))
+        .send()
+        .await
+        .map_err(|e| EnvelopeError::S3(e.into()))?;
+    Ok(())
+}
+
+/// Gets the object in `bucket` under `key`, unwraps its data key with KMS,
+/// and returns it decrypted.
+pub async fn get_object(
+    s3: &aws_sdk_s3::Client,
+    kms: &aws_sdk_kms::Client,
+    bucket: &str,
+    key: &str,
+) -> Result<Vec<u8>, EnvelopeError> {
+    let resp = s3
+        .get_object()
+        .bucket(bucket)
+        .key(key)
+        .send()
+        .await
+        .map_err(|e| EnvelopeError::S3(e.into()))?;
+    let envelope = Envelope::from_metadata(&resp.metadata.unwrap_or_default())?;
+    let ciphertext = resp
+        .body
+        .collect()
+        .await
+        .map_err(|e| EnvelopeError::Io(io::Error::other(e)))?
+        .into_bytes()
+        .to_vec();
+
+    let data_key = kms
+        .decrypt()
+        .ciphertext_blob(aws_sdk_kms::Blob::new(envelope.wrapped_key))
+        .set_encryption_context(Some(envelope.context.into_iter().collect()))
+        .send()
+        .await
+        .map_err(|e| EnvelopeError::Kms(e.into()))?;
+    let data_key = data_key.plaintext.ok_or_else(missing_data_key)?;
+    open(data_key.as_ref(), envelope.iv, ciphertext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY_BYTES: [u8; 32] = [7; 32];
+    const IV_BYTES: [u8; NONCE_LEN] = [1; NONCE_LEN];
+
+    #[test]
+    fn sealed_objects_open_only_unchanged() {
+        let mut data = b"region,total\nwest,42\n".to_vec();
+        seal(&KEY_BYTES, IV_BYTES, &mut data).unwrap();
+        assert_eq!(data.len(), 21 + AES_256_GCM.tag_len());
+
+        assert_eq!(
+            open(&KEY_BYTES, IV_BYTES, data.clone()).unwrap(),
+            b"region,total\nwest,42\n"
+        );
+        let mut changed = data.clone();
+        changed[0] ^= 1;
+        assert!(open(&KEY_BYTES, IV_BYTES, changed).is_err());
+        assert!(open(&KEY_BYTES, [2; NONCE_LEN], data).is_err());
+
+        // A key of the wrong length is refused, not a panic.
+        let mut data = b"region,total\n".to_vec();
+        assert!(matches!(
+            seal(&KEY_BYTES[..16], IV_BYTES, &mut data),
+            Err(EnvelopeError::DataKey(_))
+        ));
+    }
+
+    #[test]
+    fn envelopes_round_trip_through_metadata() {
+        let envelope = Envelope {
+            wrapped_key: b"wrapped".to_vec(),
+            iv: IV_BYTES,
+            context: [(CONTEXT_CEK_ALG.to_string(), CEK_ALG.to_string())].into(),
+        };
+        let mut metadata = envelope.to_metadata(21);
+        assert_eq!(metadata[PLAINTEXT_LEN], "21");
+        assert_eq!(metadata[TAG_LEN], "128");
+        assert_eq!(Envelope::from_metadata(&metadata).unwrap(), envelope);
+
+        metadata.insert(CEK.to_string(), "AES/CBC/PKCS5Padding".to_string());
+        assert!(Envelope::from_metadata(&metadata).is_err());
+    }
+}
@@ -0,0 +1,191 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! An inventory of the objects under a prefix: what `ListObjectsV2` says of
+//! each object, and optionally its tags and metadata, which take a
+//! `GetObjectTagging` or `HeadObject` request per object.
+
+use std::collections::BTreeMap;
+
+use aws_sdk_s3::model::Object;
+use aws_sdk_s3::{Client, Error};
+use aws_smithy_types::DateTime;
+use futures_util::{stream, StreamExt, TryStreamExt};
+
+/// What to find out about each object beyond what listing it shows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Details {
+    /// Get the object's tags.
+    pub tags: bool,
+    /// Get the object's content type and user metadata.
+    pub metadata: bool,
+}
+
+/// One object in the inventory.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    /// The key of the object.
+    pub key: String,
+    /// The size of the object, in bytes.
+    pub size: i64,
+    /// The storage class, `STANDARD` if S3 doesn't say.
+    pub storage_class: String,
+    /// When the object was last modified.
+    pub last_modified: Option<DateTime>,
+    /// The ETag, without its quotes.
+    pub etag: Option<String>,
+    /// The tags, if they were asked for.
+    pub tags: Option<BTreeMap<String, String>>,
+    /// The content type, if metadata was asked for.
+    pub content_type: Option<String>,
+    /// The user metadata, without its `x-amz-meta-` prefix, if it was asked for.
+    pub metadata: Option<BTreeMap<String, String>>,
+}
+
+impl From<&Object> for Entry {
+    fn from(object: &Object) -> Self {
+        Entry {
+            key: object.key.clone().unwrap_or_default(),
+            size: object.size,
+            storage_class: object
+                .storage_class
+                .as_ref()
+                .map_or("STANDARD", |c| c.as_str())
+                .to_string(),
+            last_modified: object.last_modified,
+            etag: object
+                .e_tag
+                .as_ref()
+                .map(|e| e.trim_matches('"').to_string()),
+            tags: None,
+            content_type: None,
+            metadata: None,
+        }
+    }
+}
+
+async fn add_details(
+    client: &Client,
+    bucket: &str,
+    mut entry: Entry,
+    details: Details,
+) -> Result<Entry, Error> {
+    if details.tags {
+        let resp = client
+            .get_object_tagging()
+            .bucket(bucket)
+            .key(&entry.key)
+            .send()
+            .await?;
+        entry.tags = Some(
+            resp.tag_set
+                .unwrap_or_default()
+                .into_iter()
+                .map(|tag| (tag.key.unwrap_or_default(), tag.value.unwrap_or_default()))
+                .collect(),
+        );
+    }
+    if details.metadata {
+        let resp = client
+            .head_object()
+            .bucket(bucket)
+            .key(&entry.key)
+            .send()
+            .await?;
+        entry.content_type = resp.content_type;
+        entry.metadata = Some(resp.metadata.unwrap_or_default().into_iter().collect());
+    }
+    Ok(entry)
+}
+
+/// Makes an entry of each of `objects`, getting the `details` asked for
+/// with at most `concurrency` objects at a time. The entries are in the
+/// same order as the objects.
+pub async fn entries(
+    client: &Client,
+    bucket: &str,
+    objects: &[Object],
+    details: Details,
+    concurrency: usize,
+) -> Result<Vec<Entry>, Error> {
+    stream::iter(objects.iter().map(Entry::from))
+        .map(|entry| add_details(client, bucket, entry, details))
+        .buffered(concurrency.max(1))
+        .try_collect()
+        .await
+}
+
+/// The number and size of the objects in a group.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Total {
+    /// How many objects there are.
+    pub objects: u64,
+    /// How many bytes they hold.
+    pub bytes: i64,
+}
+
+/// The group an object's totals are counted in: its key up to and including
+/// the first `/` after `prefix`, or `prefix` itself for objects directly under it.
+pub fn group(prefix: &str, key: &str) -> String {
+    let rest = key.strip_prefix(prefix).unwrap_or(key);
+    match rest.find('/') {
+        Some(end) => format!("{}{}", prefix, &rest[..=end]),
+        None => prefix.to_string(),
+    }
+}
+
+/// Totals the `entries` by group under `prefix` (see [`group`]) and storage class.
+pub fn totals(prefix: &str, entries: &[Entry]) -> BTreeMap<(String, String), Total> {
+    let mut totals: BTreeMap<(String, String), Total> = BTreeMap::new();
+    for entry in entries {
+        let total = totals
+            .entry((group(prefix, &entry.key), entry.storage_class.clone()))
+            .or_default();
+        total.objects += 1;
+        total.bytes += entry.size;
+    }
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, size: i64, storage_class: &str) -> Entry {
+        Entry {
+            key: key.to_string(),
+            size,
+            storage_class: storage_class.to_string(),
+            last_modified: None,
+            etag: None,
+            tags: None,
+            content_type: None,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn totals_group_by_the_next_level_and_storage_class() {
+        let entries = [
+            entry("logs/2021/10/14.gz", 100, "STANDARD"),
+            entry("logs/2021/10/15.gz", 200, "STANDARD"),
+            entry("logs/2020/12/31.gz", 50, "GLACIER"),
+            entry("logs/README", 7, "STANDARD"),
+        ];
+
+        let totals = totals("logs/", &entries);
+
+        let expected = [
+            (("logs/", "STANDARD"), 1, 7),
+            (("logs/2020/", "GLACIER"), 1, 50),
+            (("logs/2021/", "STANDARD"), 2, 300),
+        ];
+        assert_eq!(totals.len(), expected.len());
+        for ((prefix, class), objects, bytes) in expected {
+            let total = &totals[&(prefix.to_string(), class.to_string())];
+            assert_eq!((total.objects, total.bytes), (objects, bytes), "{}", prefix);
+        }
+    }
+}
@@ -0,0 +1,16 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Helpers shared by the S3 code examples.
+
+pub mod bucket_config;
+pub mod empty;
+pub mod envelope;
+pub mod inventory;
+pub mod location;
+pub mod presign;
+pub mod select;
+pub mod sync;
+pub mod transfer;
@@ -0,0 +1,63 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! The Regions that buckets are in.
+//!
+//! `GetBucketLocation` answers with a bucket's location constraint, which is
+//! not quite its Region: buckets in us-east-1 have no constraint at all, and
+//! buckets created in eu-west-1 before it had that name have the constraint `EU`.
+
+use aws_sdk_s3::model::BucketLocationConstraint;
+use aws_sdk_s3::{Client, Error};
+use futures_util::{stream, StreamExt};
+
+/// The Region of a bucket with the location constraint `constraint`.
+pub fn region_name(constraint: Option<&BucketLocationConstraint>) -> &str {
+    match constraint.map(BucketLocationConstraint::as_str) {
+        None | Some("") => "us-east-1",
+        Some("EU") => "eu-west-1",
+        Some(region) => region,
+    }
+}
+
+/// Looks up the Region of each bucket in `buckets`, with at most
+/// `concurrency` requests at once, returning the results in the same order.
+/// A bucket whose location can't be read doesn't stop the others.
+pub async fn bucket_regions(
+    client: &Client,
+    buckets: &[String],
+    concurrency: usize,
+) -> Vec<Result<String, Error>> {
+    stream::iter(buckets)
+        .map(|bucket| async move {
+            let resp = client.get_bucket_location().bucket(bucket).send().await?;
+            Ok(region_name(resp.location_constraint.as_ref()).to_string())
+        })
+        .buffered(concurrency.max(1))
+        .collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_constraints_map_to_regions() {
+        assert_eq!(region_name(None), "us-east-1");
+        assert_eq!(
+            region_name(Some(&BucketLocationConstraint::Unknown(String::new()))),
+            "us-east-1"
+        );
+        assert_eq!(
+            region_name(Some(&BucketLocationConstraint::Eu)),
+            "eu-west-1"
+        );
+        assert_eq!(
+            region_name(Some(&BucketLocationConstraint::UsWest2)),
+            "us-west-2"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Presigned URLs, which let whoever has one get or put an object without
+//! AWS credentials of their own, until the URL expires.
+//!
+//! The URL is signed with SigV4 in its query string. Headers that are part
+//! of the signature, such as the `Content-MD5` of an upload, have to be sent
+//! with the URL exactly as they were signed, so an upload URL signed with an
+//! MD5 only accepts that one body. The SDK's signer leaves `Content-Type` out
+//! of every presigned signature, so the content type of an upload is only
+//! what the uploader is asked to send, and S3 accepts any other.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use aws_sdk_s3::presigning::config::{self, PresigningConfig};
+use aws_sdk_s3::{Client, SdkError};
+
+/// The longest a presigned URL can be valid for: one week.
+pub const MAX_EXPIRY: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// What a presigned URL lets its holder do.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    /// Download the object.
+    Get,
+    /// Upload the object.
+    Put,
+}
+
+impl Method {
+    /// The HTTP method of the presigned request.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Put => "PUT",
+        }
+    }
+}
+
+impl FromStr for Method {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "get" => Ok(Method::Get),
+            "put" => Ok(Method::Put),
+            _ => Err(format!("expected get or put, got {}", s)),
+        }
+    }
+}
+
+/// What to presign.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Presign {
+    /// Whether the URL gets or puts the object.
+    pub method: Method,
+    /// The name of the bucket.
+    pub bucket: String,
+    /// The key of the object.
+    pub key: String,
+    /// For a GET, the `Content-Type` S3 responds with, whatever the object's is.
+    /// For a PUT, the `Content-Type` the upload is to be sent with,
+    /// which S3 does not enforce, as it cannot be signed.
+    pub content_type: Option<String>,
+    /// For a PUT, the base64 MD5 of the only body the URL accepts.
+    pub content_md5: Option<String>,
+    /// How long the URL is valid for, up to [`MAX_EXPIRY`].
+    pub expires_in: Duration,
+}
+
+/// Why a URL could not be presigned.
+#[derive(Debug)]
+pub enum PresignError {
+    /// The expiry is longer than [`MAX_EXPIRY`].
+    Expiry(config::Error),
+    /// The request could not be built or signed.
+    S3(aws_sdk_s3::Error),
+}
+
+impl fmt::Display for PresignError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PresignError::Expiry(e) => write!(f, "{}", e),
+            PresignError::S3(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for PresignError {}
+
+impl<E> From<SdkError<E>> for PresignError
+where
+    aws_sdk_s3::Error: From<SdkError<E>>,
+{
+    fn from(e: SdkError<E>) -> Self {
+        PresignError::S3(e.into())
+    }
+}
+
+/// A presigned request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Presigned {
+    /// The HTTP method to send the request with.
+    pub method: Method,
+    /// The presigned URL.
+    pub url: String,
+    /// The headers to send with the request, by lowercase name.
+    pub headers: Vec<(String, String)>,
+}
+
+/// Signs a request for `presign`, with the client's credentials, Region, and
+/// endpoint. No request is sent to S3.
+pub async fn presign(client: &Client, presign: &Presign) -> Result<Presigned, PresignError> {
+    let config = PresigningConfig::expires_in(presign.expires_in).map_err(PresignError::Expiry)?;
+    let request = match presign.method {
+        Method::Get => {
+            client
+                .get_object()
+                .bucket(&presign.bucket)
+                .key(&presign.key)
+                .set_response_content_type(presign.content_type.clone())
+                .presigned(config)
+                .await?
+        }
+        Method::Put => {
+            client
+                .put_object()
+                .bucket(&presign.bucket)
+                .key(&presign.key)
+                .set_content_md5(presign.content_md5.clone())
+                .presigned(config)
+                .await?
+        }
+    };
+
+    let mut headers: Vec<(String, String)> = request
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            let value = value.to_str().unwrap_or_default();
+            (name.as_str().to_string(), value.to_string())
+        })
+        .collect();
+    if let (Method::Put, Some(content_type)) = (presign.method, &presign.content_type) {
+        headers.push(("content-type".to_string(), content_type.clone()));
+    }
+    Ok(Presigned {
+        method: presign.method,
+        url: request.uri().to_string(),
+        headers,
+    })
+}
@@ -0,0 +1,116 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! S3 Select, which runs a SQL expression against one CSV, JSON, or Parquet
+//! object in S3 and sends back only the results.
+//!
+//! The results arrive as an event stream: `Records` events carry chunks of
+//! the results, which can split a record in two, `Progress` and `Stats`
+//! events count bytes, and an `End` event says that the results are
+//! complete. A stream that stops before `End` means the query failed part way.
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+
+use aws_sdk_s3::error::SelectObjectContentError;
+use aws_sdk_s3::model::{
+    ExpressionType, InputSerialization, OutputSerialization, Progress, RequestProgress,
+    SelectObjectContentEventStream, Stats,
+};
+use aws_sdk_s3::{Client, SdkError};
+use aws_smithy_http::event_stream::RawMessage;
+
+/// A query to run against one object.
+#[derive(Clone, Debug)]
+pub struct Query {
+    /// The name of the bucket.
+    pub bucket: String,
+    /// The key of the object.
+    pub key: String,
+    /// The SQL expression, such as `SELECT * FROM S3Object s WHERE s.status = '500'`.
+    pub expression: String,
+    /// How the object is formatted and compressed.
+    pub input: InputSerialization,
+    /// How to format the results.
+    pub output: OutputSerialization,
+    /// Whether S3 is to report its progress as the query runs.
+    pub progress: bool,
+}
+
+/// Why a query failed.
+#[derive(Debug)]
+pub enum SelectError {
+    /// S3 refused the query.
+    S3(aws_sdk_s3::Error),
+    /// The query failed while the results were being sent.
+    Stream(SdkError<SelectObjectContentError, RawMessage>),
+    /// The results ended without an `End` event.
+    Incomplete,
+    /// The results could not be written.
+    Io(io::Error),
+}
+
+impl fmt::Display for SelectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SelectError::S3(e) => write!(f, "{}", e),
+            SelectError::Stream(SdkError::ServiceError { err, .. }) => write!(f, "{}", err),
+            SelectError::Stream(e) => write!(f, "{}", e),
+            SelectError::Incomplete => f.write_str("the results ended before the query finished"),
+            SelectError::Io(e) => write!(f, "cannot write the results: {}", e),
+        }
+    }
+}
+
+impl Error for SelectError {}
+
+/// Runs `query`, writing the results to `out` as they arrive and calling
+/// `progress` with each progress report, which S3 only sends if the query asks for them.
+/// Returns the totals that S3 reports at the end, if it does.
+pub async fn select(
+    client: &Client,
+    query: &Query,
+    out: &mut impl Write,
+    mut progress: impl FnMut(&Progress),
+) -> Result<Option<Stats>, SelectError> {
+    let mut resp = client
+        .select_object_content()
+        .bucket(&query.bucket)
+        .key(&query.key)
+        .expression(&query.expression)
+        .expression_type(ExpressionType::Sql)
+        .input_serialization(query.input.clone())
+        .output_serialization(query.output.clone())
+        .request_progress(RequestProgress::builder().enabled(query.progress).build())
+        .send()
+        .await
+        .map_err(|e| SelectError::S3(e.into()))?;
+
+    let mut stats = None;
+    loop {
+        let event = match resp.payload.recv().await.map_err(SelectError::Stream)? {
+            Some(event) => event,
+            None => return Err(SelectError::Incomplete),
+        };
+        match event {
+            SelectObjectContentEventStream::Records(records) => {
+                if let Some(payload) = records.payload {
+                    out.write_all(payload.as_ref()).map_err(SelectError::Io)?;
+                }
+            }
+            SelectObjectContentEventStream::Progress(event) => {
+                if let Some(details) = &event.details {
+                    progress(details);
+                }
+            }
+            SelectObjectContentEventStream::Stats(event) => stats = event.details,
+            SelectObjectContentEventStream::End(_) => break,
+            _ => {}
+        }
+    }
+    out.flush().map_err(SelectError::Io)?;
+    Ok(stats)
+}
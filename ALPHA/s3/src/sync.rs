@@ -0,0 +1,219 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Working out what it takes to make a bucket prefix match a local directory.
+//!
+//! A file is uploaded if it has no object, or its size differs from its
+//! object's. A file the same size as its object is only looked at more
+//! closely if it was modified after the object: its ETag is computed and
+//! compared, so that files rebuilt with the same contents are not uploaded
+//! again. When that cannot be done, because the object was uploaded in a
+//! different number of parts, the newer file is uploaded.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use aws_sdk_s3::model::Object;
+use glob::{MatchOptions, Pattern, PatternError};
+use walkdir::WalkDir;
+
+use crate::transfer;
+
+/// Which relative paths a sync looks at.
+///
+/// In a pattern, `*` and `?` do not match `/`, but `**` matches any number
+/// of directories, so `*.html` only matches files at the top of the
+/// directory, and `**/*.html` matches them anywhere.
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+}
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+impl Filter {
+    /// A filter for paths that match one of `include`, or any path if it is
+    /// empty, and none of `exclude`.
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self, PatternError> {
+        let compile = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|p| Pattern::new(p))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(Filter {
+            include: compile(include)?,
+            exclude: compile(exclude)?,
+        })
+    }
+
+    /// Whether a sync looks at the file or key with this relative path.
+    pub fn matches(&self, path: &str) -> bool {
+        let any =
+            |patterns: &[Pattern]| patterns.iter().any(|p| p.matches_with(path, MATCH_OPTIONS));
+        (self.include.is_empty() || any(&self.include)) && !any(&self.exclude)
+    }
+}
+
+/// A file in the local directory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalFile {
+    /// Where the file is.
+    pub path: PathBuf,
+    /// The file's size in bytes.
+    pub size: u64,
+    /// When the file was last modified, in seconds since the Unix epoch.
+    pub modified: i64,
+}
+
+/// An object under the bucket prefix.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoteObject {
+    /// The object's size in bytes.
+    pub size: i64,
+    /// The object's ETag.
+    pub etag: String,
+    /// When the object was last modified, in seconds since the Unix epoch.
+    pub modified: i64,
+}
+
+impl From<&Object> for RemoteObject {
+    fn from(object: &Object) -> Self {
+        RemoteObject {
+            size: object.size,
+            etag: object.e_tag.clone().unwrap_or_default(),
+            modified: object.last_modified.as_ref().map_or(0, |t| t.secs()),
+        }
+    }
+}
+
+/// The files under `dir` that match `filter`, by their path relative to
+/// `dir` with `/` between directories.
+pub fn local_files(dir: &Path, filter: &Filter) -> io::Result<BTreeMap<String, LocalFile>> {
+    let mut files = BTreeMap::new();
+    for entry in WalkDir::new(dir) {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(dir)
+            .unwrap_or_else(|_| entry.path())
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if !filter.matches(&relative) {
+            continue;
+        }
+        let metadata = entry.metadata()?;
+        let modified = metadata
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as i64);
+        files.insert(
+            relative,
+            LocalFile {
+                path: entry.into_path(),
+                size: metadata.len(),
+                modified,
+            },
+        );
+    }
+    Ok(files)
+}
+
+/// The bucket prefix with a trailing `/`, so that keys can be made by
+/// appending relative paths, or an empty string for the whole bucket.
+pub fn key_prefix(prefix: &str) -> String {
+    let prefix = prefix.trim_matches('/');
+    if prefix.is_empty() {
+        String::new()
+    } else {
+        format!("{}/", prefix)
+    }
+}
+
+/// Why a file is uploaded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reason {
+    /// There is no object for the file.
+    New,
+    /// The file is not the size of its object.
+    Size,
+    /// The file's ETag is not its object's.
+    Contents,
+    /// The file was modified after its object, and their ETags cannot be compared.
+    Newer,
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Reason::New => "new",
+            Reason::Size => "size changed",
+            Reason::Contents => "contents changed",
+            Reason::Newer => "newer",
+        })
+    }
+}
+
+/// Why `file` has to be uploaded over `object`, or `None` if it does not.
+pub async fn compare(
+    file: &LocalFile,
+    object: Option<&RemoteObject>,
+    part_size: u64,
+) -> io::Result<Option<Reason>> {
+    let object = match object {
+        Some(object) => object,
+        None => return Ok(Some(Reason::New)),
+    };
+    if file.size as i64 != object.size {
+        return Ok(Some(Reason::Size));
+    }
+    if file.modified <= object.modified {
+        return Ok(None);
+    }
+    let etag = transfer::local_etag(&file.path, file.size, &object.etag, part_size).await?;
+    Ok(match etag {
+        Some(etag) if etag == object.etag.trim_matches('"') => None,
+        Some(_) => Some(Reason::Contents),
+        None => Some(Reason::Newer),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_relative_paths() {
+        let filter = Filter::new(
+            &["**/*.html".to_string(), "*.css".to_string()],
+            &["drafts/**".to_string()],
+        )
+        .unwrap();
+
+        assert!(filter.matches("index.html"));
+        assert!(filter.matches("blog/post.html"));
+        assert!(filter.matches("site.css"));
+        assert!(!filter.matches("theme/site.css"));
+        assert!(!filter.matches("drafts/post.html"));
+        assert!(!filter.matches("robots.txt"));
+
+        assert!(Filter::default().matches("anything/at/all"));
+        assert_eq!(key_prefix("/site/"), "site/");
+        assert_eq!(key_prefix(""), "");
+    }
+}
@@ -0,0 +1,593 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+//! Uploading and downloading large objects in parts, several at a time.
+//!
+//! Files above a size threshold are uploaded with a multipart upload. Each
+//! part is sent with its MD5, so that S3 rejects a part damaged on the way,
+//! and the ETag S3 computes for the whole object is checked against the one
+//! computed locally. A multipart upload that fails is left in place, and
+//! uploading the same file to the same key again resumes it: parts already
+//! uploaded with the same contents are not sent again. The ETag of an object
+//! encrypted with a KMS key is not an MD5, so then only the parts' MD5s are checked.
+//!
+//! Downloads fetch an object that was uploaded in parts part by part, and
+//! other large objects in ranges, then check the downloaded bytes against
+//! the object's ETag before putting the file in place.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, SeekFrom};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use aws_sdk_s3::model::{CompletedMultipartUpload, CompletedPart, ServerSideEncryption};
+use aws_sdk_s3::output::HeadObjectOutput;
+use aws_sdk_s3::{ByteStream, Client, SdkError};
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use structopt::StructOpt;
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+/// The most parts a multipart upload can have.
+pub const MAX_PARTS: u64 = 10_000;
+
+/// Command-line options for transfers.
+#[derive(Clone, Debug, StructOpt)]
+pub struct TransferOpt {
+    /// Files larger than this are transferred in parts, such as 16MiB or 1GB.
+    #[structopt(long, default_value = "16MiB", parse(try_from_str = parse_size))]
+    pub threshold: u64,
+
+    /// The size of each part. S3 needs parts of at least 5MiB, except the last.
+    #[structopt(long, default_value = "8MiB", parse(try_from_str = parse_size))]
+    pub part_size: u64,
+
+    /// The most parts to transfer at once.
+    #[structopt(long, default_value = "4")]
+    pub concurrency: usize,
+}
+
+impl Default for TransferOpt {
+    fn default() -> Self {
+        TransferOpt {
+            threshold: 16 << 20,
+            part_size: 8 << 20,
+            concurrency: 4,
+        }
+    }
+}
+
+/// Reads a size such as 100, 5MiB, or 1GB.
+pub fn parse_size(s: &str) -> Result<u64, String> {
+    let digits = s.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let unit: u64 = match &s[digits.len()..] {
+        "" | "B" => 1,
+        "KB" => 1_000,
+        "KiB" => 1 << 10,
+        "MB" => 1_000_000,
+        "MiB" => 1 << 20,
+        "GB" => 1_000_000_000,
+        "GiB" => 1 << 30,
+        other => return Err(format!("unknown unit {} in {}", other, s)),
+    };
+    digits
+        .trim()
+        .parse::<u64>()
+        .map(|n| n * unit)
+        .map_err(|_| format!("expected a size such as 8MiB, got {}", s))
+}
+
+/// Why a transfer failed.
+#[derive(Debug)]
+pub enum TransferError {
+    /// Reading or writing the local file failed.
+    Io(io::Error),
+    /// A request to S3 failed.
+    S3(aws_sdk_s3::Error),
+    /// The data does not have the checksum it should, such as a download
+    /// that does not match the object's ETag.
+    Checksum { expected: String, actual: String },
+}
+
+impl fmt::Display for TransferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransferError::Io(e) => write!(f, "{}", e),
+            TransferError::S3(e) => write!(f, "{}", e),
+            TransferError::Checksum { expected, actual } => write!(
+                f,
+                "checksum mismatch: expected ETag {}, got {}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+impl Error for TransferError {}
+
+impl From<io::Error> for TransferError {
+    fn from(e: io::Error) -> Self {
+        TransferError::Io(e)
+    }
+}
+
+impl<E> From<SdkError<E>> for TransferError
+where
+    aws_sdk_s3::Error: From<SdkError<E>>,
+{
+    fn from(e: SdkError<E>) -> Self {
+        TransferError::S3(e.into())
+    }
+}
+
+/// What a transfer did.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Transfer {
+    /// The object's ETag, without quotes.
+    pub etag: String,
+    /// The number of parts, or 1 for an object transferred whole.
+    pub parts: usize,
+    /// The parts a resumed upload did not have to send again.
+    pub reused: usize,
+}
+
+/// A progress bar for `len` bytes, drawn on stderr if it is a terminal.
+pub fn progress_bar(len: u64) -> ProgressBar {
+    let bar = ProgressBar::with_draw_target(len, ProgressDrawTarget::stderr());
+    bar.set_style(
+        ProgressStyle::default_bar()
+            .template("{bar:40} {bytes}/{total_bytes} {bytes_per_sec} {eta}")
+            .progress_chars("=> "),
+    );
+    bar
+}
+
+/// The byte ranges of the parts of a `size`-byte file, each `part_size`
+/// bytes but the last, made larger if needed to stay within [`MAX_PARTS`].
+pub fn part_ranges(size: u64, part_size: u64) -> Vec<Range<u64>> {
+    let part_size = part_size.max(size.div_ceil(MAX_PARTS)).max(1);
+    (0..size)
+        .step_by(part_size as usize)
+        .map(|start| start..(start + part_size).min(size))
+        .collect()
+}
+
+/// The ETag S3 gives an object uploaded in parts with these MD5 digests:
+/// the MD5 of the digests, then a dash and the number of parts.
+pub fn multipart_etag(digests: &[md5::Digest]) -> String {
+    let joined: Vec<u8> = digests.iter().flat_map(|d| d.0).collect();
+    format!("{:x}-{}", md5::compute(joined), digests.len())
+}
+
+fn unquote(etag: &str) -> &str {
+    etag.trim_matches('"')
+}
+
+/// Whether S3 made an object's ETag from the MD5 of its contents, which it
+/// doesn't for objects encrypted with a KMS key or a customer-provided key.
+fn etag_is_md5(sse: Option<&ServerSideEncryption>, sse_customer_algorithm: Option<&str>) -> bool {
+    sse != Some(&ServerSideEncryption::AwsKms) && sse_customer_algorithm.is_none()
+}
+
+/// Checks the ETag S3 returned for what was sent against `actual`, the one
+/// computed locally, unless S3 doesn't make the ETag from an MD5.
+/// Returns S3's ETag, without quotes.
+fn check_sent(
+    etag: Option<&str>,
+    sse: Option<&ServerSideEncryption>,
+    actual: String,
+) -> Result<String, TransferError> {
+    match etag {
+        Some(etag) if !etag_is_md5(sse, None) => Ok(unquote(etag).to_string()),
+        etag => check(etag.unwrap_or_default(), actual.clone()).map(|()| actual),
+    }
+}
+
+fn check(expected: &str, actual: String) -> Result<(), TransferError> {
+    if unquote(expected) == actual {
+        Ok(())
+    } else {
+        Err(TransferError::Checksum {
+            expected: unquote(expected).to_string(),
+            actual,
+        })
+    }
+}
+
+async fn read_range(path: &Path, range: &Range<u64>) -> io::Result<Vec<u8>> {
+    let mut file = File::open(path).await?;
+    file.seek(SeekFrom::Start(range.start)).await?;
+    let mut data = vec![0; (range.end - range.start) as usize];
+    file.read_exact(&mut data).await?;
+    Ok(data)
+}
+
+async fn write_at(path: &Path, offset: u64, data: &[u8]) -> io::Result<()> {
+    let mut file = OpenOptions::new().write(true).open(path).await?;
+    file.seek(SeekFrom::Start(offset)).await?;
+    file.write_all(data).await
+}
+
+/// The most recent multipart upload to `key` that was never completed.
+async fn unfinished_upload(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+) -> Result<Option<String>, TransferError> {
+    let resp = client
+        .list_multipart_uploads()
+        .bucket(bucket)
+        .prefix(key)
+        .send()
+        .await?;
+    Ok(resp
+        .uploads
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|upload| upload.key.as_deref() == Some(key))
+        .max_by_key(|upload| upload.initiated.as_ref().map(|t| t.secs()))
+        .and_then(|upload| upload.upload_id))
+}
+
+/// The size and ETag of each part already uploaded, by part number.
+async fn uploaded_parts(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+    upload_id: &str,
+) -> Result<HashMap<i32, (i64, String)>, TransferError> {
+    let mut parts = HashMap::new();
+    let mut marker = None;
+    loop {
+        let resp = client
+            .list_parts()
+            .bucket(bucket)
+            .key(key)
+            .upload_id(upload_id)
+            .set_part_number_marker(marker)
+            .send()
+            .await?;
+        for part in resp.parts.unwrap_or_default() {
+            let etag = part.e_tag.as_deref().map(unquote).unwrap_or_default();
+            parts.insert(part.part_number, (part.size, etag.to_string()));
+        }
+        if !resp.is_truncated {
+            return Ok(parts);
+        }
+        marker = resp.next_part_number_marker;
+    }
+}
+
+/// Uploads the file at `path` to `key`, in parts if it is larger than the
+/// threshold, resuming an earlier upload of it that did not finish.
+pub async fn upload(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+    path: &Path,
+    opt: &TransferOpt,
+    progress: &ProgressBar,
+) -> Result<Transfer, TransferError> {
+    let size = fs::metadata(path).await?.len();
+    progress.set_length(size);
+
+    if size <= opt.threshold {
+        let data = fs::read(path).await?;
+        let digest = md5::compute(&data);
+        let resp = client
+            .put_object()
+            .bucket(bucket)
+            .key(key)
+            .content_md5(base64::encode(digest.0))
+            .body(ByteStream::from(data))
+            .send()
+            .await?;
+        progress.inc(size);
+        let etag = check_sent(
+            resp.e_tag.as_deref(),
+            resp.server_side_encryption.as_ref(),
+            format!("{:x}", digest),
+        )?;
+        return Ok(Transfer {
+            etag,
+            parts: 1,
+            reused: 0,
+        });
+    }
+
+    let (upload_id, uploaded) = match unfinished_upload(client, bucket, key).await? {
+        Some(upload_id) => {
+            let uploaded = uploaded_parts(client, bucket, key, &upload_id).await?;
+            (upload_id, uploaded)
+        }
+        None => {
+            let resp = client
+                .create_multipart_upload()
+                .bucket(bucket)
+                .key(key)
+                .send()
+                .await?;
+            (resp.upload_id.unwrap_or_default(), HashMap::new())
+        }
+    };
+
+    let ranges = part_ranges(size, opt.part_size);
+    let parts: Vec<(CompletedPart, md5::Digest, bool)> =
+        stream::iter(ranges.into_iter().enumerate())
+            .map(|(i, range)| {
+                let number = i as i32 + 1;
+                let upload_id = &upload_id;
+                let uploaded = &uploaded;
+                async move {
+                    let data = read_range(path, &range).await?;
+                    let len = data.len() as u64;
+                    let digest = md5::compute(&data);
+                    let mut etag = format!("{:x}", digest);
+                    let reused = uploaded.get(&number) == Some(&(len as i64, etag.clone()));
+                    if !reused {
+                        let resp = client
+                            .upload_part()
+                            .bucket(bucket)
+                            .key(key)
+                            .upload_id(upload_id)
+                            .part_number(number)
+                            .content_md5(base64::encode(digest.0))
+                            .content_length(len as i64)
+                            .body(ByteStream::from(data))
+                            .send()
+                            .await?;
+                        etag = check_sent(
+                            resp.e_tag.as_deref(),
+                            resp.server_side_encryption.as_ref(),
+                            etag,
+                        )?;
+                    }
+                    progress.inc(len);
+                    let part = CompletedPart::builder()
+                        .e_tag(format!("\"{}\"", etag))
+                        .part_number(number)
+                        .build();
+                    Ok::<_, TransferError>((part, digest, reused))
+                }
+            })
+            .buffered(opt.concurrency.max(1))
+            .try_collect()
+            .await?;
+
+    let digests: Vec<md5::Digest> = parts.iter().map(|(_, digest, _)| *digest).collect();
+    let reused = parts.iter().filter(|(_, _, reused)| *reused).count();
+    let completed = CompletedMultipartUpload::builder()
+        .set_parts(Some(parts.into_iter().map(|(part, _, _)| part).collect()))
+        .build();
+    let resp = client
+        .complete_multipart_upload()
+        .bucket(bucket)
+        .key(key)
+        .upload_id(&upload_id)
+        .multipart_upload(completed)
+        .send()
+        .await?;
+
+    let etag = check_sent(
+        resp.e_tag.as_deref(),
+        resp.server_side_encryption.as_ref(),
+        multipart_etag(&digests),
+    )?;
+    Ok(Transfer {
+        etag,
+        parts: digests.len(),
+        reused,
+    })
+}
+
+// Where a ranged GET's data starts, from a Content-Range such as "bytes 8-15/20".
+fn range_start(content_range: &str) -> Option<u64> {
+    content_range
+        .strip_prefix("bytes ")?
+        .split('-')
+        .next()?
+        .parse()
+        .ok()
+}
+
+async fn file_md5(path: &Path) -> io::Result<md5::Digest> {
+    let mut file = File::open(path).await?;
+    let mut context = md5::Context::new();
+    let mut buf = vec![0; 1 << 20];
+    loop {
+        let n = file.read(&mut buf).await?;
+        if n == 0 {
+            return Ok(context.compute());
+        }
+        context.consume(&buf[..n]);
+    }
+}
+
+/// The ETag the `size`-byte file at `path` would have if it were uploaded
+/// the way the object with ETag `etag` was: whole, or in parts of
+/// `part_size` bytes. Returns `None` if the object was uploaded in a
+/// different number of parts, as the file's ETag cannot then be compared.
+pub async fn local_etag(
+    path: &Path,
+    size: u64,
+    etag: &str,
+    part_size: u64,
+) -> io::Result<Option<String>> {
+    let parts = match unquote(etag).split_once('-') {
+        Some((_, parts)) => parts.parse::<usize>().ok(),
+        None => return Ok(Some(format!("{:x}", file_md5(path).await?))),
+    };
+    let ranges = part_ranges(size, part_size);
+    if parts != Some(ranges.len()) {
+        return Ok(None);
+    }
+    let mut digests = Vec::with_capacity(ranges.len());
+    for range in &ranges {
+        digests.push(md5::compute(read_range(path, range).await?));
+    }
+    Ok(Some(multipart_etag(&digests)))
+}
+
+fn body_error(e: impl Error + Send + Sync + 'static) -> TransferError {
+    TransferError::Io(io::Error::other(e))
+}
+
+// Downloads the object `head` describes to `temp`, returning the ETag computed
+// from what was downloaded, and the number of parts it was downloaded in.
+async fn fetch(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+    head: &HeadObjectOutput,
+    temp: &Path,
+    opt: &TransferOpt,
+    progress: &ProgressBar,
+) -> Result<(String, usize), TransferError> {
+    let size = head.content_length as u64;
+    File::create(temp).await?.set_len(size).await?;
+
+    let uploaded_parts = unquote(head.e_tag.as_deref().unwrap_or_default())
+        .split_once('-')
+        .and_then(|(_, parts)| parts.parse::<i32>().ok());
+    match uploaded_parts {
+        // Fetching the parts as they were uploaded gives the digests the ETag is made of.
+        Some(count) => {
+            let digests: Vec<md5::Digest> = stream::iter(1..=count)
+                .map(|number| async move {
+                    let resp = client
+                        .get_object()
+                        .bucket(bucket)
+                        .key(key)
+                        .part_number(number)
+                        .send()
+                        .await?;
+                    let start = resp
+                        .content_range
+                        .as_deref()
+                        .and_then(range_start)
+                        .unwrap_or_default();
+                    let data = resp.body.collect().await.map_err(body_error)?.into_bytes();
+                    write_at(temp, start, &data).await?;
+                    progress.inc(data.len() as u64);
+                    Ok::<_, TransferError>(md5::compute(&data))
+                })
+                .buffered(opt.concurrency.max(1))
+                .try_collect()
+                .await?;
+            Ok((multipart_etag(&digests), digests.len()))
+        }
+        None if size > opt.threshold => {
+            let ranges = part_ranges(size, opt.part_size);
+            let parts = ranges.len();
+            stream::iter(ranges)
+                .map(|range| async move {
+                    let resp = client
+                        .get_object()
+                        .bucket(bucket)
+                        .key(key)
+                        .range(format!("bytes={}-{}", range.start, range.end - 1))
+                        .send()
+                        .await?;
+                    let data = resp.body.collect().await.map_err(body_error)?.into_bytes();
+                    write_at(temp, range.start, &data).await?;
+                    progress.inc(data.len() as u64);
+                    Ok::<_, TransferError>(())
+                })
+                .buffer_unordered(opt.concurrency.max(1))
+                .try_collect::<()>()
+                .await?;
+            Ok((format!("{:x}", file_md5(temp).await?), parts))
+        }
+        None => {
+            let resp = client.get_object().bucket(bucket).key(key).send().await?;
+            let mut body = resp.body;
+            let mut file = File::create(temp).await?;
+            let mut context = md5::Context::new();
+            while let Some(chunk) = body.try_next().await.map_err(body_error)? {
+                context.consume(&chunk);
+                file.write_all(&chunk).await?;
+                progress.inc(chunk.len() as u64);
+            }
+            file.flush().await?;
+            Ok((format!("{:x}", context.compute()), 1))
+        }
+    }
+}
+
+/// Downloads `key` to the file at `path`, in parts if it was uploaded in
+/// parts or is larger than the threshold. The file is written under a
+/// temporary name, only renamed to `path` once its checksum matches, and
+/// removed if the download fails.
+pub async fn download(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+    path: &Path,
+    opt: &TransferOpt,
+    progress: &ProgressBar,
+) -> Result<Transfer, TransferError> {
+    let head = client.head_object().bucket(bucket).key(key).send().await?;
+    let size = head.content_length as u64;
+    let expected = head.e_tag.as_deref().map(unquote).unwrap_or_default();
+    let verify = etag_is_md5(
+        head.server_side_encryption.as_ref(),
+        head.sse_customer_algorithm.as_deref(),
+    );
+    progress.set_length(size);
+
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".download");
+    let temp = PathBuf::from(temp);
+
+    let fetched = fetch(client, bucket, key, &head, &temp, opt, progress).await;
+    let parts = match fetched.and_then(|(actual, parts)| {
+        if verify {
+            check(expected, actual)?;
+        }
+        Ok(parts)
+    }) {
+        Ok(parts) => parts,
+        Err(e) => {
+            let _ = fs::remove_file(&temp).await;
+            return Err(e);
+        }
+    };
+    fs::rename(&temp, path).await?;
+    Ok(Transfer {
+        etag: expected.to_string(),
+        parts,
+        reused: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_into_parts() {
+        assert_eq!(part_ranges(20, 8), [0..8, 8..16, 16..20]);
+        assert_eq!(part_ranges(16, 8), [0..8, 8..16]);
+        // No more than 10,000 parts, however small the part size asked for.
+        assert_eq!(part_ranges(20_000, 1).len(), 10_000);
+        assert_eq!(parse_size("8MiB"), Ok(8 << 20));
+        assert_eq!(parse_size("100"), Ok(100));
+        assert!(parse_size("8 parsecs").is_err());
+    }
+
+    #[test]
+    fn computes_multipart_etags() {
+        // An empty object has the MD5 of no bytes.
+        let empty = md5::compute(b"");
+        assert_eq!(format!("{:x}", empty), "d41d8cd98f00b204e9800998ecf8427e");
+        let etag = multipart_etag(&[md5::compute(b"abcdefgh"), md5::compute(b"ijkl")]);
+        assert!(etag.ends_with("-2"), "{}", etag);
+        assert_eq!(etag.len(), 34);
+        assert_eq!(range_start("bytes 8-15/20"), Some(8));
+    }
+}
@@ -0,0 +1,106 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::testing::Replay;
+
+fn describe(fixture: &str, args: &[&str]) -> alpha_common::testing::ReplayRun {
+    Replay::new(
+        env!("CARGO_BIN_EXE_describe-bucket"),
+        format!(
+            "{}/tests/fixtures/{}.json",
+            env!("CARGO_MANIFEST_DIR"),
+            fixture
+        ),
+    )
+    .args(args)
+    .run()
+}
+
+#[test]
+fn describe_bucket_summarizes_every_configuration() {
+    let run = describe("describe-bucket", &["-b", "alpha-examples"]);
+
+    run.assert_success().assert_requests_match(&[]);
+    let expected = "alpha-examples
+  Versioning: Enabled (MFA delete Disabled)
+  Encryption: aws:kms with key arn:aws:kms:us-west-2:123456789012:key/alpha, bucket key enabled
+  Lifecycle:  1 rule
+  CORS:       none
+  Tags:       team=data, env=prod
+  Policy:     none
+";
+    assert!(
+        run.stdout.contains(expected),
+        "unexpected output:\n{}",
+        run.stdout
+    );
+}
+
+#[test]
+fn describe_bucket_shows_configurations_as_json() {
+    let run = describe(
+        "describe-bucket",
+        &["-b", "alpha-examples", "--output", "json"],
+    );
+
+    run.assert_success();
+    let buckets = run.json();
+    let bucket = &buckets[0];
+    assert_eq!(bucket["Bucket"], "alpha-examples");
+    assert_eq!(bucket["Versioning"], "Enabled");
+    assert_eq!(
+        bucket["Encryption"]["Rules"][0]["ApplyServerSideEncryptionByDefault"]["SSEAlgorithm"],
+        "aws:kms"
+    );
+    assert_eq!(bucket["Lifecycle"]["Rules"][0]["ID"], "logs");
+    assert_eq!(
+        bucket["Lifecycle"]["Rules"][0]["Transitions"][0]["StorageClass"],
+        "GLACIER"
+    );
+    assert!(bucket["Cors"].is_null());
+    assert_eq!(bucket["Tagging"]["TagSet"][1]["Key"], "env");
+    assert!(bucket["Policy"].is_null());
+}
+
+#[test]
+fn describe_bucket_goes_on_after_an_access_denied() {
+    let run = describe(
+        "describe-bucket-denied",
+        &[
+            "-b",
+            "alpha-locked",
+            "-b",
+            "alpha-examples",
+            "--output",
+            "json",
+        ],
+    );
+
+    assert!(!run.status.success(), "describe-bucket succeeded");
+    run.assert_requests_match(&[]);
+    let buckets = run.json();
+    assert_eq!(buckets[0]["Bucket"], "alpha-locked");
+    assert_eq!(buckets[0]["Versioning"], "Enabled");
+    assert!(buckets[0]["Encryption"]["Error"].is_string());
+    assert_eq!(buckets[0]["Tagging"]["TagSet"][0]["Key"], "team");
+    assert!(buckets[0]["Policy"]["Error"].is_string());
+    assert_eq!(buckets[1]["Bucket"], "alpha-examples");
+    assert_eq!(
+        buckets[1]["Encryption"]["Rules"][0]["ApplyServerSideEncryptionByDefault"]["SSEAlgorithm"],
+        "aws:kms"
+    );
+    assert!(
+        run.stderr
+            .contains("Got an error getting the default encryption of alpha-locked:"),
+        "unexpected errors:\n{}",
+        run.stderr
+    );
+    assert!(
+        run.stderr
+            .contains("Got an error getting the policy of alpha-locked:"),
+        "unexpected errors:\n{}",
+        run.stderr
+    );
+}
@@ -0,0 +1,73 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::testing::{Replay, ReplayRun};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn download_object(fixture: &str, file: &Path) -> ReplayRun {
+    Replay::new(
+        env!("CARGO_BIN_EXE_download-object"),
+        format!(
+            "{}/tests/fixtures/{}.json",
+            env!("CARGO_MANIFEST_DIR"),
+            fixture
+        ),
+    )
+    .args([
+        "-b".as_ref(),
+        "alpha-examples".as_ref(),
+        "-k".as_ref(),
+        "big.txt".as_ref(),
+        "-f".as_ref(),
+        file.as_os_str(),
+        "--concurrency".as_ref(),
+        "1".as_ref(),
+    ])
+    .run()
+}
+
+fn temp_file(file: &Path) -> PathBuf {
+    let mut temp = file.as_os_str().to_owned();
+    temp.push(".download");
+    PathBuf::from(temp)
+}
+
+#[test]
+fn download_object_fetches_each_uploaded_part() {
+    let file = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("downloaded.txt");
+    let _ = fs::remove_file(&file);
+
+    let run = download_object("download-object", &file);
+
+    run.assert_success().assert_requests_match(&[]);
+    assert_eq!(
+        run.stdout,
+        format!(
+            "Downloaded big.txt to {} in 2 parts. ETag: ca279b52c8d3f486bc026b575b53e04e-2\n",
+            file.display()
+        )
+    );
+    assert_eq!(fs::read_to_string(&file).unwrap(), "abcdefghijkl");
+    assert!(!temp_file(&file).exists());
+}
+
+#[test]
+fn download_object_leaves_no_file_when_the_checksum_does_not_match() {
+    let file = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("mismatched.txt");
+    let _ = fs::remove_file(&file);
+
+    let run = download_object("download-object-mismatch", &file);
+
+    assert!(!run.status.success(), "download-object succeeded");
+    run.assert_requests_match(&[]);
+    assert!(
+        run.stdout.contains("checksum mismatch"),
+        "unexpected output:\n{}",
+        run.stdout
+    );
+    assert!(!file.exists());
+    assert!(!temp_file(&file).exists());
+}
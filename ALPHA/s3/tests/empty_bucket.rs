@@ -0,0 +1,36 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::testing::Replay;
+
+#[test]
+fn empty_bucket_deletes_every_version_after_confirmation() {
+    let run = Replay::new(
+        env!("CARGO_BIN_EXE_empty-bucket"),
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/empty-bucket.json"
+        ),
+    )
+    .args(["-b", "alpha-old", "--delete-bucket"])
+    .stdin("alpha-old\n")
+    .run();
+
+    run.assert_success().assert_requests_match(&["content-md5"]);
+    assert!(
+        run.stdout.contains(
+            "This permanently deletes 4 object versions and delete markers \
+             in bucket alpha-old, then the bucket itself."
+        ),
+        "unexpected output:\n{}",
+        run.stdout
+    );
+    assert!(
+        run.stdout
+            .contains("  deleted 4 of 4\nDeleted bucket alpha-old."),
+        "unexpected output:\n{}",
+        run.stdout
+    );
+}
@@ -0,0 +1,201 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::testing::{example, StandIn, StandInRequest, StandInResponse};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::process::Output;
+use std::sync::{Arc, Mutex};
+
+/// An object as the stand-in stores it.
+#[derive(Clone, Default)]
+struct Stored {
+    body: Vec<u8>,
+    metadata: BTreeMap<String, String>,
+}
+
+/// What the stand-in for S3 and KMS holds.
+#[derive(Default)]
+struct State {
+    objects: HashMap<String, Stored>,
+    /// The data keys KMS has handed out, by wrapped key, with their encryption context.
+    data_keys: HashMap<Vec<u8>, (Vec<u8>, Value)>,
+}
+
+fn kms_response(status: u16, body: Value) -> StandInResponse {
+    StandInResponse::new(status, body.to_string())
+        .header("content-type", "application/x-amz-json-1.1")
+}
+
+/// Answers as KMS would, with a new random-looking data key for each
+/// `GenerateDataKey`, and `Decrypt` only with the context the key was made with.
+fn kms(state: &mut State, target: &str, body: &[u8]) -> StandInResponse {
+    let request: Value = serde_json::from_slice(body).unwrap();
+    let context = request["EncryptionContext"].clone();
+    match target {
+        "TrentService.GenerateDataKey" => {
+            assert_eq!(request["KeySpec"], "AES_256");
+            let n = state.data_keys.len() as u8;
+            let key: Vec<u8> = (0..32).map(|i| i * 7 + n).collect();
+            let wrapped = format!("wrapped-by-{}-{}", request["KeyId"], n).into_bytes();
+            state
+                .data_keys
+                .insert(wrapped.clone(), (key.clone(), context));
+            kms_response(
+                200,
+                json!({
+                    "KeyId": request["KeyId"],
+                    "Plaintext": base64::encode(key),
+                    "CiphertextBlob": base64::encode(wrapped),
+                }),
+            )
+        }
+        "TrentService.Decrypt" => {
+            let wrapped = base64::decode(request["CiphertextBlob"].as_str().unwrap()).unwrap();
+            match state.data_keys.get(&wrapped) {
+                Some((key, expected)) if *expected == context => kms_response(
+                    200,
+                    json!({ "KeyId": "alias/reports", "Plaintext": base64::encode(key) }),
+                ),
+                _ => kms_response(400, json!({ "__type": "InvalidCiphertextException" })),
+            }
+        }
+        target => panic!("unexpected KMS request {}", target),
+    }
+}
+
+/// Answers as S3 would a path-style `PutObject` or `GetObject`.
+fn s3(state: &mut State, request: StandInRequest) -> StandInResponse {
+    match request.method.as_str() {
+        "PUT" => {
+            let metadata = request
+                .headers
+                .into_iter()
+                .filter(|(name, _)| name.starts_with("x-amz-meta-"))
+                .collect();
+            let stored = Stored {
+                body: request.body,
+                metadata,
+            };
+            state.objects.insert(request.path, stored);
+            StandInResponse::new(200, "")
+        }
+        "GET" => match state.objects.get(&request.path) {
+            Some(object) => {
+                let mut response = StandInResponse::new(200, object.body.clone());
+                response.headers = object.metadata.clone().into_iter().collect();
+                response
+            }
+            None => StandInResponse::new(404, "<Error><Code>NoSuchKey</Code></Error>"),
+        },
+        method => panic!("unexpected S3 request {} {}", method, request.path),
+    }
+}
+
+/// Answers requests for both S3 and KMS, until the test ends.
+fn stand_in() -> (StandIn, Arc<Mutex<State>>) {
+    let state = Arc::new(Mutex::new(State::default()));
+    let shared = state.clone();
+    let stand_in = StandIn::start(move |request| {
+        let mut state = shared.lock().unwrap();
+        match request.headers.get("x-amz-target") {
+            Some(target) => kms(&mut state, target, &request.body),
+            None => s3(&mut state, request),
+        }
+    });
+    (stand_in, state)
+}
+
+/// Runs `binary` against the stand-in.
+fn run(binary: &str, stand_in: &StandIn, args: &[&str]) -> Output {
+    example(binary)
+        .args(["--endpoint-url", stand_in.endpoint()])
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn encrypted_objects_round_trip_and_refuse_tampering() {
+    let (stand_in, state) = stand_in();
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("envelope");
+    fs::create_dir_all(&dir).unwrap();
+    let plaintext = b"region,total\nwest,42\neast,17\n";
+    let source = dir.join("q3.csv");
+    let copy = dir.join("q3-copy.csv");
+    fs::write(&source, plaintext).unwrap();
+    let _ = fs::remove_file(&copy);
+
+    let put = run(
+        env!("CARGO_BIN_EXE_put-encrypted-object"),
+        &stand_in,
+        &[
+            "-b",
+            "alpha-examples",
+            "-k",
+            "reports/q3.csv",
+            "-f",
+            source.to_str().unwrap(),
+            "--kms-key",
+            "alias/reports",
+        ],
+    );
+    assert!(put.status.success(), "{:?}", put);
+
+    // S3 holds only ciphertext, with what's needed to decrypt it alongside.
+    let stored = state.lock().unwrap().objects["/alpha-examples/reports/q3.csv"].clone();
+    assert_eq!(stored.body.len(), plaintext.len() + 16);
+    assert!(!stored
+        .body
+        .windows(plaintext.len())
+        .any(|w| w == &plaintext[..]));
+    assert_eq!(
+        stored.metadata["x-amz-meta-x-amz-cek-alg"],
+        "AES/GCM/NoPadding"
+    );
+    assert_eq!(stored.metadata["x-amz-meta-x-amz-wrap-alg"], "kms+context");
+    assert_eq!(
+        stored.metadata["x-amz-meta-x-amz-unencrypted-content-length"],
+        "29"
+    );
+    assert!(stored.metadata.contains_key("x-amz-meta-x-amz-key-v2"));
+    assert!(stored.metadata.contains_key("x-amz-meta-x-amz-iv"));
+
+    let get_args = [
+        "-b",
+        "alpha-examples",
+        "-k",
+        "reports/q3.csv",
+        "-f",
+        copy.to_str().unwrap(),
+    ];
+    let get = run(
+        env!("CARGO_BIN_EXE_get-encrypted-object"),
+        &stand_in,
+        &get_args,
+    );
+    assert!(get.status.success(), "{:?}", get);
+    assert_eq!(fs::read(&copy).unwrap(), plaintext);
+    fs::remove_file(&copy).unwrap();
+
+    // A changed byte fails authentication, and nothing is written.
+    state
+        .lock()
+        .unwrap()
+        .objects
+        .get_mut("/alpha-examples/reports/q3.csv")
+        .unwrap()
+        .body[3] ^= 1;
+    let get = run(
+        env!("CARGO_BIN_EXE_get-encrypted-object"),
+        &stand_in,
+        &get_args,
+    );
+    assert_eq!(get.status.code(), Some(1), "{:?}", get);
+    assert!(String::from_utf8_lossy(&get.stdout).contains("tampered"));
+    assert!(!copy.exists());
+}
@@ -0,0 +1,78 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::testing::{Replay, ReplayRun};
+
+fn report(args: &[&str]) -> ReplayRun {
+    // One object at a time, so that requests are answered in the recorded order.
+    Replay::new(
+        env!("CARGO_BIN_EXE_inventory-report"),
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/inventory-report.json"
+        ),
+    )
+    .args(
+        [
+            "-b",
+            "alpha-examples",
+            "-p",
+            "logs/",
+            "--tags",
+            "--metadata",
+            "--concurrency",
+            "1",
+        ]
+        .iter()
+        .chain(args),
+    )
+    .run()
+}
+
+#[test]
+fn inventory_report_shows_tags_and_metadata_as_json() {
+    let run = report(&["--output", "json"]);
+
+    run.assert_success().assert_requests_match(&[]);
+    let report = run.json();
+    let objects = &report["Objects"];
+    let archived = &objects[0];
+    assert_eq!(archived["Key"], "logs/2020/12/31.gz");
+    assert_eq!(archived["StorageClass"], "GLACIER");
+    assert_eq!(archived["ETag"], "d41d8cd98f00b204e9800998ecf8427e");
+    assert_eq!(archived["Tags"]["team"], "data");
+    assert_eq!(archived["ContentType"], "application/gzip");
+    assert_eq!(archived["Metadata"]["source"], "web");
+
+    let readme = &objects[2];
+    assert_eq!(readme["StorageClass"], "STANDARD");
+    assert_eq!(readme["Tags"], serde_json::json!({}));
+    assert_eq!(readme["Metadata"]["source"], "docs");
+
+    let totals = &report["Totals"];
+    assert_eq!(totals[1]["Prefix"], "logs/2020/");
+    assert_eq!(totals[1]["StorageClass"], "GLACIER");
+    assert_eq!(totals[1]["Bytes"], 50);
+}
+
+#[test]
+fn inventory_report_totals_by_prefix_and_storage_class() {
+    let run = report(&[]);
+
+    run.assert_success().assert_requests_match(&[]);
+    let expected = "\
+Prefix      StorageClass  Objects  Bytes
+----------  ------------  -------  -----
+logs/       STANDARD      1        7
+logs/2020/  GLACIER       1        50
+logs/2021/  STANDARD      1        100
+
+Found 3 objects holding 157 bytes.";
+    assert!(
+        run.stdout.contains(expected),
+        "unexpected output:\n{}",
+        run.stdout
+    );
+}
@@ -0,0 +1,99 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::testing::Replay;
+
+#[test]
+fn list_buckets_prints_every_bucket() {
+    let run = Replay::new(
+        env!("CARGO_BIN_EXE_list-buckets"),
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/list-buckets.json"
+        ),
+    )
+    .run();
+
+    run.assert_success().assert_requests_match(&[]);
+    assert!(
+        run.stdout
+            .contains("alpha-examples\nalpha-logs\n\nFound 2 buckets in all regions."),
+        "unexpected output:\n{}",
+        run.stdout
+    );
+}
+
+#[test]
+fn list_buckets_prints_json() {
+    let run = Replay::new(
+        env!("CARGO_BIN_EXE_list-buckets"),
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/list-buckets.json"
+        ),
+    )
+    .args(["--output", "json"])
+    .run();
+
+    run.assert_success();
+    let buckets = run.json();
+    let names: Vec<_> = buckets
+        .as_array()
+        .expect("an array of buckets")
+        .iter()
+        .map(|bucket| bucket["Name"].as_str().unwrap_or_default())
+        .collect();
+    assert_eq!(names, ["alpha-examples", "alpha-logs"]);
+}
+
+fn list_with_regions(args: &[&str]) -> alpha_common::testing::ReplayRun {
+    // One lookup at a time, so that they are answered in the recorded order.
+    Replay::new(
+        env!("CARGO_BIN_EXE_list-buckets"),
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/list-buckets-regions.json"
+        ),
+    )
+    .args(args.iter().chain(&["--concurrency", "1"]))
+    .run()
+}
+
+#[test]
+fn list_buckets_groups_buckets_by_region() {
+    let run = list_with_regions(&["--by-region"]);
+
+    run.assert_requests_match(&[]);
+    assert!(!run.status.success(), "a bucket's Region was unreadable");
+    assert!(
+        run.stdout.contains(
+            "eu-west-1:\n  alpha-eu\nus-east-1:\n  alpha-logs\nus-west-2:\n  alpha-examples\n\n\
+             Found 3 buckets in 3 regions."
+        ),
+        "unexpected output:\n{}",
+        run.stdout
+    );
+    assert!(
+        run.stderr
+            .contains("Got an error getting the Region of alpha-private:"),
+        "unexpected errors:\n{}",
+        run.stderr
+    );
+}
+
+#[test]
+fn list_buckets_strict_only_lists_buckets_in_the_region() {
+    let run = list_with_regions(&["--strict"]);
+
+    run.assert_requests_match(&[]);
+    assert!(
+        run.stdout.contains(
+            "alpha-examples\n\n\
+             Found 1 buckets in the us-west-2 region out of a total of 4 buckets."
+        ),
+        "unexpected output:\n{}",
+        run.stdout
+    );
+}
@@ -0,0 +1,179 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::testing::{Replay, StandIn, StandInRequest, StandInResponse, SECRET_ACCESS_KEY};
+use ring::{digest, hmac};
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::PathBuf;
+
+fn encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, key), data.as_bytes())
+        .as_ref()
+        .to_vec()
+}
+
+/// Checks a query-string SigV4 signature the way S3 does, independently of
+/// the SDK's signer, returning the status and error code S3 would respond with.
+fn verify(request: &StandInRequest) -> (u16, &'static str) {
+    let signed_headers = request.param("X-Amz-SignedHeaders");
+    let credential = request.param("X-Amz-Credential");
+    let scope = credential
+        .split_once('/')
+        .map(|(_, s)| s)
+        .unwrap_or_default();
+
+    let mut query: Vec<_> = request
+        .query
+        .iter()
+        .filter(|(k, _)| k != "X-Amz-Signature")
+        .map(|(k, v)| format!("{}={}", encode(k), encode(v)))
+        .collect();
+    query.sort();
+    let headers: String = signed_headers
+        .split(';')
+        .map(|name| {
+            let value = request.headers.get(name).map(String::as_str);
+            format!("{}:{}\n", name, value.unwrap_or_default().trim())
+        })
+        .collect();
+    let canonical = [
+        request.method.as_str(),
+        request.path.as_str(),
+        &query.join("&"),
+        &headers,
+        signed_headers,
+        "UNSIGNED-PAYLOAD",
+    ]
+    .join("\n");
+    let string_to_sign = [
+        "AWS4-HMAC-SHA256",
+        request.param("X-Amz-Date"),
+        scope,
+        &hex::encode(digest::digest(&digest::SHA256, canonical.as_bytes())),
+    ]
+    .join("\n");
+
+    let mut key = format!("AWS4{}", SECRET_ACCESS_KEY).into_bytes();
+    for part in scope.split('/') {
+        key = hmac_sha256(&key, part);
+    }
+    if hex::encode(hmac_sha256(&key, &string_to_sign)) != request.param("X-Amz-Signature") {
+        return (403, "SignatureDoesNotMatch");
+    }
+    if let Some(md5) = request.headers.get("content-md5") {
+        if *md5 != base64::encode(md5::compute(&request.body).0) {
+            return (400, "BadDigest");
+        }
+    }
+    (200, "")
+}
+
+/// Answers requests as S3 would a presigned request.
+fn stand_in() -> StandIn {
+    StandIn::start(|request| match verify(&request) {
+        (status, "") => StandInResponse::new(status, ""),
+        (status, code) => {
+            StandInResponse::new(status, format!("<Error><Code>{}</Code></Error>", code))
+        }
+    })
+}
+
+/// Sends a request to the stand-in, returning the response's status.
+fn send(url: &str, method: &str, headers: &[(&str, &str)], body: &[u8]) -> u16 {
+    let rest = url.strip_prefix("http://").unwrap();
+    let (host, target) = rest.split_at(rest.find('/').unwrap());
+    let mut stream = TcpStream::connect(host).unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nhost: {}\r\n",
+        method, target, host
+    )
+    .unwrap();
+    for (name, value) in headers {
+        write!(stream, "{}: {}\r\n", name, value).unwrap();
+    }
+    write!(stream, "content-length: {}\r\n\r\n", body.len()).unwrap();
+    stream.write_all(body).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response[9..12].parse().unwrap()
+}
+
+#[test]
+fn presigned_put_is_accepted_only_as_signed() {
+    let stand_in = stand_in();
+    let file = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("q3.csv");
+    fs::write(&file, "region,total\nwest,42\n").unwrap();
+
+    let run = Replay::new(
+        env!("CARGO_BIN_EXE_presign"),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/presign.json"),
+    )
+    .args([
+        "--endpoint-url".as_ref(),
+        stand_in.endpoint().as_ref(),
+        "-b".as_ref(),
+        "alpha-examples".as_ref(),
+        "-k".as_ref(),
+        "reports/q3 totals.csv".as_ref(),
+        "-m".as_ref(),
+        "put".as_ref(),
+        "-e".as_ref(),
+        "600".as_ref(),
+        "-c".as_ref(),
+        "text/csv".as_ref(),
+        "--md5-of".as_ref(),
+        file.as_os_str(),
+    ])
+    .run();
+
+    run.assert_success().assert_requests_match(&[]);
+    let mut lines = run.stdout.lines();
+    lines
+        .find(|line| line.starts_with("Presigned PUT URL, valid for 600 seconds"))
+        .expect("the URL is printed");
+    let url = lines.next().unwrap();
+    assert!(url.contains("X-Amz-Expires=600"), "{}", url);
+    let headers: Vec<(&str, &str)> = lines
+        .filter_map(|line| line.strip_prefix("  ")?.split_once(": "))
+        .collect();
+    assert!(
+        headers.contains(&("content-type", "text/csv")),
+        "{:?}",
+        headers
+    );
+    assert!(
+        run.stdout.contains("The Content-Type is not signed"),
+        "{}",
+        run.stdout
+    );
+
+    let body = fs::read(&file).unwrap();
+    assert_eq!(send(url, "PUT", &headers, &body), 200);
+
+    // The Content-Type is not signed, so another one is accepted.
+    let csv = headers.iter().position(|h| h.0 == "content-type").unwrap();
+    let mut other_type = headers.clone();
+    other_type[csv].1 = "text/plain";
+    assert_eq!(send(url, "PUT", &other_type, &body), 200);
+
+    // Any other body, or a longer expiry, is refused.
+    assert_eq!(send(url, "PUT", &headers, b"region,total\nwest,0\n"), 400);
+    let longer = url.replace("X-Amz-Expires=600", "X-Amz-Expires=6000");
+    assert_eq!(send(&longer, "PUT", &headers, &body), 403);
+}
@@ -0,0 +1,62 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::testing::{Replay, ReplayRun};
+
+fn select(fixture: &str, args: &[&str]) -> ReplayRun {
+    Replay::new(
+        env!("CARGO_BIN_EXE_select-object"),
+        format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), fixture),
+    )
+    .args([
+        "-b",
+        "alpha-examples",
+        "-k",
+        "logs/access.csv",
+        "-q",
+        "SELECT s.path FROM S3Object s WHERE s.status = '500'",
+        "--progress",
+    ])
+    .args(args)
+    .run()
+}
+
+#[test]
+fn select_object_writes_only_the_results() {
+    // Even the banner goes to stderr.
+    let run = select("select-object.json", &["-v"]);
+
+    run.assert_success().assert_requests_match(&[]);
+    assert_eq!(run.stdout, "/checkout\n/cart\n");
+    assert!(
+        run.stderr.contains("S3 client version:"),
+        "no banner:\n{}",
+        run.stderr
+    );
+    assert!(
+        run.stderr
+            .contains("Scanned 4096 bytes, processed 4096, returned 0\n")
+            && run
+                .stderr
+                .contains("Done. Scanned 8192 bytes, processed 8192, returned 16\n"),
+        "unexpected progress:\n{}",
+        run.stderr
+    );
+}
+
+#[test]
+fn select_object_fails_when_the_results_stop_early() {
+    let run = select("select-object-incomplete.json", &[]);
+
+    run.assert_requests_match(&[]);
+    assert!(!run.status.success(), "the results were incomplete");
+    assert_eq!(run.stdout, "/checkout\n");
+    assert!(
+        run.stderr
+            .contains("the results ended before the query finished"),
+        "unexpected errors:\n{}",
+        run.stderr
+    );
+}
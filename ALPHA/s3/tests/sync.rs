@@ -0,0 +1,99 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::testing::Replay;
+use std::fs;
+use std::path::PathBuf;
+
+#[test]
+fn sync_uploads_changes_and_deletes_extraneous_objects() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("sync-site");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("drafts")).unwrap();
+    // index.html is newer than its object but has the same contents, and
+    // drafts are excluded, so neither is uploaded, nor drafts/old.html deleted.
+    fs::write(dir.join("index.html"), "<h1>Hi</h1>\n").unwrap();
+    fs::write(dir.join("style.css"), "body { margin: 0; }\n").unwrap();
+    fs::write(dir.join("new.js"), "console.log(1);\n").unwrap();
+    fs::write(dir.join("drafts/wip.html"), "<p>wip</p>\n").unwrap();
+
+    let run = Replay::new(
+        env!("CARGO_BIN_EXE_sync"),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/sync.json"),
+    )
+    .args([
+        "-b".as_ref(),
+        "alpha-examples".as_ref(),
+        "-p".as_ref(),
+        "site".as_ref(),
+        "-d".as_ref(),
+        dir.as_os_str(),
+        "--delete".as_ref(),
+        "--exclude".as_ref(),
+        "drafts/**".as_ref(),
+        "--files".as_ref(),
+        "1".as_ref(),
+    ])
+    .run();
+
+    run.assert_success().assert_requests_match(&["content-md5"]);
+    for line in [
+        "/new.js to s3://alpha-examples/site/new.js (new)",
+        "/style.css to s3://alpha-examples/site/style.css (size changed)",
+        "delete: s3://alpha-examples/site/old.html",
+        "Uploaded 2 files, deleted 1 objects, and left 1 files unchanged.",
+    ] {
+        assert!(
+            run.stdout.contains(line),
+            "missing {:?} in output:\n{}",
+            line,
+            run.stdout
+        );
+    }
+}
+
+#[test]
+fn sync_lists_the_objects_it_could_not_delete() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("sync-locked");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("index.html"), "<h1>Hi</h1>\n").unwrap();
+
+    let run = Replay::new(
+        env!("CARGO_BIN_EXE_sync"),
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/sync-delete-denied.json"
+        ),
+    )
+    .args([
+        "-b".as_ref(),
+        "alpha-examples".as_ref(),
+        "-p".as_ref(),
+        "site".as_ref(),
+        "-d".as_ref(),
+        dir.as_os_str(),
+        "--delete".as_ref(),
+    ])
+    .run();
+
+    assert!(!run.status.success(), "{}", run.stdout);
+    run.assert_requests_match(&["content-md5"]);
+    for line in [
+        "delete: s3://alpha-examples/site/old.html",
+        "Uploaded 0 files, deleted 1 objects, and left 1 files unchanged.",
+        "  s3://alpha-examples/site/locked.html: AccessDenied: Access Denied",
+    ] {
+        assert!(
+            run.stdout.contains(line),
+            "missing {:?} in output:\n{}",
+            line,
+            run.stdout
+        );
+    }
+    assert!(!run
+        .stdout
+        .contains("delete: s3://alpha-examples/site/locked.html"));
+}
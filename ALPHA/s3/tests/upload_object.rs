@@ -0,0 +1,75 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0.
+ */
+
+use alpha_common::testing::Replay;
+use std::fs;
+use std::path::PathBuf;
+
+#[test]
+fn upload_object_resumes_an_interrupted_upload() {
+    let file = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("big.txt");
+    fs::write(&file, "abcdefghijklmnopqrst").unwrap();
+
+    // Part 1 is already uploaded. Part 2 was uploaded with other contents,
+    // so it is sent again along with part 3.
+    let run = Replay::new(
+        env!("CARGO_BIN_EXE_upload-object"),
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/upload-object.json"
+        ),
+    )
+    .args([
+        "-b".as_ref(),
+        "alpha-examples".as_ref(),
+        "-f".as_ref(),
+        file.as_os_str(),
+        "--threshold".as_ref(),
+        "10".as_ref(),
+        "--part-size".as_ref(),
+        "8".as_ref(),
+        "--concurrency".as_ref(),
+        "1".as_ref(),
+    ])
+    .run();
+
+    run.assert_success().assert_requests_match(&["content-md5"]);
+    assert!(
+        run.stdout.contains(
+            "Uploaded big.txt in 3 parts, 1 of them by an earlier run. \
+             ETag: 42e07c05df08444cf4450aabdf7d250d-3"
+        ),
+        "unexpected output:\n{}",
+        run.stdout
+    );
+}
+
+#[test]
+fn upload_object_accepts_the_etag_of_a_kms_encrypted_object() {
+    let file = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("small.txt");
+    fs::write(&file, "abcd").unwrap();
+
+    // The ETag of an object encrypted with a KMS key is not its MD5.
+    let run = Replay::new(
+        env!("CARGO_BIN_EXE_upload-object"),
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/upload-object-kms.json"
+        ),
+    )
+    .args([
+        "-b".as_ref(),
+        "alpha-examples".as_ref(),
+        "-f".as_ref(),
+        file.as_os_str(),
+    ])
+    .run();
+
+    run.assert_success().assert_requests_match(&["content-md5"]);
+    assert_eq!(
+        run.stdout,
+        "Uploaded small.txt. ETag: 9c2a1f0e5b7d4e3a8c6b0d2f4e1a3c5b\n"
+    );
+}
@@ -3,74 +3,95 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_sagemaker::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::{date_time, Listing};
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_sagemaker::{Error, PKG_VERSION};
+use aws_smithy_types_convert::date_time::DateTimeExt;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists your SageMaker jobs in the Region.
 /// /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.#[tokio::main]
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
-    let Opt { region, verbose } = Opt::from_args();
-
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-
-    println!();
-
-    if verbose {
-        println!("SageMaker client version: {}", PKG_VERSION);
-        println!(
-            "Region:                   {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!();
+    let Opt { pages, common } = Opt::from_args();
+
+    let shared = common.load().await;
+
+    shared.banner("SageMaker", PKG_VERSION, &[]);
+
+    let client = shared.sagemaker();
+
+    let jobs = paginate(&pages, move |token, max_results| {
+        let request = client
+            .list_training_jobs()
+            .set_next_token(token)
+            .set_max_results(max_results);
+        async move {
+            request.send().await.map(|resp| {
+                Page::with_token(
+                    resp.training_job_summaries.unwrap_or_default(),
+                    resp.next_token,
+                )
+            })
+        }
+    })
+    .try_collect()
+    .await?;
+
+    let mut listing = Listing::new(&[
+        "TrainingJobName",
+        "CreationTime",
+        "TrainingEndTime",
+        "TrainingJobStatus",
+    ]);
+    for j in &jobs {
+        listing.push(vec![
+            j.training_job_name.as_deref().into(),
+            date_time(j.creation_time.as_ref()),
+            date_time(j.training_end_time.as_ref()),
+            j.training_job_status.as_ref().map(|s| s.as_str()).into(),
+        ]);
     }
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    shared.print(&listing, || {
+        println!("Jobs:");
 
-    let job_details = client.list_training_jobs().send().await?;
+        for j in jobs {
+            let name = j.training_job_name.as_deref().unwrap_or_default();
+            let creation_time = j.creation_time.unwrap().to_chrono_utc();
+            let training_end_time = j.training_end_time.unwrap().to_chrono_utc();
 
-    println!("Jobs:");
+            let status = j.training_job_status.unwrap();
+            let duration = training_end_time - creation_time;
 
-    for j in job_details.training_job_summaries.unwrap_or_default() {
-        let name = j.training_job_name.as_deref().unwrap_or_default();
-        let creation_time = j.creation_time.unwrap().to_chrono();
-        let training_end_time = j.training_end_time.unwrap().to_chrono();
+            println!("  Name:               {}", name);
+            println!(
+                "  Creation date/time: {}",
+                creation_time.format("%Y-%m-%d@%H:%M:%S")
+            );
+            println!("  Duration (seconds): {}", duration.num_seconds());
+            println!("  Status:             {}", status.as_ref());
 
-        let status = j.training_job_status.unwrap();
-        let duration = training_end_time - creation_time;
-
-        println!("  Name:               {}", name);
-        println!(
-            "  Creation date/time: {}",
-            creation_time.format("%Y-%m-%d@%H:%M:%S")
-        );
-        println!("  Duration (seconds): {}", duration.num_seconds());
-        println!("  Status:             {}", status.as_ref());
-
-        println!();
-    }
+            println!();
+        }
+    });
 
     Ok(())
 }
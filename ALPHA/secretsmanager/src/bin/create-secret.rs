@@ -3,16 +3,12 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_secretsmanager::{Client, Error, Region, PKG_VERSION};
+use alpha_common::CommonOpt;
+use aws_sdk_secretsmanager::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The name of the secret.
     #[structopt(short, long)]
     name: String,
@@ -21,9 +17,8 @@ struct Opt {
     #[structopt(short, long)]
     secret_value: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Creates a secret.
@@ -37,33 +32,21 @@ struct Opt {
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
     let Opt {
         name,
-        region,
         secret_value,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("SecretsManager client version: {}", PKG_VERSION);
-        println!(
-            "Region:                        {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Secret name:                   {}", &name);
-        println!("Secret value:                  {}", &secret_value);
-        println!();
-    }
+    shared.banner(
+        "SecretsManager",
+        PKG_VERSION,
+        &[("Secret name", &name), ("Secret value", &secret_value)],
+    );
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.secretsmanager();
 
     client
         .create_secret()
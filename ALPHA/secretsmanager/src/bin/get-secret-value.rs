@@ -3,23 +3,19 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_secretsmanager::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::Listing;
+use alpha_common::CommonOpt;
+use aws_sdk_secretsmanager::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The name of the secret.
     #[structopt(short, long)]
     name: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Retrieves the value of a secret.
@@ -33,38 +29,29 @@ struct Opt {
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-    let Opt {
-        name,
-        region,
-        verbose,
-    } = Opt::from_args();
+    let Opt { name, common } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
+    let shared = common.load().await;
 
-    println!();
+    shared.banner("SecretsMManager", PKG_VERSION, &[("Secret name", &name)]);
 
-    if verbose {
-        println!("SecretsMManager client version: {}", PKG_VERSION);
-        println!(
-            "Region:                         {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Secret name:                    {}", name);
-        println!();
-    }
-
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.secretsmanager();
 
     let resp = client.get_secret_value().secret_id(name).send().await?;
 
-    println!(
-        "Value: {}",
-        resp.secret_string.as_deref().unwrap_or("No value!")
-    );
+    let mut listing = Listing::new(&["Name", "VersionId", "SecretString"]);
+    listing.push(vec![
+        resp.name.as_deref().into(),
+        resp.version_id.as_deref().into(),
+        resp.secret_string.as_deref().into(),
+    ]);
+
+    shared.print(&listing, || {
+        println!(
+            "Value: {}",
+            resp.secret_string.as_deref().unwrap_or("No value!")
+        );
+    });
 
     Ok(())
 }
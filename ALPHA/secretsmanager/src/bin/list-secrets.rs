@@ -3,62 +3,73 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_secretsmanager::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::{date_time, Listing};
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_secretsmanager::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists the names of your secrets in the Region.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { pages, common } = Opt::from_args();
 
-    let Opt { region, verbose } = Opt::from_args();
+    let shared = common.load().await;
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
+    shared.banner("SecretsManager", PKG_VERSION, &[]);
 
-    println!();
+    let client = shared.secretsmanager();
 
-    if verbose {
-        println!("SecretsManager client version: {}", PKG_VERSION);
-        println!(
-            "Region:                        {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!();
-    }
-
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
-
-    let resp = client.list_secrets().send().await?;
+    let secrets = paginate(&pages, move |token, max_results| {
+        let request = client
+            .list_secrets()
+            .set_next_token(token)
+            .set_max_results(max_results);
+        async move {
+            request
+                .send()
+                .await
+                .map(|resp| Page::with_token(resp.secret_list.unwrap_or_default(), resp.next_token))
+        }
+    })
+    .try_collect()
+    .await?;
 
-    println!("Secret names:");
-
-    let secrets = resp.secret_list.unwrap_or_default();
+    let mut listing = Listing::new(&["Name", "ARN", "LastChangedDate"]);
     for secret in &secrets {
-        println!("  {}", secret.name.as_deref().unwrap_or("No name!"));
+        listing.push(vec![
+            secret.name.as_deref().into(),
+            secret.arn.as_deref().into(),
+            date_time(secret.last_changed_date.as_ref()),
+        ]);
     }
 
-    println!("Found {} secrets", secrets.len());
+    shared.print(&listing, || {
+        println!("Secret names:");
+
+        for secret in &secrets {
+            println!("  {}", secret.name.as_deref().unwrap_or("No name!"));
+        }
+
+        println!("Found {} secrets", secrets.len());
+    });
 
     Ok(())
 }
@@ -3,8 +3,8 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_ses::{Client, Error, Region, PKG_VERSION};
+use alpha_common::CommonOpt;
+use aws_sdk_ses::{Error, PKG_VERSION};
 
 use structopt::StructOpt;
 
@@ -14,44 +14,30 @@ struct Opt {
     #[structopt(short, long)]
     contact_list: String,
 
-    /// The AWS Region.
-    #[structopt(short, long)]
-    default_region: Option<String>,
-
-    /// Whether to display additional runtime information
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Creates a new contact list.
 /// # Arguments
 ///
 /// * `-c CONTACT-LIST` - The name of the contact list.
-/// * `[-d DEFAULT-REGION]` - The region in which the client is created.
-///    If not supplied, uses the value of the **AWS_DEFAULT_REGION** environment variable.
+/// * `[-r REGION]` - The region in which the client is created.
+///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
     let Opt {
         contact_list,
-        default_region,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(default_region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
+    let shared = common.load().await;
 
-    if verbose {
-        println!("SES client version: {}", PKG_VERSION);
-        println!("Region:             {:?}", shared_config.region().unwrap());
-        println!("Contact list:       {}", &contact_list);
-        println!();
-    }
+    shared.banner("SES", PKG_VERSION, &[("Contact list", &contact_list)]);
 
-    let client = Client::new(&shared_config);
+    let client = shared.ses();
 
     let resp = client
         .create_contact_list()
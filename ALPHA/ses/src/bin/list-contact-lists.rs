@@ -3,54 +3,68 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_ses::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::{date_time, Listing};
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_ses::{Error, PKG_VERSION};
 
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    default_region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional runtime information
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists your contact lists (there should only be one).
 /// # Arguments
 ///
-/// * `[-d DEFAULT-REGION]` - The region in which the client is created.
-///    If not supplied, uses the value of the **AWS_DEFAULT_REGION** environment variable.
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
+/// * `[-r REGION]` - The region in which the client is created.
+///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    let Opt {
-        default_region,
-        verbose,
-    } = Opt::from_args();
-
-    let region_provider = RegionProviderChain::first_try(default_region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-
-    if verbose {
-        println!("SES client version: {}", PKG_VERSION);
-        println!("Region:             {:?}", shared_config.region().unwrap());
-        println!();
-    }
+    let Opt { pages, common } = Opt::from_args();
+
+    let shared = common.load().await;
 
-    let client = Client::new(&shared_config);
+    shared.banner("SES", PKG_VERSION, &[]);
 
-    let resp = client.list_contact_lists().send().await;
+    let client = shared.ses();
 
-    for list in resp.unwrap().contact_lists.unwrap_or_default() {
-        println!("{}", list.contact_list_name.as_deref().unwrap_or_default());
+    let lists = paginate(&pages, move |token, page_size| {
+        let request = client
+            .list_contact_lists()
+            .set_next_token(token)
+            .set_page_size(page_size);
+        async move {
+            request.send().await.map(|resp| {
+                Page::with_token(resp.contact_lists.unwrap_or_default(), resp.next_token)
+            })
+        }
+    })
+    .try_collect()
+    .await?;
+
+    let mut listing = Listing::new(&["ContactListName", "LastUpdatedTimestamp"]);
+    for list in &lists {
+        listing.push(vec![
+            list.contact_list_name.as_deref().into(),
+            date_time(list.last_updated_timestamp.as_ref()),
+        ]);
     }
 
+    shared.print(&listing, || {
+        for list in lists {
+            println!("{}", list.contact_list_name.as_deref().unwrap_or_default());
+        }
+    });
+
     Ok(())
 }
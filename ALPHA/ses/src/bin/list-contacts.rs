@@ -3,8 +3,10 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_ses::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::{date_time, Listing};
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_ses::{Error, PKG_VERSION};
 
 use structopt::StructOpt;
 
@@ -14,54 +16,67 @@ struct Opt {
     #[structopt(short, long)]
     contact_list: String,
 
-    /// The AWS Region.
-    #[structopt(short, long)]
-    default_region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional runtime information
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists the contacts in a contact list.
 /// # Arguments
 ///
 /// * `-c CONTACT-LIST` - The name of the contact list.
-/// * `[-d DEFAULT-REGION]` - The region in which the client is created.
-///    If not supplied, uses the value of the **AWS_DEFAULT_REGION** environment variable.
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
+/// * `[-r REGION]` - The region in which the client is created.
+///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
     let Opt {
         contact_list,
-        default_region,
-        verbose,
+        pages,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(default_region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
+    let shared = common.load().await;
 
-    if verbose {
-        println!("SES client version: {}", PKG_VERSION);
-        println!("Region:             {:?}", shared_config.region().unwrap());
-        println!("Contact list:       {}", &contact_list);
-        println!();
-    }
+    shared.banner("SES", PKG_VERSION, &[("Contact list", &contact_list)]);
 
-    let client = Client::new(&shared_config);
+    let client = shared.ses();
 
-    let resp = client
-        .list_contacts()
-        .contact_list_name(contact_list)
-        .send()
-        .await;
+    let contacts = paginate(&pages, move |token, page_size| {
+        let request = client
+            .list_contacts()
+            .contact_list_name(&contact_list)
+            .set_next_token(token)
+            .set_page_size(page_size);
+        async move {
+            request
+                .send()
+                .await
+                .map(|resp| Page::with_token(resp.contacts.unwrap_or_default(), resp.next_token))
+        }
+    })
+    .try_collect()
+    .await?;
 
-    for contact in resp.unwrap().contacts.unwrap_or_default() {
-        println!("{}", contact.email_address.as_deref().unwrap_or_default());
+    let mut listing = Listing::new(&["EmailAddress", "UnsubscribeAll", "LastUpdatedTimestamp"]);
+    for contact in &contacts {
+        listing.push(vec![
+            contact.email_address.as_deref().into(),
+            contact.unsubscribe_all.into(),
+            date_time(contact.last_updated_timestamp.as_ref()),
+        ]);
     }
 
+    shared.print(&listing, || {
+        for contact in contacts {
+            println!("{}", contact.email_address.as_deref().unwrap_or_default());
+        }
+    });
+
     Ok(())
 }
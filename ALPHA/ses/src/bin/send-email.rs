@@ -3,9 +3,9 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
+use alpha_common::CommonOpt;
 use aws_sdk_ses::model::{Body, Content, Destination, EmailContent, Message};
-use aws_sdk_ses::{Client, Error, Region, PKG_VERSION};
+use aws_sdk_ses::{Error, PKG_VERSION};
 
 use structopt::StructOpt;
 
@@ -15,10 +15,6 @@ struct Opt {
     #[structopt(short, long)]
     contact_list: String,
 
-    /// The AWS Region.
-    #[structopt(short, long)]
-    default_region: Option<String>,
-
     /// The email address of the sender.
     #[structopt(short, long)]
     from_address: String,
@@ -30,9 +26,9 @@ struct Opt {
     /// The subject of the email.
     #[structopt(short, long)]
     subject: String,
-    /// Whether to display additional runtime information
-    #[structopt(short, long)]
-    verbose: bool,
+
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Sends a message to the email addresses in the contact list.
@@ -42,37 +38,34 @@ struct Opt {
 /// * `-m MESSAGE` - The email message that is sent.
 /// * `-s SUBJECT` - The subject of the email message.
 /// * `-c CONTACT-LIST` - The contact list with the email addresses of the recepients.
-/// * `[-d DEFAULT-REGION]` - The region in which the client is created.
-///    If not supplied, uses the value of the **AWS_DEFAULT_REGION** environment variable.
+/// * `[-r REGION]` - The region in which the client is created.
+///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
     let Opt {
         contact_list,
-        default_region,
         from_address,
         message,
         subject,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(default_region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-
-    if verbose {
-        println!("SES client version: {}", PKG_VERSION);
-        println!("Region:             {:?}", shared_config.region().unwrap());
-        println!("From address:       {}", &from_address);
-        println!("Contact list:       {}", &contact_list);
-        println!("Subject:            {}", &subject);
-        println!("Message:            {}", &message);
-        println!();
-    }
+    let shared = common.load().await;
+
+    shared.banner(
+        "SES",
+        PKG_VERSION,
+        &[
+            ("From address", &from_address),
+            ("Contact list", &contact_list),
+            ("Subject", &subject),
+            ("Message", &message),
+        ],
+    );
 
-    let client = Client::new(&shared_config);
+    let client = shared.ses();
 
     // Get list of email addresses from contact list.
     let resp = client
@@ -3,17 +3,13 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
+use alpha_common::CommonOpt;
 use aws_sdk_snowball::model::Address;
-use aws_sdk_snowball::{Client, Error, Region, PKG_VERSION};
+use aws_sdk_snowball::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     // Address information.
     #[structopt(long)]
     city: String,
@@ -51,9 +47,8 @@ struct Opt {
     #[structopt(long)]
     street3: Option<String>,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Creates an AWS Snowball address.
@@ -77,10 +72,7 @@ struct Opt {
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
-        region,
         city,
         company,
         country,
@@ -93,34 +85,32 @@ async fn main() -> Result<(), Error> {
         street1,
         street2,
         street3,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-
-    println!();
-
-    if verbose {
-        println!("Snowball version:       {}", PKG_VERSION);
-        println!(
-            "Region:                 {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("City:                   {}", &city);
-        println!("Company:                {:?}", &company);
-        println!("Country:                {}", &country);
-        println!("Landmark:               {:?}", &landmark);
-        println!("Name:                   {}", &name);
-        println!("Phone number:           {}", &phone_number);
-        println!("Postal code:            {}", &postal_code);
-        println!("Prefecture or district: {:?}", &prefecture_or_district);
-        println!("State:                  {}", &state);
-        println!("Street1:                {}", &street1);
-        println!("Street2:                {:?}", &street2);
-        println!("Street3:                {:?}", &street3);
-    }
+    let shared = common.load().await;
+
+    shared.banner(
+        "Snowball",
+        PKG_VERSION,
+        &[
+            ("City", &city),
+            ("Company", &company.as_deref().unwrap_or_default()),
+            ("Country", &country),
+            ("Landmark", &landmark.as_deref().unwrap_or_default()),
+            ("Name", &name),
+            ("Phone number", &phone_number),
+            ("Postal code", &postal_code),
+            (
+                "Prefecture or district",
+                &prefecture_or_district.as_deref().unwrap_or_default(),
+            ),
+            ("State", &state),
+            ("Street1", &street1),
+            ("Street2", &street2.as_deref().unwrap_or_default()),
+            ("Street3", &street3.as_deref().unwrap_or_default()),
+        ],
+    );
 
     let new_address = Address::builder()
         .set_address_id(None)
@@ -139,8 +129,7 @@ async fn main() -> Result<(), Error> {
         .set_is_restricted(Some(false))
         .build();
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.snowball();
 
     let result = client.create_address().address(new_address).send().await?;
 
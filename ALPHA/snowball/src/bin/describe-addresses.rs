@@ -3,56 +3,81 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_snowball::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::Listing;
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_snowball::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists your AWS Snowball addresses.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { pages, common } = Opt::from_args();
 
-    let Opt { region, verbose } = Opt::from_args();
+    let shared = common.load().await;
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
+    shared.banner("Snowball", PKG_VERSION, &[]);
 
-    println!();
+    let client = shared.snowball();
 
-    if verbose {
-        println!("Snowball version: {}", PKG_VERSION);
-        println!(
-            "Region:               {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!();
-    }
-
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let addresses = paginate(&pages, move |token, max_results| {
+        let request = client
+            .describe_addresses()
+            .set_next_token(token)
+            .set_max_results(max_results);
+        async move {
+            request
+                .send()
+                .await
+                .map(|resp| Page::with_token(resp.addresses.unwrap_or_default(), resp.next_token))
+        }
+    })
+    .try_collect()
+    .await?;
 
-    let addresses = client.describe_addresses().send().await?;
-    for address in addresses.addresses.unwrap() {
-        println!("Address: {:?}", address);
+    let mut listing = Listing::new(&[
+        "AddressId",
+        "Name",
+        "Street1",
+        "City",
+        "StateOrProvince",
+        "PostalCode",
+        "Country",
+    ]);
+    for address in &addresses {
+        listing.push(vec![
+            address.address_id.as_deref().into(),
+            address.name.as_deref().into(),
+            address.street1.as_deref().into(),
+            address.city.as_deref().into(),
+            address.state_or_province.as_deref().into(),
+            address.postal_code.as_deref().into(),
+            address.country.as_deref().into(),
+        ]);
     }
 
+    shared.print(&listing, || {
+        for address in addresses {
+            println!("Address: {:?}", address);
+        }
+    });
+
     Ok(())
 }
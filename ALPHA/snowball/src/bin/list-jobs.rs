@@ -3,58 +3,71 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_snowball::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::{date_time, Listing};
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_snowball::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists your AWS Snowball jobs.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { pages, common } = Opt::from_args();
 
-    let Opt { region, verbose } = Opt::from_args();
+    let shared = common.load().await;
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
+    shared.banner("Snowball", PKG_VERSION, &[]);
 
-    println!();
+    let client = shared.snowball();
 
-    if verbose {
-        println!("Snowball client version: {}", PKG_VERSION);
-        println!(
-            "Region:                  {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!();
-    }
+    let jobs = paginate(&pages, move |token, max_results| {
+        let request = client
+            .list_jobs()
+            .set_next_token(token)
+            .set_max_results(max_results);
+        async move {
+            request.send().await.map(|resp| {
+                Page::with_token(resp.job_list_entries.unwrap_or_default(), resp.next_token)
+            })
+        }
+    })
+    .try_collect()
+    .await?;
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let mut listing = Listing::new(&["JobId", "JobState", "JobType", "CreationDate"]);
+    for job in &jobs {
+        listing.push(vec![
+            job.job_id.as_deref().into(),
+            job.job_state.as_ref().map(|s| s.as_str()).into(),
+            job.job_type.as_ref().map(|t| t.as_str()).into(),
+            date_time(job.creation_date.as_ref()),
+        ]);
+    }
 
-    println!("Jobs:");
+    shared.print(&listing, || {
+        println!("Jobs:");
 
-    let jobs = client.list_jobs().send().await?;
-    for job in jobs.job_list_entries.unwrap() {
-        println!("  JobId: {:?}", job.job_id);
-    }
+        for job in jobs {
+            println!("  JobId: {:?}", job.job_id);
+        }
+    });
 
     Ok(())
 }
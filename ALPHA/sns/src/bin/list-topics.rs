@@ -3,65 +3,71 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_sns::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::Listing;
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::{CommonOpt, Shared};
+use aws_sdk_sns::{Client, Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 // Shows your topics.
-async fn show_topics(client: &Client) -> Result<(), Error> {
-    let resp = client.list_topics().send().await?;
+async fn show_topics(shared: &Shared, pages: &PageOpt, client: &Client) -> Result<(), Error> {
+    // ListTopics has no page size.
+    let client = client.clone();
+    let topics = paginate(pages, move |token, _| {
+        let request = client.list_topics().set_next_token(token);
+        async move {
+            request
+                .send()
+                .await
+                .map(|resp| Page::with_token(resp.topics.unwrap_or_default(), resp.next_token))
+        }
+    })
+    .try_collect()
+    .await?;
 
-    println!("Topic ARNs:");
-
-    for topic in resp.topics.unwrap_or_default() {
-        println!("{}", topic.topic_arn.as_deref().unwrap_or_default());
+    let mut listing = Listing::new(&["TopicArn"]);
+    for topic in &topics {
+        listing.push(vec![topic.topic_arn.as_deref().into()]);
     }
 
+    shared.print(&listing, || {
+        println!("Topic ARNs:");
+
+        for topic in topics {
+            println!("{}", topic.topic_arn.as_deref().unwrap_or_default());
+        }
+    });
+
     Ok(())
 }
 
 /// Lists your Amazon SNS topics in the Region.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
-    let Opt { region, verbose } = Opt::from_args();
+    let Opt { pages, common } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
+    let shared = common.load().await;
 
-    println!();
-
-    if verbose {
-        println!("SNS client version:   {}", PKG_VERSION);
-        println!(
-            "Region:               {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-
-        println!();
-    }
+    shared.banner("SNS", PKG_VERSION, &[]);
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.sns();
 
-    show_topics(&client).await
+    show_topics(&shared, &pages, &client).await
 }
@@ -3,16 +3,12 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_sns::{Client, Error, Region, PKG_VERSION};
+use alpha_common::CommonOpt;
+use aws_sdk_sns::{Client, Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The email address to subscribe to the topic.
     #[structopt(short, long)]
     email_address: String,
@@ -21,9 +17,8 @@ struct Opt {
     #[structopt(short, long)]
     topic_arn: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 // Subscribes an email address and publishes a message to a topic.
@@ -69,34 +64,21 @@ async fn subscribe_and_publish(
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
-
     let Opt {
-        region,
         email_address,
         topic_arn,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("SNS client version:   {}", PKG_VERSION);
-        println!(
-            "Region:               {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Email address:        {}", &email_address);
-        println!("Topic ARN:            {}", &topic_arn);
-        println!();
-    }
+    shared.banner(
+        "SNS",
+        PKG_VERSION,
+        &[("Email address", &email_address), ("Topic ARN", &topic_arn)],
+    );
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.sns();
 
     subscribe_and_publish(&client, &topic_arn, &email_address).await
 }
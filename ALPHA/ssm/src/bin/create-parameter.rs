@@ -3,17 +3,13 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
+use alpha_common::CommonOpt;
 use aws_sdk_ssm::model::ParameterType;
-use aws_sdk_ssm::{Client, Error, Region, PKG_VERSION};
+use aws_sdk_ssm::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The parameter name.
     #[structopt(short, long)]
     name: String,
@@ -26,9 +22,8 @@ struct Opt {
     #[structopt(short, long)]
     title: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Creates a new AWS Systems Manager parameter in the Region.
@@ -43,35 +38,26 @@ struct Opt {
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
     let Opt {
         name,
         parameter_value,
         title,
-        region,
-        verbose,
+        common,
     } = Opt::from_args();
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-
-    println!();
+    let shared = common.load().await;
 
-    if verbose {
-        println!("SQS client version:   {}", PKG_VERSION);
-        println!(
-            "Region:               {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Parameter name:       {}", &name);
-        println!("Paramter value:       {}", &parameter_value);
-        println!("Paramter description: {}", &title);
-        println!();
-    }
+    shared.banner(
+        "SQS",
+        PKG_VERSION,
+        &[
+            ("Parameter name", &name),
+            ("Paramter value", &parameter_value),
+            ("Paramter description", &title),
+        ],
+    );
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.ssm();
 
     let resp = client
         .put_parameter()
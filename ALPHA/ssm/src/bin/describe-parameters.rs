@@ -3,59 +3,72 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
-use aws_config::meta::region::RegionProviderChain;
-use aws_sdk_ssm::{Client, Error, Region, PKG_VERSION};
+use alpha_common::output::{date_time, Listing};
+use alpha_common::pagination::{paginate, Page, PageOpt};
+use alpha_common::CommonOpt;
+use aws_sdk_ssm::{Error, PKG_VERSION};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
+    #[structopt(flatten)]
+    pages: PageOpt,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Lists the names of your AWS Systems Manager parameters in the Region.
 /// # Arguments
 ///
+/// * `[--max-items MAX-ITEMS]` - The most items to list.
+/// * `[--page-size PAGE-SIZE]` - The number of items to request at a time.
 /// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { pages, common } = Opt::from_args();
 
-    let Opt { region, verbose } = Opt::from_args();
+    let shared = common.load().await;
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
+    shared.banner("SQS", PKG_VERSION, &[]);
 
-    println!();
+    let client = shared.ssm();
 
-    if verbose {
-        println!("SQS client version: {}", PKG_VERSION);
-        println!(
-            "Region:             {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!();
-    }
-
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let params = paginate(&pages, move |token, max_results| {
+        let request = client
+            .describe_parameters()
+            .set_next_token(token)
+            .set_max_results(max_results);
+        async move {
+            request
+                .send()
+                .await
+                .map(|resp| Page::with_token(resp.parameters.unwrap_or_default(), resp.next_token))
+        }
+    })
+    .try_collect()
+    .await?;
 
-    println!("Parameter names:");
+    let mut listing = Listing::new(&["Name", "Type", "Version", "LastModifiedDate"]);
+    for param in &params {
+        listing.push(vec![
+            param.name.as_deref().into(),
+            param.r#type.as_ref().map(|t| t.as_str()).into(),
+            param.version.into(),
+            date_time(param.last_modified_date.as_ref()),
+        ]);
+    }
 
-    let resp = client.describe_parameters().send().await?;
+    shared.print(&listing, || {
+        println!("Parameter names:");
 
-    for param in resp.parameters.unwrap().iter() {
-        println!("  {}", param.name.as_deref().unwrap_or_default());
-    }
+        for param in params.iter() {
+            println!("  {}", param.name.as_deref().unwrap_or_default());
+        }
+    });
 
     Ok(())
 }
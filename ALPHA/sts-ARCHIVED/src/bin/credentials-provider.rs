@@ -3,8 +3,8 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
+use alpha_common::CommonOpt;
 use aws_auth::{CredentialsError, ProvideCredentials};
-use aws_types::region::ProvideRegion;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime};
 use structopt::StructOpt;
@@ -12,49 +12,31 @@ use sts::Credentials;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The default AWS Region.
-    #[structopt(short, long)]
-    default_region: Option<String>,
-
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 /// Implements a basic version of ProvideCredentials with AWS STS
 /// and lists the tables in the region based on those credentials.
 /// # Arguments
 ///
-/// * `[-d DEFAULT-REGION]` - The Region in which the client is created.
+/// * `[-r REGION]` - The Region in which the client is created.
 ///    If not supplied, uses the value of the **AWS_REGION** environment variable.
 ///    If the environment variable is not set, defaults to **us-west-2**.
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), dynamodb::Error> {
-    tracing_subscriber::fmt::init();
-
-    let Opt {
-        default_region,
-        verbose,
-    } = Opt::from_args();
+    let Opt { common } = Opt::from_args();
 
-    let region = default_region
-        .as_ref()
-        .map(|region| dynamodb::Region::new(region.clone()))
-        .or_else(|| aws_types::region::default_provider().region())
-        .unwrap_or_else(|| dynamodb::Region::new("us-west-2"));
+    let shared = common.load().await;
 
-    println!();
-
-    if verbose {
-        println!("STS version:      {}", sts::PKG_VERSION);
-        println!("DynamoDB version: {}", dynamodb::PKG_VERSION);
-        println!("Region:           {:?}", &region);
-        println!();
-    }
+    shared.banner(
+        "STS",
+        sts::PKG_VERSION,
+        &[("DynamoDB client version", &dynamodb::PKG_VERSION)],
+    );
 
-    let config = sts::Config::builder().region(region).build();
-    let client = sts::Client::from_conf(config);
+    let client = shared.sts();
 
     let sts_provider = StsCredentialsProvider {
         client,
@@ -63,7 +45,8 @@ async fn main() -> Result<(), dynamodb::Error> {
 
     sts_provider.spawn_refresh_loop().await;
 
-    let dynamodb_conf = dynamodb::Config::builder()
+    let dynamodb_conf = shared
+        .dynamodb_config()
         .credentials_provider(sts_provider)
         .build();
 
@@ -3,29 +3,24 @@
  * SPDX-License-Identifier: Apache-2.0.
  */
 
+use alpha_common::CommonOpt;
 use async_stream::stream;
-use aws_config::meta::region::RegionProviderChain;
 use aws_sdk_transcribestreaming::model::{
     AudioEvent, AudioStream, LanguageCode, MediaEncoding, TranscriptResultStream,
 };
-use aws_sdk_transcribestreaming::{Blob, Client, Error, Region, PKG_VERSION};
+use aws_sdk_transcribestreaming::{Blob, Error, PKG_VERSION};
 use bytes::BufMut;
 use std::time::Duration;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// The AWS Region.
-    #[structopt(short, long)]
-    region: Option<String>,
-
     /// The name of the audio file.
     #[structopt(short, long)]
     audio_file: String,
 
-    /// Whether to display additional information.
-    #[structopt(short, long)]
-    verbose: bool,
+    #[structopt(flatten)]
+    common: CommonOpt,
 }
 
 const CHUNK_SIZE: usize = 8192;
@@ -41,35 +36,20 @@ const CHUNK_SIZE: usize = 8192;
 /// * `[-v]` - Whether to display additional information.
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    tracing_subscriber::fmt::init();
+    let Opt { audio_file, common } = Opt::from_args();
 
-    let Opt {
-        region,
-        audio_file,
-        verbose,
-    } = Opt::from_args();
+    let shared = common.load().await;
 
-    let region_provider = RegionProviderChain::first_try(region.map(Region::new))
-        .or_default_provider()
-        .or_else(Region::new("us-west-2"));
-
-    println!();
-
-    if verbose {
-        println!("Transcribe client version: {}", PKG_VERSION);
-        println!(
-            "Region:                    {}",
-            region_provider.region().await.unwrap().as_ref()
-        );
-        println!("Audio filename:            {}", &audio_file);
-        println!();
-    }
+    shared.banner(
+        "Transcribe",
+        PKG_VERSION,
+        &[("Audio filename", &audio_file)],
+    );
 
-    let shared_config = aws_config::from_env().region(region_provider).load().await;
-    let client = Client::new(&shared_config);
+    let client = shared.transcribestreaming();
 
     let input_stream = stream! {
-        let pcm = pcm_data(&*audio_file);
+        let pcm = pcm_data(&audio_file);
         for chunk in pcm.chunks(CHUNK_SIZE) {
             // Sleeping isn't necessary, but emphasizes the streaming aspect of this
             tokio::time::sleep(Duration::from_millis(100)).await;
@@ -91,9 +71,9 @@ async fn main() -> Result<(), Error> {
         match event {
             TranscriptResultStream::TranscriptEvent(transcript_event) => {
                 let transcript = transcript_event.transcript.unwrap();
-                for result in transcript.results.unwrap_or_else(|| Vec::new()) {
+                for result in transcript.results.unwrap_or_else(Vec::new) {
                     if result.is_partial {
-                        if verbose {
+                        if shared.verbose() {
                             println!("Partial: {:?}", result);
                         }
                     } else {
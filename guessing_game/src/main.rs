@@ -14,7 +14,7 @@ fn main() {
     let mut num_tries: u32 = 0;
 
     loop {
-        num_tries = num_tries + 1;
+        num_tries += 1;
 
         println!("Enter your guess.");
 